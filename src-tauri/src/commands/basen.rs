@@ -0,0 +1,375 @@
+// Base32/45/58/62/85/N 命令背后的字节 <-> 文本编解码器
+//
+// 分两类:
+//   按位打包        Base32 各变体: 每个输出字符对应 5 个输入位。
+//   按位值          Base45 和 Base85 各变体按固定大小的分组（2 和 4 字节）处理；
+//                   Base58/62/N 把整个输入视为一个大端整数，无需填充，
+//                   但复杂度为 O(n²)。
+//
+// 解码器返回的 `Err` 信息直接面向用户。
+
+use sha2::{Digest, Sha256};
+
+// ==================== 大整数字母表（Base58、Base62、BaseN） ====================
+
+pub const BASE58_BITCOIN: &str = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+pub const BASE58_RIPPLE: &str = "rpshnaf39wBUDNEGHJKLM4PQRST7VWXYZ2bcdeCg65jkm8oFqi1tuvAxyz";
+pub const BASE58_FLICKR: &str = "123456789abcdefghijkmnopqrstuvwxyzABCDEFGHJKLMNPQRSTUVWXYZ";
+pub const BASE62: &str = "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+/// 检查用户提供的字母表能否用于 `encode_big`
+pub fn validate_alphabet(alphabet: &str) -> Result<(), String> {
+    let chars: Vec<char> = alphabet.chars().collect();
+    if !(2..=256).contains(&chars.len()) {
+        return Err("字母表长度必须在 2 到 256 之间".to_string());
+    }
+    for (i, c) in chars.iter().enumerate() {
+        if chars[..i].contains(c) {
+            return Err(format!("字母表包含重复字符: {}", c));
+        }
+    }
+    Ok(())
+}
+
+/// 把 `bytes` 作为一个大端整数按 `alphabet.len()` 进制编码，
+/// 每个前导零字节对应一个 `alphabet[0]`
+pub fn encode_big(bytes: &[u8], alphabet: &str) -> String {
+    let alphabet: Vec<char> = alphabet.chars().collect();
+    let base = alphabet.len() as u32;
+    let leading_zeros = bytes.iter().take_while(|&&b| b == 0).count();
+
+    // 目标进制下的小端数位
+    let mut digits: Vec<u8> = Vec::with_capacity(bytes.len() * 2);
+    for &byte in &bytes[leading_zeros..] {
+        let mut carry = byte as u32;
+        for digit in digits.iter_mut() {
+            carry += (*digit as u32) << 8;
+            *digit = (carry % base) as u8;
+            carry /= base;
+        }
+        while carry > 0 {
+            digits.push((carry % base) as u8);
+            carry /= base;
+        }
+    }
+
+    let mut result = String::with_capacity(leading_zeros + digits.len());
+    result.extend(std::iter::repeat_n(alphabet[0], leading_zeros));
+    result.extend(digits.iter().rev().map(|&d| alphabet[d as usize]));
+    result
+}
+
+pub fn decode_big(input: &str, alphabet: &str, name: &str) -> Result<Vec<u8>, String> {
+    if input.is_empty() {
+        return Err("输入不能为空".to_string());
+    }
+
+    let alphabet: Vec<char> = alphabet.chars().collect();
+    let base = alphabet.len() as u32;
+    let leading_zeros = input.chars().take_while(|&c| c == alphabet[0]).count();
+
+    // 小端字节
+    let mut bytes: Vec<u8> = Vec::with_capacity(input.len());
+    for c in input.chars().skip(leading_zeros) {
+        let value = alphabet
+            .iter()
+            .position(|&a| a == c)
+            .ok_or_else(|| format!("无效的{}字符: {}", name, c))?;
+
+        let mut carry = value as u32;
+        for byte in bytes.iter_mut() {
+            carry += *byte as u32 * base;
+            *byte = (carry & 0xff) as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            bytes.push((carry & 0xff) as u8);
+            carry >>= 8;
+        }
+    }
+
+    let mut result = vec![0u8; leading_zeros];
+    result.extend(bytes.iter().rev());
+    Ok(result)
+}
+
+/// Base58Check: 追加 SHA256(SHA256(payload)) 的前四个字节
+pub fn append_checksum(payload: &[u8]) -> Vec<u8> {
+    let mut out = payload.to_vec();
+    out.extend_from_slice(&double_sha256(payload)[..4]);
+    out
+}
+
+/// 校验并去除 Base58Check 校验和
+pub fn strip_checksum(data: &[u8]) -> Result<Vec<u8>, String> {
+    if data.len() < 4 {
+        return Err("数据太短，不包含 Base58Check 校验和".to_string());
+    }
+    let (payload, checksum) = data.split_at(data.len() - 4);
+    let expected = &double_sha256(payload)[..4];
+    if checksum != expected {
+        return Err(format!(
+            "Base58Check 校验和不匹配: 期望 {}，实际 {}",
+            hex::encode(expected),
+            hex::encode(checksum)
+        ));
+    }
+    Ok(payload.to_vec())
+}
+
+fn double_sha256(data: &[u8]) -> [u8; 32] {
+    Sha256::digest(Sha256::digest(data)).into()
+}
+
+// ==================== Base32 ====================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Base32Spec {
+    pub alphabet: &'static [u8; 32],
+    /// 除非关闭，否则用 `=` 把输出填充到 8 个字符的整数倍
+    pub padded: bool,
+    pub lowercase: bool,
+}
+
+pub const BASE32_RFC4648: Base32Spec = Base32Spec {
+    alphabet: b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567",
+    padded: true,
+    lowercase: false,
+};
+pub const BASE32_HEX: Base32Spec = Base32Spec {
+    alphabet: b"0123456789ABCDEFGHIJKLMNOPQRSTUV",
+    padded: true,
+    lowercase: false,
+};
+pub const BASE32_CROCKFORD: Base32Spec = Base32Spec {
+    alphabet: b"0123456789ABCDEFGHJKMNPQRSTVWXYZ",
+    padded: false,
+    lowercase: false,
+};
+pub const BASE32_ZBASE32: Base32Spec = Base32Spec {
+    alphabet: b"ybndrfg8ejkmcpqxot1uwisza345h769",
+    padded: false,
+    lowercase: true,
+};
+
+pub fn base32_encode(bytes: &[u8], spec: Base32Spec, padding: bool) -> String {
+    let mut out = String::with_capacity(bytes.len().div_ceil(5) * 8);
+    let mut buffer = 0u16;
+    let mut bits = 0;
+    for &byte in bytes {
+        buffer = (buffer << 8) | byte as u16;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            out.push(spec.alphabet[((buffer >> bits) & 0x1f) as usize] as char);
+        }
+    }
+    if bits > 0 {
+        out.push(spec.alphabet[((buffer << (5 - bits)) & 0x1f) as usize] as char);
+    }
+    if padding && spec.padded {
+        while !out.len().is_multiple_of(8) {
+            out.push('=');
+        }
+    }
+    out
+}
+
+pub fn base32_decode(input: &str, spec: Base32Spec) -> Result<Vec<u8>, String> {
+    let crockford = spec == BASE32_CROCKFORD;
+    let mut out = Vec::with_capacity(input.len() * 5 / 8);
+    let mut buffer = 0u16;
+    let mut bits = 0;
+    for c in input.trim_end_matches(|c: char| c == '=' || c.is_whitespace()).chars() {
+        if c.is_whitespace() || (crockford && c == '-') {
+            continue;
+        }
+        let c = match (crockford, c.to_ascii_uppercase()) {
+            // Crockford 把形近的字母映射为数字
+            (true, 'O') => '0',
+            (true, 'I' | 'L') => '1',
+            (_, upper) if !spec.lowercase => upper,
+            _ => c.to_ascii_lowercase(),
+        };
+        let value = spec
+            .alphabet
+            .iter()
+            .position(|&a| a as char == c)
+            .ok_or_else(|| format!("无效的Base32字符: {}", c))?;
+        buffer = (buffer << 5) | value as u16;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((buffer >> bits) as u8);
+        }
+    }
+    Ok(out)
+}
+
+// ==================== Base45（RFC 9285） ====================
+
+const BASE45: &[u8; 45] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ $%*+-./:";
+
+pub fn base45_encode(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len().div_ceil(2) * 3);
+    for chunk in bytes.chunks(2) {
+        let (mut n, len) = match chunk {
+            [a, b] => ((*a as u32) << 8 | *b as u32, 3),
+            [a] => (*a as u32, 2),
+            _ => unreachable!("chunks(2) yields one or two bytes"),
+        };
+        for _ in 0..len {
+            out.push(BASE45[(n % 45) as usize] as char);
+            n /= 45;
+        }
+    }
+    out
+}
+
+pub fn base45_decode(input: &str) -> Result<Vec<u8>, String> {
+    let values = input
+        .chars()
+        .map(|c| {
+            BASE45
+                .iter()
+                .position(|&a| a as char == c)
+                .map(|v| v as u32)
+                .ok_or_else(|| format!("无效的Base45字符: {}", c))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut out = Vec::with_capacity(values.len() * 2 / 3);
+    for chunk in values.chunks(3) {
+        match chunk {
+            [c, d, e] => {
+                let n = c + d * 45 + e * 45 * 45;
+                if n > 0xffff {
+                    return Err(format!("无效的Base45分组: 值 {} 超出范围", n));
+                }
+                out.extend_from_slice(&(n as u16).to_be_bytes());
+            }
+            [c, d] => {
+                let n = c + d * 45;
+                if n > 0xff {
+                    return Err(format!("无效的Base45分组: 值 {} 超出范围", n));
+                }
+                out.push(n as u8);
+            }
+            _ => return Err("Base45 长度无效，余下单个字符".to_string()),
+        }
+    }
+    Ok(out)
+}
+
+// ==================== Base85 ====================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Base85Kind {
+    /// Adobe/btoa Ascii85: `!`..`u`，全零分组写作 `z`
+    Ascii85,
+    /// ZeroMQ Z85
+    Z85,
+    /// RFC 1924 字母表，git 和 Python 的 `b85encode` 使用
+    Rfc1924,
+}
+
+const Z85: &[u8; 85] = b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ.-:+=^!/*?&<>()[]{}@%$#";
+const RFC1924: &[u8; 85] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz!#$%&()*+-;<=>?@^_`{|}~";
+
+impl Base85Kind {
+    fn digit(self, value: u32) -> char {
+        match self {
+            Base85Kind::Ascii85 => (b'!' + value as u8) as char,
+            Base85Kind::Z85 => Z85[value as usize] as char,
+            Base85Kind::Rfc1924 => RFC1924[value as usize] as char,
+        }
+    }
+
+    fn value(self, c: char) -> Option<u32> {
+        match self {
+            Base85Kind::Ascii85 => ('!'..='u').contains(&c).then(|| c as u32 - '!' as u32),
+            Base85Kind::Z85 => Z85.iter().position(|&a| a as char == c).map(|v| v as u32),
+            Base85Kind::Rfc1924 => RFC1924.iter().position(|&a| a as char == c).map(|v| v as u32),
+        }
+    }
+}
+
+/// 按 4 字节分组编码。末尾不足的分组补零，输出截断为 `n + 1` 个字符，
+/// 这是 Z85 和 RFC 1924 处理长度不是 4 的倍数的输入时的常见扩展。
+pub fn base85_encode(bytes: &[u8], kind: Base85Kind, delimiters: bool) -> String {
+    let mut out = String::with_capacity(bytes.len().div_ceil(4) * 5 + 4);
+    if delimiters {
+        out.push_str("<~");
+    }
+    for chunk in bytes.chunks(4) {
+        let mut group = [0u8; 4];
+        group[..chunk.len()].copy_from_slice(chunk);
+        let n = u32::from_be_bytes(group);
+        if kind == Base85Kind::Ascii85 && n == 0 && chunk.len() == 4 {
+            out.push('z');
+            continue;
+        }
+        let mut digits = [0u32; 5];
+        let mut rest = n;
+        for digit in digits.iter_mut().rev() {
+            *digit = rest % 85;
+            rest /= 85;
+        }
+        for &digit in &digits[..chunk.len() + 1] {
+            out.push(kind.digit(digit));
+        }
+    }
+    if delimiters {
+        out.push_str("~>");
+    }
+    out
+}
+
+pub fn base85_decode(input: &str, kind: Base85Kind) -> Result<Vec<u8>, String> {
+    let mut body = input.trim();
+    if kind == Base85Kind::Ascii85 {
+        body = body.strip_prefix("<~").unwrap_or(body);
+        body = body.strip_suffix("~>").unwrap_or(body);
+    }
+
+    let mut out = Vec::with_capacity(body.len() * 4 / 5);
+    let mut group = Vec::with_capacity(5);
+    let flush = |group: &[u32], out: &mut Vec<u8>| -> Result<(), String> {
+        let len = group.len();
+        // 用最大的数位补齐不足的分组，再丢弃同样多的字节
+        let mut n: u64 = 0;
+        for i in 0..5 {
+            n = n * 85 + *group.get(i).unwrap_or(&84) as u64;
+        }
+        if n > u32::MAX as u64 {
+            return Err("Base85 分组超出 32 位范围".to_string());
+        }
+        out.extend_from_slice(&(n as u32).to_be_bytes()[..len - 1]);
+        Ok(())
+    };
+
+    for c in body.chars() {
+        if c.is_whitespace() {
+            continue;
+        }
+        if kind == Base85Kind::Ascii85 && c == 'z' {
+            if !group.is_empty() {
+                return Err("'z' 只能出现在分组边界".to_string());
+            }
+            out.extend_from_slice(&[0; 4]);
+            continue;
+        }
+        let value = kind.value(c).ok_or_else(|| format!("无效的Base85字符: {}", c))?;
+        group.push(value);
+        if group.len() == 5 {
+            flush(&group, &mut out)?;
+            group.clear();
+        }
+    }
+    match group.len() {
+        0 => {}
+        1 => return Err("Base85 长度无效，末尾分组只有一个字符".to_string()),
+        _ => flush(&group, &mut out)?,
+    }
+    Ok(out)
+}
@@ -0,0 +1,222 @@
+// 编码类命令共用的字节级输入输出
+//
+// 编解码器只处理原始字节；这里的类型决定字节从哪里来、结果如何交回，
+// 任何编解码器都可以接收二进制数据（密钥、哈希、文件），也可以与其他
+// 编解码器串联，中间不必经过 UTF-8 字符串。
+//
+// 前端传入的形式:
+//
+//   input:  "plain text"                                 UTF-8 文本
+//           { "type": "text", "value": "…", "charset": "gbk" }
+//           { "type": "hex", "value": "de ad be ef" }
+//           { "type": "base64", "value": "3q2+7w==" }
+//           { "type": "file", "path": "/tmp/key.bin" }
+//
+//   output: { "type": "text", "charset": "gbk" }         （默认 UTF-8）
+//           { "type": "hex", "uppercase": true, "separator": " " }
+//           { "type": "base64" }
+//           { "type": "file", "path": "/tmp/out.bin" }
+
+use std::borrow::Cow;
+use std::io::Read;
+
+use base64::engine::general_purpose::{STANDARD, STANDARD_NO_PAD, URL_SAFE_NO_PAD};
+use base64::Engine as _;
+use encoding_rs::{Encoding, UTF_16BE, UTF_16LE, UTF_8};
+use serde::Deserialize;
+
+/// 编解码器输入字节的来源，裸字符串视为 UTF-8 文本
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum ByteInput {
+    Plain(String),
+    Typed(TypedInput),
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum TypedInput {
+    Text { value: String, charset: Option<String> },
+    Hex { value: String },
+    Base64 { value: String },
+    File { path: String },
+}
+
+/// 编解码器输出字节的返回方式
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum ByteOutput {
+    Text {
+        charset: Option<String>,
+    },
+    Hex {
+        #[serde(default)]
+        uppercase: bool,
+        separator: Option<String>,
+    },
+    Base64,
+    File {
+        path: String,
+    },
+}
+
+impl Default for ByteOutput {
+    fn default() -> Self {
+        ByteOutput::Text { charset: None }
+    }
+}
+
+impl ByteInput {
+    /// 原始输入字节，文本按其字符集编码
+    pub fn into_bytes(self) -> Result<Vec<u8>, String> {
+        match self {
+            ByteInput::Plain(value) => Ok(value.into_bytes()),
+            ByteInput::Typed(TypedInput::Text { value, charset }) => {
+                encode_text(&value, charset.as_deref()).map(Cow::into_owned)
+            }
+            ByteInput::Typed(TypedInput::Hex { value }) => decode_hex(&value),
+            ByteInput::Typed(TypedInput::Base64 { value }) => decode_base64(&value),
+            ByteInput::Typed(TypedInput::File { path }) => {
+                std::fs::read(&path).map_err(|e| format!("读取文件失败 {}: {}", path, e))
+            }
+        }
+    }
+
+    /// 最多读取 `limit` 个输入字节，并返回是否还有更多。文件只读到该长度为止。
+    pub fn into_prefix(self, limit: usize) -> Result<(Vec<u8>, bool), String> {
+        let mut bytes = match self {
+            ByteInput::Typed(TypedInput::File { path }) => {
+                let file = std::fs::File::open(&path).map_err(|e| format!("读取文件失败 {}: {}", path, e))?;
+                let mut bytes = Vec::new();
+                file.take(limit as u64 + 1)
+                    .read_to_end(&mut bytes)
+                    .map_err(|e| format!("读取文件失败 {}: {}", path, e))?;
+                bytes
+            }
+            other => other.into_bytes()?,
+        };
+        let truncated = bytes.len() > limit;
+        bytes.truncate(limit);
+        Ok((bytes, truncated))
+    }
+
+    /// 以文本形式取得输入，供按字符处理的编解码器使用。二进制来源按 UTF-8 读取
+    /// （带 BOM 时改用 UTF-16）。
+    pub fn into_text(self) -> Result<String, String> {
+        match self {
+            ByteInput::Plain(value) | ByteInput::Typed(TypedInput::Text { value, .. }) => Ok(value),
+            other => {
+                let bytes = other.into_bytes()?;
+                let encoding = Encoding::for_bom(&bytes).map_or(UTF_8, |(encoding, _)| encoding);
+                decode_text(&bytes, encoding)
+            }
+        }
+    }
+}
+
+impl ByteOutput {
+    /// 按要求的形式输出 `bytes`。输出到文件时写入字节，改为返回一段简短说明。
+    pub fn write(&self, bytes: &[u8]) -> Result<String, String> {
+        match self {
+            ByteOutput::Text { charset } => decode_text(bytes, lookup_charset(charset.as_deref())?),
+            ByteOutput::Hex { uppercase, separator } => Ok(encode_hex(bytes, *uppercase, separator.as_deref().unwrap_or(""))),
+            ByteOutput::Base64 => Ok(STANDARD.encode(bytes)),
+            ByteOutput::File { path } => {
+                std::fs::write(path, bytes).map_err(|e| format!("写入文件失败 {}: {}", path, e))?;
+                Ok(format!("已写入 {} 字节到 {}", bytes.len(), path))
+            }
+        }
+    }
+
+    /// 输出文本结果。文本输出原样返回，其他形式使用其 UTF-8 字节。
+    pub fn write_text(&self, text: String) -> Result<String, String> {
+        match self {
+            ByteOutput::Text { .. } => Ok(text),
+            _ => self.write(text.as_bytes()),
+        }
+    }
+}
+
+/// 解析 WHATWG 编码标签（`utf-8`、`gbk`、`shift_jis` 等）
+pub fn lookup_charset(label: Option<&str>) -> Result<&'static Encoding, String> {
+    match label.map(str::trim) {
+        None | Some("") => Ok(UTF_8),
+        Some(label) => Encoding::for_label(label.as_bytes()).ok_or_else(|| format!("不支持的字符集: {}", label)),
+    }
+}
+
+/// 以 `charset` 编码 `text`，遇到无法表示的字符时报错
+pub fn encode_text<'a>(text: &'a str, charset: Option<&str>) -> Result<Cow<'a, [u8]>, String> {
+    let encoding = lookup_charset(charset)?;
+    // encoding_rs 只能解码 UTF-16，其编码器会回退到 UTF-8
+    if encoding == UTF_16LE || encoding == UTF_16BE {
+        let le = encoding == UTF_16LE;
+        let bytes = text
+            .encode_utf16()
+            .flat_map(|unit| if le { unit.to_le_bytes() } else { unit.to_be_bytes() })
+            .collect();
+        return Ok(Cow::Owned(bytes));
+    }
+    let (bytes, _, unmappable) = encoding.encode(text);
+    if unmappable {
+        return Err(format!("文本包含 {} 无法表示的字符", encoding.name()));
+    }
+    Ok(bytes)
+}
+
+/// 以 `encoding` 解码 `bytes`，遇到非法序列时报错，而不是替换为 U+FFFD
+pub fn decode_text(bytes: &[u8], encoding: &'static Encoding) -> Result<String, String> {
+    let bytes = match Encoding::for_bom(bytes) {
+        Some((bom, len)) if bom == encoding => &bytes[len..],
+        _ => bytes,
+    };
+    encoding
+        .decode_without_bom_handling_and_without_replacement(bytes)
+        .map(Cow::into_owned)
+        .ok_or_else(|| format!("结果不是有效的 {} 文本，可改用十六进制或 Base64 输出", encoding.name()))
+}
+
+/// 解析十六进制，忽略空白、`:`/`-` 分隔符和 `0x` 前缀
+pub fn decode_hex(input: &str) -> Result<Vec<u8>, String> {
+    let mut digits = String::with_capacity(input.len());
+    for group in input.split(|c: char| c.is_whitespace() || c == ':' || c == '-' || c == ',') {
+        let group = group
+            .strip_prefix("0x")
+            .or_else(|| group.strip_prefix("0X"))
+            .unwrap_or(group);
+        digits.push_str(group);
+    }
+    if let Some(c) = digits.chars().find(|c| !c.is_ascii_hexdigit()) {
+        return Err(format!("无效的十六进制字符: {}", c));
+    }
+    if !digits.len().is_multiple_of(2) {
+        return Err("十六进制字符串长度必须是偶数".to_string());
+    }
+    hex::decode(&digits).map_err(|e| format!("十六进制解码失败: {}", e))
+}
+
+pub fn encode_hex(bytes: &[u8], uppercase: bool, separator: &str) -> String {
+    let mut out = String::with_capacity(bytes.len() * (2 + separator.len()));
+    for (i, byte) in bytes.iter().enumerate() {
+        if i > 0 {
+            out.push_str(separator);
+        }
+        if uppercase {
+            out.push_str(&format!("{:02X}", byte));
+        } else {
+            out.push_str(&format!("{:02x}", byte));
+        }
+    }
+    out
+}
+
+/// 解析标准或 URL 安全的 Base64，可带或不带填充，忽略空白
+pub fn decode_base64(input: &str) -> Result<Vec<u8>, String> {
+    let compact: String = input.chars().filter(|c| !c.is_whitespace()).collect();
+    if compact.contains(['-', '_']) {
+        URL_SAFE_NO_PAD.decode(compact.trim_end_matches('='))
+    } else {
+        STANDARD_NO_PAD.decode(compact.trim_end_matches('='))
+    }
+    .map_err(|e| format!("Base64 解码失败: {}", e))
+}
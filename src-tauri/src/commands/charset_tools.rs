@@ -0,0 +1,576 @@
+// 字符集检测与转码（GBK/GB18030/Big5/Shift_JIS/EUC-KR/UTF-16 …）
+//
+// 检测顺序：BOM → 纯 ASCII → 合法 UTF-8 → UTF-16 零字节特征 → chardetng
+// 统计检测。chardetng 只给出结论不给分数，置信度由本模块按解码结果估算：
+// 解码无错误、非 ASCII 字符落在该编码常见的 Unicode 区块内的比例越高、
+// 样本越长，置信度越高。候选列表列出所有能无错解码样本的编码。
+//
+// 转码先按源编码解码（无效字节序列替换为 U+FFFD 并记录偏移），再按目标
+// 编码逐段编码；目标编码无法表示的字符按 `on_unmappable` 处理并汇总报告。
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use chardetng::{EncodingDetector, Iso2022JpDetection, Utf8Detection};
+use encoding_rs::{
+    CoderResult, DecoderResult, Encoding, EncoderResult, BIG5, EUC_JP, EUC_KR, GB18030, GBK, SHIFT_JIS, UTF_16BE, UTF_16LE,
+    UTF_8, WINDOWS_1252,
+};
+use serde::{Deserialize, Serialize};
+
+use super::byte_io::{self, ByteInput, ByteOutput};
+
+/// 检测时最多读取的字节数
+const SAMPLE_LIMIT: usize = 1024 * 1024;
+/// 报告中最多列出的不同字符 / 无效序列数
+const MAX_REPORTED: usize = 100;
+const PREVIEW_CHARS: usize = 200;
+
+/// 参与打分的候选编码（UTF-16 由零字节特征单独判断，随机字节按 UTF-16
+/// 解码也常落在 CJK 区块，不适合按区块打分）
+const CANDIDATES: [&Encoding; 6] = [GB18030, BIG5, SHIFT_JIS, EUC_JP, EUC_KR, WINDOWS_1252];
+
+#[derive(Serialize)]
+pub struct CharsetDetection {
+    pub encoding: String,
+    /// 0.0 ~ 1.0
+    pub confidence: f64,
+    /// bom / ascii / utf-8 / utf-16 / statistical
+    pub method: String,
+    pub has_bom: bool,
+    pub candidates: Vec<CharsetCandidate>,
+    /// 按检测结果解码的前若干字符
+    pub preview: String,
+    pub sample_bytes: usize,
+    pub truncated: bool,
+}
+
+#[derive(Serialize)]
+pub struct CharsetCandidate {
+    pub encoding: String,
+    pub confidence: f64,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum UnmappableMode {
+    /// 替换为 `?`
+    #[default]
+    Replace,
+    /// 替换为 HTML 数字字符引用 `&#NNNN;`
+    Ncr,
+    /// 遇到第一个无法表示的字符即报错
+    Fail,
+}
+
+#[derive(Serialize)]
+pub struct UnmappableChar {
+    pub character: String,
+    pub code_point: String,
+    pub count: usize,
+    /// 首次出现的位置（行、列从 1 开始，列按字符计）
+    pub line: usize,
+    pub column: usize,
+}
+
+#[derive(Serialize)]
+pub struct MalformedSequence {
+    pub offset: usize,
+    pub bytes: String,
+}
+
+#[derive(Serialize)]
+pub struct TranscodeReport {
+    pub from: String,
+    pub to: String,
+    /// 未指定源编码时为自动检测的置信度
+    pub detected_confidence: Option<f64>,
+    pub input_bytes: usize,
+    pub output_bytes: usize,
+    pub unmappable_count: usize,
+    pub unmappable: Vec<UnmappableChar>,
+    pub malformed_count: usize,
+    pub malformed: Vec<MalformedSequence>,
+}
+
+#[derive(Serialize)]
+pub struct TranscodeResult {
+    pub output: String,
+    #[serde(flatten)]
+    pub report: TranscodeReport,
+}
+
+#[derive(Serialize)]
+pub struct FileTranscodeResult {
+    pub input_path: String,
+    pub output_path: Option<String>,
+    pub success: bool,
+    pub error: Option<String>,
+    #[serde(flatten)]
+    pub report: Option<TranscodeReport>,
+}
+
+// ==================== 命令 ====================
+
+/// 检测文本、字节或文件的字符集
+#[tauri::command]
+pub fn detect_charset(input: ByteInput) -> Result<CharsetDetection, String> {
+    let (bytes, truncated) = input.into_prefix(SAMPLE_LIMIT)?;
+    if bytes.is_empty() {
+        return Err("输入不能为空".to_string());
+    }
+    Ok(detect(&bytes, truncated))
+}
+
+/// 在任意两种 encoding_rs 支持的编码之间转换
+///
+/// `from` 为空时自动检测；`output` 默认按目标编码显示转换结果。
+#[tauri::command]
+pub fn transcode(
+    input: ByteInput,
+    from: Option<String>,
+    to: String,
+    on_unmappable: Option<UnmappableMode>,
+    bom: Option<bool>,
+    output: Option<ByteOutput>,
+) -> Result<TranscodeResult, String> {
+    let bytes = input.into_bytes()?;
+    let (converted, report) = transcode_bytes(
+        &bytes,
+        from.as_deref(),
+        &to,
+        on_unmappable.unwrap_or_default(),
+        bom.unwrap_or(false),
+    )?;
+    let output = output.unwrap_or(ByteOutput::Text { charset: Some(to) });
+    Ok(TranscodeResult {
+        output: output.write(&converted)?,
+        report,
+    })
+}
+
+/// 批量转换文件
+///
+/// 指定 `output_dir` 时以原文件名写入该目录；否则写到原文件旁，文件名加上
+/// 目标编码，如 `data.utf-8.csv`。`overwrite` 为 true 时直接替换原文件；
+/// 为 false 时目标已存在则该文件失败。同一批中输出路径重复的文件也会失败。
+/// 单个文件失败不影响其余文件。
+#[tauri::command]
+pub async fn transcode_files(
+    paths: Vec<String>,
+    from: Option<String>,
+    to: String,
+    output_dir: Option<String>,
+    overwrite: Option<bool>,
+    on_unmappable: Option<UnmappableMode>,
+    bom: Option<bool>,
+) -> Result<Vec<FileTranscodeResult>, String> {
+    byte_io::lookup_charset(Some(&to))?;
+    if let Some(label) = &from {
+        byte_io::lookup_charset(Some(label))?;
+    }
+    if let Some(dir) = &output_dir {
+        std::fs::create_dir_all(dir).map_err(|e| format!("无法创建输出目录: {}", e))?;
+    }
+
+    tokio::task::spawn_blocking(move || {
+        let overwrite = overwrite.unwrap_or(false);
+        let mut targets = HashSet::new();
+        paths
+            .into_iter()
+            .map(|path| {
+                let target = output_path(&path, output_dir.as_deref(), &to, overwrite);
+                let result = if !targets.insert(target.clone()) {
+                    Err(format!("与同批其他文件的输出路径重复: {}", target.display()))
+                } else if !overwrite && target.exists() {
+                    Err(format!("目标文件已存在: {}", target.display()))
+                } else {
+                    transcode_file(
+                        &path,
+                        &target,
+                        from.as_deref(),
+                        &to,
+                        on_unmappable.unwrap_or_default(),
+                        bom.unwrap_or(false),
+                    )
+                };
+                match result {
+                    Ok(report) => FileTranscodeResult {
+                        input_path: path,
+                        output_path: Some(target.to_string_lossy().to_string()),
+                        success: true,
+                        error: None,
+                        report: Some(report),
+                    },
+                    Err(e) => FileTranscodeResult {
+                        input_path: path,
+                        output_path: None,
+                        success: false,
+                        error: Some(e),
+                        report: None,
+                    },
+                }
+            })
+            .collect()
+    })
+    .await
+    .map_err(|e| format!("任务执行失败: {}", e))
+}
+
+fn transcode_file(
+    input: &str,
+    target: &Path,
+    from: Option<&str>,
+    to: &str,
+    mode: UnmappableMode,
+    bom: bool,
+) -> Result<TranscodeReport, String> {
+    let bytes = std::fs::read(input).map_err(|e| format!("无法读取文件: {}", e))?;
+    let (converted, report) = transcode_bytes(&bytes, from, to, mode, bom)?;
+
+    // 先写临时文件再改名，覆盖原文件时中途失败也不会损坏原文件
+    let temp = target.with_extension("transcode.tmp");
+    std::fs::write(&temp, &converted).map_err(|e| format!("无法写入文件: {}", e))?;
+    std::fs::rename(&temp, target).map_err(|e| {
+        let _ = std::fs::remove_file(&temp);
+        format!("无法写入文件: {}", e)
+    })?;
+    Ok(report)
+}
+
+fn output_path(input: &str, output_dir: Option<&str>, to: &str, overwrite: bool) -> PathBuf {
+    let path = PathBuf::from(input);
+    if let Some(dir) = output_dir {
+        return Path::new(dir).join(path.file_name().unwrap_or_default());
+    }
+    if overwrite {
+        return path;
+    }
+    let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("output");
+    let name = match path.extension().and_then(|s| s.to_str()) {
+        Some(ext) => format!("{}.{}.{}", stem, to.to_lowercase(), ext),
+        None => format!("{}.{}", stem, to.to_lowercase()),
+    };
+    path.with_file_name(name)
+}
+
+// ==================== 检测 ====================
+
+pub fn detect(bytes: &[u8], truncated: bool) -> CharsetDetection {
+    let (encoding, confidence, method, has_bom) = guess(bytes, truncated);
+    let body = match Encoding::for_bom(bytes) {
+        Some((_, len)) if has_bom => &bytes[len..],
+        _ => bytes,
+    };
+
+    let mut candidates: Vec<CharsetCandidate> = CANDIDATES
+        .iter()
+        // GBK 与 GB18030 的解码结果相同，只保留一个
+        .filter(|&&candidate| candidate != encoding && !(candidate == GB18030 && encoding == GBK))
+        .filter_map(|&candidate| {
+            let text = decode_sample(body, candidate, truncated)?;
+            Some(CharsetCandidate {
+                encoding: candidate.name().to_string(),
+                confidence: score(&text, candidate),
+            })
+        })
+        .collect();
+    candidates.sort_by(|a, b| b.confidence.total_cmp(&a.confidence));
+    candidates.insert(
+        0,
+        CharsetCandidate {
+            encoding: encoding.name().to_string(),
+            confidence,
+        },
+    );
+
+    let preview = decode_sample(body, encoding, truncated)
+        .unwrap_or_else(|| encoding.decode_without_bom_handling(body).0.into_owned())
+        .chars()
+        .take(PREVIEW_CHARS)
+        .collect();
+
+    CharsetDetection {
+        encoding: encoding.name().to_string(),
+        confidence,
+        method: method.to_string(),
+        has_bom,
+        candidates,
+        preview,
+        sample_bytes: bytes.len(),
+        truncated,
+    }
+}
+
+/// 返回 (编码, 置信度, 检测方式, 是否带 BOM)
+fn guess(bytes: &[u8], truncated: bool) -> (&'static Encoding, f64, &'static str, bool) {
+    if let Some((encoding, _)) = Encoding::for_bom(bytes) {
+        return (encoding, 1.0, "bom", true);
+    }
+    if bytes.is_ascii() {
+        return (UTF_8, 1.0, "ascii", false);
+    }
+    match std::str::from_utf8(bytes) {
+        Ok(_) => return (UTF_8, 0.99, "utf-8", false),
+        // 样本在多字节字符中间被截断
+        Err(e) if truncated && e.error_len().is_none() => return (UTF_8, 0.99, "utf-8", false),
+        Err(_) => {}
+    }
+    if let Some(encoding) = utf16_by_zero_bytes(bytes) {
+        return (encoding, 0.9, "utf-16", false);
+    }
+
+    let mut detector = EncodingDetector::new(Iso2022JpDetection::Allow);
+    detector.feed(bytes, !truncated);
+    let encoding = detector.guess(None, Utf8Detection::Allow);
+    let confidence = decode_sample(bytes, encoding, truncated).map_or(0.0, |text| score(&text, encoding));
+    (encoding, confidence, "statistical", false)
+}
+
+/// 无 BOM 的 UTF-16：拉丁文本每两个字节就有一个零字节，且集中在同一侧
+fn utf16_by_zero_bytes(bytes: &[u8]) -> Option<&'static Encoding> {
+    if bytes.len() < 4 {
+        return None;
+    }
+    let pairs = bytes.len() / 2;
+    let even = bytes.iter().step_by(2).filter(|&&b| b == 0).count();
+    let odd = bytes.iter().skip(1).step_by(2).filter(|&&b| b == 0).count();
+    let ratio = |n: usize| n as f64 / pairs as f64;
+    if ratio(odd) > 0.3 && ratio(even) < 0.05 {
+        Some(UTF_16LE)
+    } else if ratio(even) > 0.3 && ratio(odd) < 0.05 {
+        Some(UTF_16BE)
+    } else {
+        None
+    }
+}
+
+/// 无替换地解码样本；有无效序列时返回 None
+fn decode_sample(bytes: &[u8], encoding: &'static Encoding, truncated: bool) -> Option<String> {
+    let mut decoder = encoding.new_decoder_without_bom_handling();
+    let mut text = String::with_capacity(decoder.max_utf8_buffer_length_without_replacement(bytes.len())?);
+    let (result, _) = decoder.decode_to_string_without_replacement(bytes, &mut text, !truncated);
+    match result {
+        DecoderResult::InputEmpty => Some(text),
+        _ => None,
+    }
+}
+
+/// 非 ASCII 字符中属于该编码常见区块的比例，按样本长度折减
+fn score(text: &str, encoding: &'static Encoding) -> f64 {
+    let mut total = 0usize;
+    let mut plausible = 0usize;
+    let mut prev_ascii = true;
+    for c in text.chars() {
+        if c.is_ascii() {
+            prev_ascii = true;
+            continue;
+        }
+        total += 1;
+        if is_plausible(c, prev_ascii, encoding) {
+            plausible += 1;
+        }
+        prev_ascii = false;
+    }
+    if total == 0 {
+        return 0.5;
+    }
+    // 非 ASCII 字符少于 32 个时结论不可靠
+    let weight = 0.6 + 0.4 * (total.min(32) as f64 / 32.0);
+    let confidence = plausible as f64 / total as f64 * weight;
+    (confidence * 100.0).round() / 100.0
+}
+
+fn is_plausible(c: char, prev_ascii: bool, encoding: &'static Encoding) -> bool {
+    let cp = c as u32;
+    let cjk_common = matches!(cp,
+        0x3000..=0x303F     // CJK 标点
+        | 0x4E00..=0x9FFF   // CJK 统一表意文字
+        | 0xFF00..=0xFFEF   // 全角字符
+        | 0x2010..=0x2027   // 常用标点（破折号、引号、省略号）
+    );
+    if encoding == GBK || encoding == GB18030 || encoding == BIG5 {
+        cjk_common
+    } else if encoding == SHIFT_JIS || encoding == EUC_JP {
+        cjk_common || matches!(cp, 0x3040..=0x30FF)
+    } else if encoding == EUC_KR {
+        cjk_common || matches!(cp, 0xAC00..=0xD7AF | 0x3130..=0x318F)
+    } else {
+        // 单字节西文编码：拉丁字母、常用符号，且很少连续出现；
+        // 多字节编码的文本按单字节解码时会得到成串的非 ASCII 字符
+        prev_ascii && matches!(cp, 0xA0..=0xFF | 0x2010..=0x2027 | 0x20AC | 0x2122)
+    }
+}
+
+// ==================== 转码 ====================
+
+/// 按行列定位字符，供报告使用
+struct Cursor {
+    offset: usize,
+    line: usize,
+    column: usize,
+}
+
+impl Cursor {
+    fn advance_to(&mut self, text: &str, offset: usize) {
+        for c in text[self.offset..offset].chars() {
+            if c == '\n' {
+                self.line += 1;
+                self.column = 1;
+            } else {
+                self.column += 1;
+            }
+        }
+        self.offset = offset;
+    }
+}
+
+pub fn transcode_bytes(
+    bytes: &[u8],
+    from: Option<&str>,
+    to: &str,
+    mode: UnmappableMode,
+    bom: bool,
+) -> Result<(Vec<u8>, TranscodeReport), String> {
+    let target = byte_io::lookup_charset(Some(to))?;
+    let (source, detected_confidence) = match from {
+        Some(label) if !label.trim().is_empty() => (byte_io::lookup_charset(Some(label))?, None),
+        _ => {
+            let sample = &bytes[..bytes.len().min(SAMPLE_LIMIT)];
+            let detection = guess(sample, bytes.len() > SAMPLE_LIMIT);
+            (detection.0, Some(detection.1))
+        }
+    };
+    let body = match Encoding::for_bom(bytes) {
+        Some((encoding, len)) if encoding == source => &bytes[len..],
+        _ => bytes,
+    };
+
+    let (text, malformed_count, malformed) = decode_lossy(body, source, mode)?;
+    let mut out = Vec::with_capacity(text.len() + 3);
+    if bom {
+        out.extend_from_slice(match target.name() {
+            "UTF-8" => b"\xEF\xBB\xBF".as_slice(),
+            "UTF-16LE" => b"\xFF\xFE",
+            "UTF-16BE" => b"\xFE\xFF",
+            _ => b"",
+        });
+    }
+    let mut unmappable: Vec<UnmappableChar> = Vec::new();
+    let mut unmappable_count = 0;
+    encode_reporting(&text, target, mode, &mut out, |c, cursor| {
+        unmappable_count += 1;
+        if let Some(entry) = unmappable.iter_mut().find(|u| u.character.starts_with(c)) {
+            entry.count += 1;
+        } else if unmappable.len() < MAX_REPORTED {
+            unmappable.push(UnmappableChar {
+                character: c.to_string(),
+                code_point: format!("U+{:04X}", c as u32),
+                count: 1,
+                line: cursor.line,
+                column: cursor.column,
+            });
+        }
+    })?;
+
+    let report = TranscodeReport {
+        from: source.name().to_string(),
+        to: target.name().to_string(),
+        detected_confidence,
+        input_bytes: bytes.len(),
+        output_bytes: out.len(),
+        unmappable_count,
+        unmappable,
+        malformed_count,
+        malformed,
+    };
+    Ok((out, report))
+}
+
+/// 解码，无效序列替换为 U+FFFD 并记录（`Fail` 模式下直接报错）
+fn decode_lossy(
+    bytes: &[u8],
+    encoding: &'static Encoding,
+    mode: UnmappableMode,
+) -> Result<(String, usize, Vec<MalformedSequence>), String> {
+    let mut decoder = encoding.new_decoder_without_bom_handling();
+    let mut text = String::with_capacity(bytes.len() + bytes.len() / 2);
+    let mut malformed = Vec::new();
+    let mut count = 0;
+    let mut pos = 0;
+    loop {
+        let (result, read) = decoder.decode_to_string_without_replacement(&bytes[pos..], &mut text, true);
+        pos += read;
+        match result {
+            DecoderResult::InputEmpty => break,
+            DecoderResult::OutputFull => text.reserve(bytes.len() - pos + 16),
+            DecoderResult::Malformed(bad, extra) => {
+                let offset = pos - extra as usize - bad as usize;
+                let sequence = byte_io::encode_hex(&bytes[offset..offset + bad as usize], true, " ");
+                if mode == UnmappableMode::Fail {
+                    return Err(format!("第{}字节处存在无效的 {} 字节序列: {}", offset, encoding.name(), sequence));
+                }
+                count += 1;
+                if malformed.len() < MAX_REPORTED {
+                    malformed.push(MalformedSequence { offset, bytes: sequence });
+                }
+                text.push('\u{FFFD}');
+            }
+        }
+    }
+    Ok((text, count, malformed))
+}
+
+/// 编码 `text` 到 `out`，每遇到一个无法表示的字符调用一次 `report`
+fn encode_reporting(
+    text: &str,
+    encoding: &'static Encoding,
+    mode: UnmappableMode,
+    out: &mut Vec<u8>,
+    mut report: impl FnMut(char, &Cursor),
+) -> Result<(), String> {
+    // encoding_rs 的编码器不输出 UTF-16，UTF-16/UTF-8 也不存在无法表示的字符
+    if encoding == UTF_16LE || encoding == UTF_16BE || encoding == UTF_8 {
+        out.extend_from_slice(&byte_io::encode_text(text, Some(encoding.name()))?);
+        return Ok(());
+    }
+
+    let mut encoder = encoding.new_encoder();
+    let mut cursor = Cursor {
+        offset: 0,
+        line: 1,
+        column: 1,
+    };
+    let mut pos = 0;
+    loop {
+        out.reserve(text.len() - pos + 16);
+        let (result, read) = encoder.encode_from_utf8_to_vec_without_replacement(&text[pos..], out, true);
+        pos += read;
+        match result {
+            EncoderResult::InputEmpty => return Ok(()),
+            EncoderResult::OutputFull => {}
+            EncoderResult::Unmappable(c) => {
+                cursor.advance_to(text, pos - c.len_utf8());
+                if mode == UnmappableMode::Fail {
+                    return Err(format!(
+                        "第{}行第{}列: 字符 '{}' (U+{:04X}) 无法用 {} 表示",
+                        cursor.line,
+                        cursor.column,
+                        c,
+                        c as u32,
+                        encoding.name()
+                    ));
+                }
+                report(c, &cursor);
+                let replacement = match mode {
+                    UnmappableMode::Ncr => format!("&#{};", c as u32),
+                    _ => "?".to_string(),
+                };
+                // 经编码器写出替换文本，ISO-2022-JP 等有状态编码才能正确切换
+                out.reserve(replacement.len() + 8);
+                let (result, _, _) = encoder.encode_from_utf8_to_vec(&replacement, out, false);
+                debug_assert!(result == CoderResult::InputEmpty);
+            }
+        }
+    }
+}
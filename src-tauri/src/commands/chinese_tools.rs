@@ -0,0 +1,445 @@
+// 中文文本工具：汉字转拼音、简繁转换、全角/半角转换、分词与词频统计。
+//
+// 拼音先用 jieba 分词再逐词取音：整词在多音词表（pinyin_phrases）中时按
+// 词表读音，否则在词内按最长匹配查词表，剩下的字取 pinyin 字库的首个读音。
+// 词表只收常见多音词，`heteronym` 会列出每个字的全部读音供核对。
+//
+// 简繁转换用 zhconv 内置的 OpenCC / MediaWiki 词表，按词转换（如 软件 →
+// 軟體 仅在台湾正体下）。
+
+use std::collections::HashMap;
+
+use jieba_rs::{Jieba, TokenizeMode};
+use once_cell::sync::Lazy;
+use pinyin::PINYIN_MAP;
+use serde::{Deserialize, Serialize};
+use zhconv::{zhconv, Variant};
+
+use super::byte_io::{ByteInput, ByteOutput};
+use super::pinyin_phrases::{MAX_PHRASE_CHARS, PHRASES};
+
+/// 全局分词器，search.rs 建索引也用这一个实例
+pub static JIEBA: Lazy<Jieba> = Lazy::new(Jieba::new);
+
+/// 声母表，双字母声母在前
+const INITIALS: [&str; 21] = [
+    "zh", "ch", "sh", "b", "p", "m", "f", "d", "t", "n", "l", "g", "k", "h", "j", "q", "x", "r", "z", "c", "s",
+];
+
+/// 带调字母 → (基本字母, 声调)
+const TONE_MARKS: [(char, char, u8); 28] = [
+    ('ā', 'a', 1),
+    ('á', 'a', 2),
+    ('ǎ', 'a', 3),
+    ('à', 'a', 4),
+    ('ē', 'e', 1),
+    ('é', 'e', 2),
+    ('ě', 'e', 3),
+    ('è', 'e', 4),
+    ('ī', 'i', 1),
+    ('í', 'i', 2),
+    ('ǐ', 'i', 3),
+    ('ì', 'i', 4),
+    ('ō', 'o', 1),
+    ('ó', 'o', 2),
+    ('ǒ', 'o', 3),
+    ('ò', 'o', 4),
+    ('ū', 'u', 1),
+    ('ú', 'u', 2),
+    ('ǔ', 'u', 3),
+    ('ù', 'u', 4),
+    ('ǖ', 'ü', 1),
+    ('ǘ', 'ü', 2),
+    ('ǚ', 'ü', 3),
+    ('ǜ', 'ü', 4),
+    ('ń', 'n', 2),
+    ('ň', 'n', 3),
+    ('ǹ', 'n', 4),
+    ('ḿ', 'm', 2),
+];
+
+// ==================== 拼音 ====================
+
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum PinyinStyle {
+    /// 声调符号：`zhōng`
+    #[default]
+    Tone,
+    /// 数字声调：`zhong1`，ü 写作 v，轻声不标
+    ToneNumber,
+    /// 不带声调：`zhong`
+    Plain,
+    /// 首字母：`z`
+    FirstLetter,
+    /// 声母：`zh`，零声母为空
+    Initials,
+}
+
+#[derive(Serialize)]
+pub struct PinyinResult {
+    pub output: String,
+    pub words: Vec<PinyinWord>,
+}
+
+#[derive(Serialize)]
+pub struct PinyinWord {
+    pub word: String,
+    /// 每个汉字一个音节，非汉字词为空
+    pub pinyin: Vec<String>,
+    /// 读音来自多音词表
+    pub from_phrase: bool,
+    /// 每个汉字的全部读音（仅在 `heteronym` 为 true 时给出）
+    pub alternatives: Option<Vec<Vec<String>>>,
+}
+
+/// 汉字转拼音，按 jieba 分词处理多音字；`separator` 为音节间的分隔符，默认空格
+#[tauri::command]
+pub fn to_pinyin(
+    input: ByteInput,
+    style: Option<PinyinStyle>,
+    separator: Option<String>,
+    heteronym: Option<bool>,
+) -> Result<PinyinResult, String> {
+    let text = input.into_text()?;
+    let style = style.unwrap_or_default();
+    let separator = separator.unwrap_or_else(|| " ".to_string());
+    let heteronym = heteronym.unwrap_or(false);
+
+    let mut words = Vec::new();
+    let mut pieces: Vec<String> = Vec::new();
+    for word in JIEBA.cut(&text, true) {
+        if word.trim().is_empty() {
+            continue;
+        }
+        let (syllables, from_phrase) = word_pinyin(word);
+        if syllables.is_empty() {
+            // 标点紧贴前一个音节，其他非汉字内容原样作为一段
+            let is_punctuation = !word.chars().any(char::is_alphanumeric);
+            match pieces.last_mut() {
+                Some(last) if is_punctuation => last.push_str(word),
+                _ => pieces.push(word.to_string()),
+            }
+        } else {
+            // 零声母音节在声母风格下为空，不占位
+            pieces.extend(
+                syllables
+                    .iter()
+                    .map(|s| style_syllable(s, style))
+                    .filter(|s| !s.is_empty()),
+            );
+        }
+        words.push(PinyinWord {
+            word: word.to_string(),
+            pinyin: syllables.iter().map(|s| style_syllable(s, style)).collect(),
+            from_phrase,
+            alternatives: (heteronym && !syllables.is_empty()).then(|| {
+                word.chars()
+                    .filter_map(readings)
+                    .map(|all| {
+                        let mut styled: Vec<String> = Vec::new();
+                        for reading in all {
+                            let s = style_syllable(reading, style);
+                            if !styled.contains(&s) {
+                                styled.push(s);
+                            }
+                        }
+                        styled
+                    })
+                    .collect()
+            }),
+        });
+    }
+
+    Ok(PinyinResult {
+        output: pieces.join(&separator),
+        words,
+    })
+}
+
+/// 一个分词结果的带调拼音；第二项表示是否用到了词表
+fn word_pinyin(word: &str) -> (Vec<String>, bool) {
+    if let Some(reading) = phrase(word) {
+        return (reading.split(' ').map(str::to_string).collect(), true);
+    }
+    let chars: Vec<char> = word.chars().collect();
+    let mut syllables = Vec::new();
+    let mut from_phrase = false;
+    let mut i = 0;
+    while i < chars.len() {
+        // 词内最长匹配，单字条目只用于整词
+        let matched = (2..=MAX_PHRASE_CHARS.min(chars.len() - i)).rev().find_map(|len| {
+            let candidate: String = chars[i..i + len].iter().collect();
+            phrase(&candidate).map(|reading| (len, reading))
+        });
+        if let Some((len, reading)) = matched {
+            syllables.extend(reading.split(' ').map(str::to_string));
+            from_phrase = true;
+            i += len;
+        } else {
+            if let Some(all) = readings(chars[i]) {
+                syllables.push(all[0].to_string());
+            }
+            i += 1;
+        }
+    }
+    (syllables, from_phrase)
+}
+
+fn phrase(word: &str) -> Option<&'static str> {
+    PHRASES
+        .binary_search_by(|(entry, _)| entry.as_bytes().cmp(word.as_bytes()))
+        .ok()
+        .map(|index| PHRASES[index].1)
+}
+
+/// 单字的全部带调读音，常用读音在前
+fn readings(c: char) -> Option<Vec<&'static str>> {
+    PINYIN_MAP
+        .binary_search_by_key(&c, |&(k, _)| k)
+        .ok()
+        .map(|index| PINYIN_MAP[index].1.split(',').collect())
+}
+
+fn style_syllable(syllable: &str, style: PinyinStyle) -> String {
+    let mut plain = String::with_capacity(syllable.len());
+    let mut tone = 0;
+    for c in syllable.chars() {
+        match TONE_MARKS.iter().find(|(mark, _, _)| *mark == c) {
+            Some(&(_, base, t)) => {
+                plain.push(base);
+                tone = t;
+            }
+            None => plain.push(c),
+        }
+    }
+    match style {
+        PinyinStyle::Tone => syllable.to_string(),
+        PinyinStyle::ToneNumber if tone > 0 => format!("{}{}", plain.replace('ü', "v"), tone),
+        PinyinStyle::ToneNumber | PinyinStyle::Plain => plain.replace('ü', "v"),
+        PinyinStyle::FirstLetter => plain.chars().next().map(String::from).unwrap_or_default(),
+        PinyinStyle::Initials => INITIALS
+            .iter()
+            .find(|initial| plain.starts_with(**initial))
+            .map(|initial| initial.to_string())
+            .unwrap_or_default(),
+    }
+}
+
+// ==================== 简繁转换 ====================
+
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ChineseVariant {
+    /// 简体
+    #[default]
+    Simplified,
+    /// 繁体（不区分地区用词）
+    Traditional,
+    /// 台湾正体，含台湾用词
+    Taiwan,
+    /// 香港繁体
+    HongKong,
+    /// 大陆简体，含大陆用词
+    Mainland,
+}
+
+#[tauri::command]
+pub fn convert_chinese(
+    input: ByteInput,
+    target: Option<ChineseVariant>,
+    output: Option<ByteOutput>,
+) -> Result<String, String> {
+    let text = input.into_text()?;
+    let variant = match target.unwrap_or_default() {
+        ChineseVariant::Simplified => Variant::ZhHans,
+        ChineseVariant::Traditional => Variant::ZhHant,
+        ChineseVariant::Taiwan => Variant::ZhTW,
+        ChineseVariant::HongKong => Variant::ZhHK,
+        ChineseVariant::Mainland => Variant::ZhCN,
+    };
+    output.unwrap_or_default().write_text(zhconv(&text, variant))
+}
+
+// ==================== 全角/半角 ====================
+
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum WidthTarget {
+    #[default]
+    Half,
+    Full,
+}
+
+/// 全角与半角 ASCII 互转。转半角时 `punctuation` 为 true 会把中文标点
+/// （。、「」“”《》【】……）也换成对应的 ASCII 标点
+#[tauri::command]
+pub fn convert_width(
+    input: ByteInput,
+    target: Option<WidthTarget>,
+    punctuation: Option<bool>,
+    output: Option<ByteOutput>,
+) -> Result<String, String> {
+    let text = input.into_text()?;
+    let punctuation = punctuation.unwrap_or(false);
+    let mut result = String::with_capacity(text.len());
+    for c in text.chars() {
+        match target.unwrap_or_default() {
+            WidthTarget::Half => match c {
+                '\u{3000}' => result.push(' '),
+                '\u{FF01}'..='\u{FF5E}' => result.push(char::from_u32(c as u32 - 0xFEE0).unwrap_or(c)),
+                _ if punctuation => match c {
+                    '。' => result.push('.'),
+                    '、' => result.push(','),
+                    '「' | '」' | '“' | '”' | '『' | '』' => result.push('"'),
+                    '‘' | '’' => result.push('\''),
+                    '《' | '〈' => result.push('<'),
+                    '》' | '〉' => result.push('>'),
+                    '【' | '〔' => result.push('['),
+                    '】' | '〕' => result.push(']'),
+                    '…' => result.push_str("..."),
+                    '—' => result.push('-'),
+                    '·' => result.push('.'),
+                    _ => result.push(c),
+                },
+                _ => result.push(c),
+            },
+            WidthTarget::Full => match c {
+                ' ' => result.push('\u{3000}'),
+                '!'..='~' => result.push(char::from_u32(c as u32 + 0xFEE0).unwrap_or(c)),
+                _ => result.push(c),
+            },
+        }
+    }
+    output.unwrap_or_default().write_text(result)
+}
+
+// ==================== 分词与词频 ====================
+
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum SegmentMode {
+    /// 精确模式
+    #[default]
+    Default,
+    /// 搜索引擎模式，在精确模式基础上对长词再切分
+    Search,
+}
+
+#[derive(Serialize)]
+pub struct WordToken {
+    pub word: String,
+    /// 按字符计的起止位置
+    pub start: usize,
+    pub end: usize,
+    /// 词性标注（仅精确模式且 `with_tags` 为 true 时给出）
+    pub tag: Option<String>,
+}
+
+/// 分词，`hmm` 控制是否用 HMM 识别新词（默认开启）
+#[tauri::command]
+pub async fn segment_text(
+    input: ByteInput,
+    mode: Option<SegmentMode>,
+    hmm: Option<bool>,
+    with_tags: Option<bool>,
+) -> Result<Vec<WordToken>, String> {
+    tokio::task::spawn_blocking(move || {
+        let text = input.into_text()?;
+        let hmm = hmm.unwrap_or(true);
+        let mode = mode.unwrap_or_default();
+        let tokenize_mode = match mode {
+            SegmentMode::Default => TokenizeMode::Default,
+            SegmentMode::Search => TokenizeMode::Search,
+        };
+        // tag 与精确模式的 tokenize 用同一次切分，结果一一对应
+        let tags: Vec<String> = if with_tags.unwrap_or(false) && mode == SegmentMode::Default {
+            JIEBA.tag(&text, hmm).into_iter().map(|t| t.tag.to_string()).collect()
+        } else {
+            Vec::new()
+        };
+        Ok(JIEBA
+            .tokenize(&text, tokenize_mode, hmm)
+            .into_iter()
+            .enumerate()
+            .filter(|(_, token)| !token.word.trim().is_empty())
+            .map(|(i, token)| WordToken {
+                word: token.word.to_string(),
+                start: token.start,
+                end: token.end,
+                tag: tags.get(i).cloned(),
+            })
+            .collect())
+    })
+    .await
+    .map_err(|e| format!("任务执行失败: {}", e))?
+}
+
+#[derive(Serialize)]
+pub struct WordFrequency {
+    /// 参与统计的词数（已去掉标点、停用词和过短的词）
+    pub total_words: usize,
+    pub unique_words: usize,
+    pub words: Vec<WordCount>,
+}
+
+#[derive(Serialize)]
+pub struct WordCount {
+    pub word: String,
+    pub count: usize,
+    /// 占 `total_words` 的比例
+    pub ratio: f64,
+}
+
+/// 词频统计。`min_length` 按字符计，默认 2（过滤"的""了"等单字）；
+/// 英文词不区分大小写
+#[tauri::command]
+pub async fn word_frequency(
+    input: ByteInput,
+    top: Option<usize>,
+    min_length: Option<usize>,
+    stop_words: Option<Vec<String>>,
+) -> Result<WordFrequency, String> {
+    tokio::task::spawn_blocking(move || {
+        let text = input.into_text()?;
+        let min_length = min_length.unwrap_or(2);
+        let stop_words: Vec<String> = stop_words
+            .unwrap_or_default()
+            .iter()
+            .map(|w| w.trim().to_lowercase())
+            .collect();
+
+        let mut counts: HashMap<String, usize> = HashMap::new();
+        let mut total_words = 0;
+        for word in JIEBA.cut(&text, true) {
+            let word = word.trim().to_lowercase();
+            if word.chars().count() < min_length
+                || !word.chars().any(char::is_alphanumeric)
+                || stop_words.contains(&word)
+            {
+                continue;
+            }
+            total_words += 1;
+            *counts.entry(word).or_insert(0) += 1;
+        }
+
+        let unique_words = counts.len();
+        let mut words: Vec<WordCount> = counts
+            .into_iter()
+            .map(|(word, count)| WordCount {
+                word,
+                count,
+                ratio: (count as f64 / total_words as f64 * 10000.0).round() / 10000.0,
+            })
+            .collect();
+        words.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.word.cmp(&b.word)));
+        words.truncate(top.unwrap_or(100));
+        Ok(WordFrequency {
+            total_words,
+            unique_words,
+            words,
+        })
+    })
+    .await
+    .map_err(|e| format!("任务执行失败: {}", e))?
+}
@@ -0,0 +1,332 @@
+// Gzip / zlib / deflate / brotli / zstd 压缩与解压
+//
+// 输入输出沿用 byte_io 的模型：可以直接给 Base64（API 响应、Kafka 消息里
+// 常见的形式）、十六进制、文本或文件路径，结果同样可以输出为 Base64、
+// 十六进制、文本或写入文件。压缩默认输出 Base64，解压默认输出文本。
+// 每次调用都返回大小、压缩比和耗时，`compare_compression` 在同一输入上
+// 依次跑所有算法便于比较。
+
+use std::io::{Read, Write};
+use std::time::Instant;
+
+use flate2::read::{DeflateDecoder, GzDecoder, ZlibDecoder};
+use flate2::write::{DeflateEncoder, GzEncoder, ZlibEncoder};
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
+
+use super::byte_io::{ByteInput, ByteOutput};
+
+/// 解压结果上限，防止压缩炸弹占满内存。结果还要再编码成 Base64 或文本
+/// 返回前端，256 MB 已远超界面能处理的大小。
+const MAX_DECOMPRESSED: u64 = 256 * 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CompressionAlgorithm {
+    Gzip,
+    Zlib,
+    /// 不带头部的原始 DEFLATE 流
+    Deflate,
+    Brotli,
+    Zstd,
+}
+
+const ALL_ALGORITHMS: [CompressionAlgorithm; 5] = [
+    CompressionAlgorithm::Gzip,
+    CompressionAlgorithm::Zlib,
+    CompressionAlgorithm::Deflate,
+    CompressionAlgorithm::Brotli,
+    CompressionAlgorithm::Zstd,
+];
+
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum CompressionPreset {
+    Fast,
+    #[default]
+    Default,
+    Best,
+}
+
+impl CompressionAlgorithm {
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            CompressionAlgorithm::Gzip => "gzip",
+            CompressionAlgorithm::Zlib => "zlib",
+            CompressionAlgorithm::Deflate => "deflate",
+            CompressionAlgorithm::Brotli => "brotli",
+            CompressionAlgorithm::Zstd => "zstd",
+        }
+    }
+
+    /// 支持的级别范围
+    fn levels(self) -> (i32, i32) {
+        match self {
+            CompressionAlgorithm::Gzip | CompressionAlgorithm::Zlib | CompressionAlgorithm::Deflate => (0, 9),
+            CompressionAlgorithm::Brotli => (0, 11),
+            CompressionAlgorithm::Zstd => (1, 22),
+        }
+    }
+
+    fn preset_level(self, preset: CompressionPreset) -> i32 {
+        let (min, max) = self.levels();
+        match (preset, self) {
+            (CompressionPreset::Fast, _) => min.max(1),
+            (CompressionPreset::Best, _) => max,
+            (CompressionPreset::Default, CompressionAlgorithm::Brotli) => 6,
+            (CompressionPreset::Default, CompressionAlgorithm::Zstd) => 3,
+            (CompressionPreset::Default, _) => 6,
+        }
+    }
+
+    fn check_level(self, level: i32) -> Result<i32, String> {
+        let (min, max) = self.levels();
+        if (min..=max).contains(&level) {
+            Ok(level)
+        } else {
+            Err(format!("压缩级别超出范围: {} 支持 {}-{}", self.name(), min, max))
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct CompressionResult {
+    output: String,
+    algorithm: CompressionAlgorithm,
+    level: Option<i32>,
+    input_size: usize,
+    output_size: usize,
+    /// 压缩后大小 / 原始大小
+    ratio: f64,
+    elapsed_ms: f64,
+}
+
+#[derive(Serialize)]
+pub struct CompressionStat {
+    algorithm: CompressionAlgorithm,
+    level: i32,
+    compressed_size: usize,
+    ratio: f64,
+    compress_ms: f64,
+    decompress_ms: f64,
+}
+
+/// 压缩，`level` 缺省时使用各算法的常用默认级别；默认输出 Base64
+#[tauri::command]
+pub async fn compress(
+    input: ByteInput,
+    algorithm: CompressionAlgorithm,
+    level: Option<i32>,
+    output: Option<ByteOutput>,
+) -> Result<CompressionResult, String> {
+    tokio::task::spawn_blocking(move || {
+        let data = input.into_bytes()?;
+        let level = match level {
+            Some(level) => algorithm.check_level(level)?,
+            None => algorithm.preset_level(CompressionPreset::Default),
+        };
+        let started = Instant::now();
+        let compressed = compress_bytes(&data, algorithm, level)?;
+        let elapsed_ms = millis(started);
+        Ok(CompressionResult {
+            output: output.unwrap_or(ByteOutput::Base64).write(&compressed)?,
+            algorithm,
+            level: Some(level),
+            input_size: data.len(),
+            output_size: compressed.len(),
+            ratio: ratio(compressed.len(), data.len()),
+            elapsed_ms,
+        })
+    })
+    .await
+    .map_err(|e| format!("任务执行失败: {}", e))?
+}
+
+/// 解压，`algorithm` 缺省时按魔数识别 gzip/zlib/zstd，再依次尝试 brotli 和 deflate
+#[tauri::command]
+pub async fn decompress(
+    input: ByteInput,
+    algorithm: Option<CompressionAlgorithm>,
+    output: Option<ByteOutput>,
+) -> Result<CompressionResult, String> {
+    tokio::task::spawn_blocking(move || {
+        let data = input.into_bytes()?;
+        if data.is_empty() {
+            return Err("输入不能为空".to_string());
+        }
+        let started = Instant::now();
+        let (algorithm, decompressed) = match algorithm {
+            Some(algorithm) => (algorithm, decompress_bytes(&data, algorithm)?),
+            None => decompress_detect(&data)?,
+        };
+        let elapsed_ms = millis(started);
+        Ok(CompressionResult {
+            output: output.unwrap_or_default().write(&decompressed)?,
+            algorithm,
+            level: None,
+            input_size: data.len(),
+            output_size: decompressed.len(),
+            ratio: ratio(data.len(), decompressed.len()),
+            elapsed_ms,
+        })
+    })
+    .await
+    .map_err(|e| format!("任务执行失败: {}", e))?
+}
+
+/// 用同一输入比较各算法的压缩比、压缩与解压耗时
+#[tauri::command]
+pub async fn compare_compression(
+    input: ByteInput,
+    preset: Option<CompressionPreset>,
+) -> Result<Vec<CompressionStat>, String> {
+    tokio::task::spawn_blocking(move || {
+        let data = input.into_bytes()?;
+        let preset = preset.unwrap_or_default();
+        ALL_ALGORITHMS
+            .iter()
+            .map(|&algorithm| {
+                let level = algorithm.preset_level(preset);
+                let started = Instant::now();
+                let compressed = compress_bytes(&data, algorithm, level)?;
+                let compress_ms = millis(started);
+                let started = Instant::now();
+                decompress_bytes(&compressed, algorithm)?;
+                let decompress_ms = millis(started);
+                Ok(CompressionStat {
+                    algorithm,
+                    level,
+                    compressed_size: compressed.len(),
+                    ratio: ratio(compressed.len(), data.len()),
+                    compress_ms,
+                    decompress_ms,
+                })
+            })
+            .collect()
+    })
+    .await
+    .map_err(|e| format!("任务执行失败: {}", e))?
+}
+
+pub fn compress_bytes(data: &[u8], algorithm: CompressionAlgorithm, level: i32) -> Result<Vec<u8>, String> {
+    let err = |e: std::io::Error| format!("{} 压缩失败: {}", algorithm.name(), e);
+    let flate_level = Compression::new(level as u32);
+    match algorithm {
+        CompressionAlgorithm::Gzip => {
+            let mut encoder = GzEncoder::new(Vec::new(), flate_level);
+            encoder.write_all(data).map_err(err)?;
+            encoder.finish().map_err(err)
+        }
+        CompressionAlgorithm::Zlib => {
+            let mut encoder = ZlibEncoder::new(Vec::new(), flate_level);
+            encoder.write_all(data).map_err(err)?;
+            encoder.finish().map_err(err)
+        }
+        CompressionAlgorithm::Deflate => {
+            let mut encoder = DeflateEncoder::new(Vec::new(), flate_level);
+            encoder.write_all(data).map_err(err)?;
+            encoder.finish().map_err(err)
+        }
+        CompressionAlgorithm::Brotli => {
+            let mut out = Vec::new();
+            {
+                let mut writer = brotli::CompressorWriter::new(&mut out, 4096, level as u32, 22);
+                writer.write_all(data).map_err(err)?;
+            }
+            Ok(out)
+        }
+        CompressionAlgorithm::Zstd => zstd::stream::encode_all(data, level).map_err(err),
+    }
+}
+
+pub fn decompress_bytes(data: &[u8], algorithm: CompressionAlgorithm) -> Result<Vec<u8>, String> {
+    decompress_limited(data, algorithm, MAX_DECOMPRESSED)
+}
+
+/// 解压，结果超过 `limit` 字节时报错
+pub(crate) fn decompress_limited(data: &[u8], algorithm: CompressionAlgorithm, limit: u64) -> Result<Vec<u8>, String> {
+    let reader: Box<dyn Read + '_> = match algorithm {
+        CompressionAlgorithm::Gzip => Box::new(GzDecoder::new(data)),
+        CompressionAlgorithm::Zlib => Box::new(ZlibDecoder::new(data)),
+        CompressionAlgorithm::Deflate => Box::new(DeflateDecoder::new(data)),
+        CompressionAlgorithm::Brotli => Box::new(brotli::Decompressor::new(data, 4096)),
+        CompressionAlgorithm::Zstd => Box::new(
+            zstd::stream::read::Decoder::new(data).map_err(|e| format!("zstd 解压失败: {}", e))?,
+        ),
+    };
+    let mut out = Vec::new();
+    reader
+        .take(limit + 1)
+        .read_to_end(&mut out)
+        .map_err(|e| format!("{} 解压失败: {}", algorithm.name(), e))?;
+    if out.len() as u64 > limit {
+        return Err(format!("解压结果超过 {} MB 上限", limit / 1024 / 1024));
+    }
+    Ok(out)
+}
+
+fn decompress_detect(data: &[u8]) -> Result<(CompressionAlgorithm, Vec<u8>), String> {
+    let by_magic = match data {
+        [0x1f, 0x8b, ..] => Some(CompressionAlgorithm::Gzip),
+        [0x28, 0xb5, 0x2f, 0xfd, ..] => Some(CompressionAlgorithm::Zstd),
+        [cmf, flg, ..] if is_zlib_header(*cmf, *flg) => Some(CompressionAlgorithm::Zlib),
+        _ => None,
+    };
+    if let Some(algorithm) = by_magic {
+        return Ok((algorithm, decompress_bytes(data, algorithm)?));
+    }
+    // brotli 与原始 deflate 没有魔数，只能试解
+    for algorithm in [CompressionAlgorithm::Brotli, CompressionAlgorithm::Deflate] {
+        if let Ok(out) = decompress_bytes(data, algorithm) {
+            return Ok((algorithm, out));
+        }
+    }
+    Err("无法识别压缩格式，请指定算法".to_string())
+}
+
+/// zlib 头：CM 为 8（deflate），窗口不超过 32K，且 CMF/FLG 能被 31 整除
+pub(crate) fn is_zlib_header(cmf: u8, flg: u8) -> bool {
+    cmf & 0x0f == 8 && cmf >> 4 <= 7 && (u16::from(cmf) << 8 | u16::from(flg)).is_multiple_of(31)
+}
+
+fn ratio(part: usize, whole: usize) -> f64 {
+    if whole == 0 {
+        return 0.0;
+    }
+    (part as f64 / whole as f64 * 10000.0).round() / 10000.0
+}
+
+fn millis(started: Instant) -> f64 {
+    (started.elapsed().as_secs_f64() * 1_000_000.0).round() / 1000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip_all_algorithms() {
+        let data = "压缩往返测试 compression round trip ".repeat(200).into_bytes();
+        for algorithm in ALL_ALGORITHMS {
+            let level = algorithm.preset_level(CompressionPreset::Default);
+            let compressed = compress_bytes(&data, algorithm, level).unwrap();
+            assert!(compressed.len() < data.len(), "{}", algorithm.name());
+            assert_eq!(decompress_bytes(&compressed, algorithm).unwrap(), data, "{}", algorithm.name());
+            assert_eq!(decompress_detect(&compressed).unwrap().1, data, "{}", algorithm.name());
+        }
+    }
+
+    #[test]
+    fn rejects_output_over_limit() {
+        const LIMIT: u64 = 1024 * 1024;
+        let data = vec![0u8; LIMIT as usize + 1];
+        for algorithm in ALL_ALGORITHMS {
+            let level = algorithm.preset_level(CompressionPreset::Default);
+            let compressed = compress_bytes(&data, algorithm, level).unwrap();
+            let err = decompress_limited(&compressed, algorithm, LIMIT).unwrap_err();
+            assert_eq!(err, "解压结果超过 1 MB 上限", "{}", algorithm.name());
+            let exact = decompress_limited(&compressed, algorithm, LIMIT + 1).unwrap();
+            assert_eq!(exact.len(), data.len(), "{}", algorithm.name());
+        }
+    }
+}
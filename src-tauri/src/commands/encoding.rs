@@ -1,23 +1,92 @@
+// 所有命令的输入都是 `ByteInput`（文本/十六进制/Base64/文件），输出由可选的
+// `ByteOutput` 决定（默认文本），见 byte_io.rs。编码器作用于原始字节，
+// 因此可以处理任意二进制数据并相互串联。
+
+use base64::engine::general_purpose::{STANDARD, STANDARD_NO_PAD, URL_SAFE, URL_SAFE_NO_PAD};
+use base64::Engine as _;
+use serde::{Deserialize, Serialize};
+
+use super::basen;
+use super::html_codec::{self, EscapeMode, UnknownEntity};
+use super::magic::{self, DecodeLayer};
+use super::number_tools;
+use super::url_parts;
+use super::byte_io::{self, ByteInput, ByteOutput};
 
 // ==================== URL编码/解码 ====================
 
+/// URL 各组成部分对应的 RFC 3986 编码集
+#[derive(Debug, Clone, Copy, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum UrlComponent {
+    /// 除字母数字外全部编码（兼容旧行为）
+    #[default]
+    All,
+    /// 仅保留 unreserved 字符 `A-Z a-z 0-9 - . _ ~`
+    Unreserved,
+    Path,
+    PathSegment,
+    /// 查询参数的键或值
+    Query,
+    Fragment,
+    Userinfo,
+    /// application/x-www-form-urlencoded，空格编码为 `+`
+    Form,
+}
+
 #[tauri::command]
-pub fn url_encode(input: String) -> Result<String, String> {
-    Ok(percent_encoding::utf8_percent_encode(&input, percent_encoding::NON_ALPHANUMERIC).to_string())
+pub fn url_encode(input: ByteInput, component: Option<UrlComponent>, output: Option<ByteOutput>) -> Result<String, String> {
+    let bytes = input.into_bytes()?;
+    let encode = |set| percent_encoding::percent_encode(&bytes, set).to_string();
+    let encoded = match component.unwrap_or_default() {
+        UrlComponent::All => encode(percent_encoding::NON_ALPHANUMERIC),
+        UrlComponent::Unreserved => encode(url_parts::UNRESERVED),
+        UrlComponent::Path => encode(url_parts::PATH),
+        UrlComponent::PathSegment => encode(url_parts::PATH_SEGMENT),
+        UrlComponent::Query => encode(url_parts::QUERY_COMPONENT),
+        UrlComponent::Fragment => encode(url_parts::FRAGMENT),
+        UrlComponent::Userinfo => encode(url_parts::USERINFO),
+        UrlComponent::Form => url_parts::form_encode(&bytes),
+    };
+    output.unwrap_or_default().write_text(encoded)
 }
 
+/// `form` 为 true 时 `+` 解码为空格；非 UTF-8 结果可通过 `output` 指定字符集或改用十六进制
 #[tauri::command]
-pub fn url_decode(input: String) -> Result<String, String> {
-    percent_encoding::percent_decode_str(&input)
-        .decode_utf8()
+pub fn url_decode(input: ByteInput, form: Option<bool>, output: Option<ByteOutput>) -> Result<String, String> {
+    let mut text = input.into_text()?;
+    if form.unwrap_or(false) {
+        text = text.replace('+', " ");
+    }
+    let bytes: Vec<u8> = percent_encoding::percent_decode_str(&text).collect();
+    output
+        .unwrap_or_default()
+        .write(&bytes)
         .map_err(|e| format!("URL解码失败: {}", e))
-        .map(|s| s.to_string())
+}
+
+/// 拆分 URL：协议、用户信息、主机（含 IDNA 转换）、端口、路径段、查询参数、片段
+///
+/// 百分号转义按 `charset`（默认 UTF-8）解码，无效字节替换为 U+FFFD。
+#[tauri::command]
+pub fn parse_url(url: String, form: Option<bool>, charset: Option<String>) -> Result<url_parts::ParsedUrl, String> {
+    let encoding = byte_io::lookup_charset(charset.as_deref())?;
+    url_parts::parse(&url, form.unwrap_or(false), |bytes| {
+        encoding.decode_without_bom_handling(bytes).0.into_owned()
+    })
+}
+
+/// 由各组成部分（未编码）组装 URL，每部分使用对应的编码集
+#[tauri::command]
+pub fn build_url(parts: url_parts::UrlParts, form: Option<bool>) -> Result<String, String> {
+    url_parts::build(&parts, form.unwrap_or(false))
 }
 
 // ==================== Unicode转换 ====================
 
 #[tauri::command]
-pub fn unicode_to_chinese(input: String) -> Result<String, String> {
+pub fn unicode_to_chinese(input: ByteInput, output: Option<ByteOutput>) -> Result<String, String> {
+    let input = input.into_text()?;
     // 处理 \uXXXX 或 \u{XXXX} 格式的Unicode转义
     let mut result = String::new();
     let mut chars = input.chars().peekable();
@@ -58,14 +127,18 @@ pub fn unicode_to_chinese(input: String) -> Result<String, String> {
                             return Err("不完整的Unicode转义序列".to_string());
                         }
                     }
-                    if let Ok(code_point) = u32::from_str_radix(&hex, 16) {
-                        if let Some(ch) = char::from_u32(code_point) {
-                            result.push(ch);
-                        } else {
-                            return Err(format!("无效的Unicode码点: \\u{}", hex));
-                        }
+                    let mut code_point =
+                        u32::from_str_radix(&hex, 16).map_err(|_| format!("无效的十六进制数: {}", hex))?;
+                    // 辅助平面字符写成 UTF-16 代理对 \uD83D\uDE00
+                    if (0xD800..0xDC00).contains(&code_point) {
+                        let low = take_low_surrogate(&mut chars)
+                            .ok_or_else(|| format!("缺少低位代理项: \\u{}", hex))?;
+                        code_point = 0x10000 + ((code_point - 0xD800) << 10) + (low - 0xDC00);
+                    }
+                    if let Some(ch) = char::from_u32(code_point) {
+                        result.push(ch);
                     } else {
-                        return Err(format!("无效的十六进制数: {}", hex));
+                        return Err(format!("无效的Unicode码点: \\u{}", hex));
                     }
                 }
             } else {
@@ -76,408 +149,427 @@ pub fn unicode_to_chinese(input: String) -> Result<String, String> {
         }
     }
 
-    Ok(result)
+    output.unwrap_or_default().write_text(result)
+}
+
+/// 紧跟在高位代理项后的 `\uDC00`-`\uDFFF`，不匹配时不消耗输入
+fn take_low_surrogate(chars: &mut std::iter::Peekable<std::str::Chars>) -> Option<u32> {
+    let mut ahead = chars.clone();
+    if ahead.next() != Some('\\') || ahead.next() != Some('u') {
+        return None;
+    }
+    let hex: String = ahead.by_ref().take(4).collect();
+    let low = u32::from_str_radix(&hex, 16).ok().filter(|low| (0xDC00..0xE000).contains(low))?;
+    *chars = ahead;
+    Some(low)
 }
 
 #[tauri::command]
-pub fn chinese_to_unicode(input: String) -> Result<String, String> {
+pub fn chinese_to_unicode(input: ByteInput, output: Option<ByteOutput>) -> Result<String, String> {
+    let input = input.into_text()?;
     let mut result = String::new();
+    let mut units = [0u16; 2];
     for c in input.chars() {
         if c.is_ascii() {
             result.push(c);
         } else {
-            // 非ASCII字符转为 \uXXXX 格式
-            result.push_str(&format!("\\u{:04x}", c as u32));
+            // 非ASCII字符转为 \uXXXX 格式，辅助平面字符转为代理对
+            for unit in c.encode_utf16(&mut units) {
+                result.push_str(&format!("\\u{:04x}", unit));
+            }
         }
     }
-    Ok(result)
+    output.unwrap_or_default().write_text(result)
 }
 
 // ==================== Base58 编解码 ====================
 
-const BASE58_ALPHABET: &[u8] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+#[derive(Debug, Clone, Copy, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum Base58Alphabet {
+    #[default]
+    Bitcoin,
+    Ripple,
+    Flickr,
+}
 
-#[tauri::command]
-pub fn base58_encode(input: String) -> Result<String, String> {
-    // 将输入字符串转换为字节
-    let bytes = input.as_bytes();
-
-    // 计算结果字符串的长度
-    let leading_zeros = bytes.iter().take_while(|&&b| b == 0).count();
-    let size = (bytes.len() - leading_zeros) * 138 / 100 + 1;
-    let mut buffer = vec![0u8; size];
-    let high = buffer.len() - 1;
-
-    // 处理每个字节
-    for &byte in bytes {
-        let mut carry = byte as i32;
-        let mut i = high;
-
-        loop {
-            carry += 256 * buffer[i] as i32;
-            buffer[i] = (carry % 58) as u8;
-            carry /= 58;
-
-            if carry == 0 && i == 0 && byte == 0 {
-                break;
-            }
-            if i == 0 {
-                break;
-            }
-            i -= 1;
+impl Base58Alphabet {
+    fn chars(self) -> &'static str {
+        match self {
+            Base58Alphabet::Bitcoin => basen::BASE58_BITCOIN,
+            Base58Alphabet::Ripple => basen::BASE58_RIPPLE,
+            Base58Alphabet::Flickr => basen::BASE58_FLICKR,
         }
     }
+}
 
-    // 找到非零部分的起始位置
-    let mut result = String::new();
-    let start = buffer.iter().position(|&b| b != 0).unwrap_or(buffer.len());
-
-    // 添加前导的 '1'（对应前导零字节）
-    for _ in 0..leading_zeros {
-        result.push('1');
-    }
-
-    // 转换剩余部分
-    for &b in &buffer[start..] {
-        result.push(BASE58_ALPHABET[b as usize] as char);
+/// `check` 为 true 时按 Base58Check 追加 4 字节双 SHA-256 校验和
+#[tauri::command]
+pub fn base58_encode(
+    input: ByteInput,
+    alphabet: Option<Base58Alphabet>,
+    check: Option<bool>,
+    output: Option<ByteOutput>,
+) -> Result<String, String> {
+    let mut bytes = input.into_bytes()?;
+    if check.unwrap_or(false) {
+        bytes = basen::append_checksum(&bytes);
     }
-
-    Ok(result)
+    let encoded = basen::encode_big(&bytes, alphabet.unwrap_or_default().chars());
+    output.unwrap_or_default().write_text(encoded)
 }
 
+/// `check` 为 true 时校验并去掉末尾 4 字节 Base58Check 校验和
 #[tauri::command]
-pub fn base58_decode(input: String) -> Result<String, String> {
-    // 检查输入是否为空
-    if input.is_empty() {
-        return Err("输入不能为空".to_string());
+pub fn base58_decode(
+    input: ByteInput,
+    alphabet: Option<Base58Alphabet>,
+    check: Option<bool>,
+    output: Option<ByteOutput>,
+) -> Result<String, String> {
+    let mut bytes = basen::decode_big(input.into_text()?.trim(), alphabet.unwrap_or_default().chars(), "Base58")?;
+    if check.unwrap_or(false) {
+        bytes = basen::strip_checksum(&bytes)?;
     }
+    output.unwrap_or_default().write(&bytes)
+}
 
-    let bytes = input.as_bytes();
-    let leading_ones = bytes.iter().take_while(|&&b| b == b'1').count();
-    let size = (bytes.len() - leading_ones) * 138 / 100 + 1;
-    let mut buffer = vec![0u8; size];
-    let high = buffer.len() - 1;
-
-    for &byte in bytes {
-        let c = match byte {
-            b'1'..=b'9' => byte - b'1',
-            b'A'..=b'H' => byte - b'A' + 9,
-            b'J'..=b'N' => byte - b'J' + 17,
-            b'P'..=b'Z' => byte - b'P' + 22,
-            b'a'..=b'k' => byte - b'a' + 25,
-            b'm'..=b'z' => byte - b'm' + 31,
-            _ => return Err(format!("无效的Base58字符: {}", byte as char)),
-        };
-
-        let mut carry = c as i32;
-        let mut i = high;
-
-        loop {
-            carry += 58 * buffer[i] as i32;
-            buffer[i] = (carry % 256) as u8;
-            carry /= 256;
-
-            if carry == 0 && i == 0 && c == 0 {
-                break;
-            }
-            if i == 0 {
-                break;
-            }
-            i -= 1;
-        }
-    }
+// ==================== Base62 编解码 ====================
 
-    let mut result = Vec::new();
-    let start = buffer.iter().position(|&b| b != 0).unwrap_or(buffer.len());
+#[tauri::command]
+pub fn base62_encode(input: ByteInput, output: Option<ByteOutput>) -> Result<String, String> {
+    let bytes = input.into_bytes()?;
+    output.unwrap_or_default().write_text(basen::encode_big(&bytes, basen::BASE62))
+}
 
-    for _ in 0..leading_ones {
-        result.push(0);
-    }
+#[tauri::command]
+pub fn base62_decode(input: ByteInput, output: Option<ByteOutput>) -> Result<String, String> {
+    let bytes = basen::decode_big(input.into_text()?.trim(), basen::BASE62, "Base62")?;
+    output.unwrap_or_default().write(&bytes)
+}
 
-    for &b in &buffer[start..] {
-        result.push(b);
-    }
+// ==================== 自定义字母表 BaseN ====================
 
-    String::from_utf8(result).map_err(|e| format!("解码后不是有效的UTF-8字符串: {}", e))
+/// 以 `alphabet` 的长度为进制（2~256）做大数转换，前导零字节对应首字符
+#[tauri::command]
+pub fn base_n_encode(input: ByteInput, alphabet: String, output: Option<ByteOutput>) -> Result<String, String> {
+    basen::validate_alphabet(&alphabet)?;
+    let bytes = input.into_bytes()?;
+    output.unwrap_or_default().write_text(basen::encode_big(&bytes, &alphabet))
 }
 
-// ==================== Base62 编解码 ====================
+#[tauri::command]
+pub fn base_n_decode(input: ByteInput, alphabet: String, output: Option<ByteOutput>) -> Result<String, String> {
+    basen::validate_alphabet(&alphabet)?;
+    let bytes = basen::decode_big(input.into_text()?.trim(), &alphabet, "BaseN")?;
+    output.unwrap_or_default().write(&bytes)
+}
 
-const BASE62_ALPHABET: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+// ==================== Base64 编解码 ====================
 
+/// `url_safe` 使用 `-_` 字母表；`padding` 默认保留 `=`
 #[tauri::command]
-pub fn base62_encode(input: String) -> Result<String, String> {
-    let bytes = input.as_bytes();
-    let leading_zeros = bytes.iter().take_while(|&&b| b == 0).count();
-    let size = (bytes.len() - leading_zeros) * 146 / 100 + 1;
-    let mut buffer = vec![0u8; size];
-    let high = buffer.len() - 1;
-
-    for &byte in bytes {
-        let mut carry = byte as i32;
-        let mut i = high;
-
-        loop {
-            carry += 256 * buffer[i] as i32;
-            buffer[i] = (carry % 62) as u8;
-            carry /= 62;
-
-            if carry == 0 && i == 0 && byte == 0 {
-                break;
-            }
-            if i == 0 {
-                break;
-            }
-            i -= 1;
-        }
-    }
+pub fn base64_encode(
+    input: ByteInput,
+    url_safe: Option<bool>,
+    padding: Option<bool>,
+    output: Option<ByteOutput>,
+) -> Result<String, String> {
+    let bytes = input.into_bytes()?;
+    let encoded = match (url_safe.unwrap_or(false), padding.unwrap_or(true)) {
+        (false, true) => STANDARD.encode(&bytes),
+        (false, false) => STANDARD_NO_PAD.encode(&bytes),
+        (true, true) => URL_SAFE.encode(&bytes),
+        (true, false) => URL_SAFE_NO_PAD.encode(&bytes),
+    };
+    output.unwrap_or_default().write_text(encoded)
+}
 
-    let mut result = String::new();
-    let start = buffer.iter().position(|&b| b != 0).unwrap_or(buffer.len());
+/// 自动识别标准/URL 安全字母表，有无填充均可
+#[tauri::command]
+pub fn base64_decode(input: ByteInput, output: Option<ByteOutput>) -> Result<String, String> {
+    let bytes = byte_io::decode_base64(&input.into_text()?)?;
+    output.unwrap_or_default().write(&bytes)
+}
 
-    for _ in 0..leading_zeros {
-        result.push('0');
-    }
+// ==================== Base32 编解码 ====================
+
+#[derive(Debug, Clone, Copy, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum Base32Variant {
+    /// RFC 4648 标准字母表
+    #[default]
+    Rfc4648,
+    /// RFC 4648 base32hex
+    Hex,
+    Crockford,
+    Zbase32,
+}
 
-    for &b in &buffer[start..] {
-        result.push(BASE62_ALPHABET[b as usize] as char);
+impl Base32Variant {
+    fn spec(self) -> basen::Base32Spec {
+        match self {
+            Base32Variant::Rfc4648 => basen::BASE32_RFC4648,
+            Base32Variant::Hex => basen::BASE32_HEX,
+            Base32Variant::Crockford => basen::BASE32_CROCKFORD,
+            Base32Variant::Zbase32 => basen::BASE32_ZBASE32,
+        }
     }
-
-    Ok(result)
 }
 
+/// `padding` 仅对 RFC 4648 两种字母表有效，默认保留 `=`
 #[tauri::command]
-pub fn base62_decode(input: String) -> Result<String, String> {
-    if input.is_empty() {
-        return Err("输入不能为空".to_string());
-    }
-
-    let bytes = input.as_bytes();
-    let leading_zeros = bytes.iter().take_while(|&&b| b == b'0').count();
-    let size = (bytes.len() - leading_zeros) * 146 / 100 + 1;
-    let mut buffer = vec![0u8; size];
-    let high = buffer.len() - 1;
-
-    for &byte in bytes {
-        let c = match byte {
-            b'0'..=b'9' => byte - b'0',
-            b'A'..=b'Z' => byte - b'A' + 10,
-            b'a'..=b'z' => byte - b'a' + 36,
-            _ => return Err(format!("无效的Base62字符: {}", byte as char)),
-        };
-
-        let mut carry = c as i32;
-        let mut i = high;
-
-        loop {
-            carry += 62 * buffer[i] as i32;
-            buffer[i] = (carry % 256) as u8;
-            carry /= 256;
-
-            if carry == 0 && i == 0 && c == 0 {
-                break;
-            }
-            if i == 0 {
-                break;
-            }
-            i -= 1;
-        }
-    }
+pub fn base32_encode(
+    input: ByteInput,
+    variant: Option<Base32Variant>,
+    padding: Option<bool>,
+    output: Option<ByteOutput>,
+) -> Result<String, String> {
+    let bytes = input.into_bytes()?;
+    let encoded = basen::base32_encode(&bytes, variant.unwrap_or_default().spec(), padding.unwrap_or(true));
+    output.unwrap_or_default().write_text(encoded)
+}
 
-    let mut result = Vec::new();
-    let start = buffer.iter().position(|&b| b != 0).unwrap_or(buffer.len());
+#[tauri::command]
+pub fn base32_decode(input: ByteInput, variant: Option<Base32Variant>, output: Option<ByteOutput>) -> Result<String, String> {
+    let bytes = basen::base32_decode(&input.into_text()?, variant.unwrap_or_default().spec())?;
+    output.unwrap_or_default().write(&bytes)
+}
 
-    for _ in 0..leading_zeros {
-        result.push(0);
-    }
+// ==================== Base45 编解码 ====================
 
-    for &b in &buffer[start..] {
-        result.push(b);
-    }
+/// RFC 9285，常见于健康码等二维码载荷
+#[tauri::command]
+pub fn base45_encode(input: ByteInput, output: Option<ByteOutput>) -> Result<String, String> {
+    let bytes = input.into_bytes()?;
+    output.unwrap_or_default().write_text(basen::base45_encode(&bytes))
+}
 
-    String::from_utf8(result).map_err(|e| format!("解码后不是有效的UTF-8字符串: {}", e))
+#[tauri::command]
+pub fn base45_decode(input: ByteInput, output: Option<ByteOutput>) -> Result<String, String> {
+    // Base45 字母表包含空格，只去掉首尾换行
+    let bytes = basen::base45_decode(input.into_text()?.trim_matches(['\r', '\n']))?;
+    output.unwrap_or_default().write(&bytes)
 }
 
-// ==================== 十六进制转换 ====================
+// ==================== Base85 编解码 ====================
+
+#[derive(Debug, Clone, Copy, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum Base85Variant {
+    /// Adobe Ascii85
+    #[default]
+    Ascii85,
+    /// ZeroMQ Z85
+    Z85,
+    /// RFC 1924（git、Python b85encode）
+    Rfc1924,
+}
 
-#[tauri::command]
-pub fn string_to_hex(input: String) -> Result<String, String> {
-    // Single-pass build of "AA BB CC ..." from the input bytes.
-    let mut out = String::with_capacity(input.len() * 3);
-    for (i, byte) in input.as_bytes().iter().enumerate() {
-        if i > 0 {
-            out.push(' ');
+impl Base85Variant {
+    fn kind(self) -> basen::Base85Kind {
+        match self {
+            Base85Variant::Ascii85 => basen::Base85Kind::Ascii85,
+            Base85Variant::Z85 => basen::Base85Kind::Z85,
+            Base85Variant::Rfc1924 => basen::Base85Kind::Rfc1924,
         }
-        out.push_str(&format!("{:02X}", byte));
     }
-    Ok(out)
 }
 
+/// `delimiters` 为 true 时 Ascii85 输出带 `<~ ~>` 定界符
 #[tauri::command]
-pub fn hex_to_string(hex: String) -> Result<String, String> {
-    // 移除空格
-    let hex = hex.replace(" ", "").replace("\n", "").replace("\r", "");
+pub fn base85_encode(
+    input: ByteInput,
+    variant: Option<Base85Variant>,
+    delimiters: Option<bool>,
+    output: Option<ByteOutput>,
+) -> Result<String, String> {
+    let bytes = input.into_bytes()?;
+    let variant = variant.unwrap_or_default();
+    let delimiters = delimiters.unwrap_or(false) && matches!(variant, Base85Variant::Ascii85);
+    output
+        .unwrap_or_default()
+        .write_text(basen::base85_encode(&bytes, variant.kind(), delimiters))
+}
 
-    // 验证十六进制格式
-    if hex.len() % 2 != 0 {
-        return Err("十六进制字符串长度必须是偶数".to_string());
-    }
+#[tauri::command]
+pub fn base85_decode(input: ByteInput, variant: Option<Base85Variant>, output: Option<ByteOutput>) -> Result<String, String> {
+    let bytes = basen::base85_decode(&input.into_text()?, variant.unwrap_or_default().kind())?;
+    output.unwrap_or_default().write(&bytes)
+}
 
-    // 验证字符
-    for c in hex.chars() {
-        if !c.is_ascii_hexdigit() {
-            return Err(format!("无效的十六进制字符: {}", c));
-        }
-    }
+// ==================== 自动识别多层编码 ====================
 
-    // 解码
-    let bytes = hex::decode(&hex).map_err(|e| format!("十六进制解码失败: {}", e))?;
-    String::from_utf8(bytes).map_err(|e| format!("解码后不是有效的UTF-8字符串: {}", e))
+#[derive(Serialize)]
+pub struct MagicDecodeResult {
+    /// 每一步去掉的编码及其结果，按解码顺序
+    layers: Vec<DecodeLayer>,
+    /// 最终结果（文本，或二进制的十六进制预览）
+    output: String,
+    is_text: bool,
 }
 
-// ==================== HTML实体编解码 ====================
-
+/// 识别并逐层解开 URL、Base64、十六进制、\uXXXX、HTML 实体、Punycode、
+/// gzip/zlib、JWT 等编码，直到无法继续或达到 `max_depth`（默认 10）层
 #[tauri::command]
-pub fn html_encode(input: String) -> Result<String, String> {
-    let mut result = String::with_capacity(input.len() * 6);
-    for c in input.chars() {
-        match c {
-            '<' => result.push_str("&lt;"),
-            '>' => result.push_str("&gt;"),
-            '&' => result.push_str("&amp;"),
-            '"' => result.push_str("&quot;"),
-            '\'' => result.push_str("&#39;"),
-            _ => result.push(c),
-        }
+pub fn magic_decode(input: ByteInput, max_depth: Option<usize>) -> Result<MagicDecodeResult, String> {
+    let bytes = input.into_bytes()?;
+    if bytes.is_empty() {
+        return Err("输入不能为空".to_string());
     }
-    Ok(result)
+    let original = magic::layer("", &bytes);
+    let layers = magic::peel(bytes, max_depth.unwrap_or(10));
+    let last = layers.last().cloned().unwrap_or(original);
+    Ok(MagicDecodeResult {
+        layers,
+        output: last.output,
+        is_text: last.is_text,
+    })
 }
 
+// ==================== 十六进制转换 ====================
+
+/// 默认输出 `AA BB CC` 形式，可通过 `output` 改为其他格式
 #[tauri::command]
-pub fn html_decode(input: String) -> Result<String, String> {
-    // Single-pass scanner. &-sequences are decoded in-place into the output
-    // string; everything else is copied byte-for-byte.
-    const NAMED: &[(&str, &str)] = &[
-        ("&lt;", "<"),
-        ("&gt;", ">"),
-        ("&amp;", "&"),
-        ("&quot;", "\""),
-        ("&#39;", "'"),
-        ("&nbsp;", " "),
-    ];
-
-    let bytes = input.as_bytes();
-    let mut out = String::with_capacity(input.len());
-    let mut i = 0;
-    while i < bytes.len() {
-        if bytes[i] != b'&' {
-            out.push(bytes[i] as char);
-            i += 1;
-            continue;
-        }
+pub fn string_to_hex(input: ByteInput, output: Option<ByteOutput>) -> Result<String, String> {
+    let bytes = input.into_bytes()?;
+    output
+        .unwrap_or(ByteOutput::Hex {
+            uppercase: true,
+            separator: Some(" ".to_string()),
+        })
+        .write(&bytes)
+}
 
-        // Try named entities first.
-        let mut matched = false;
-        for (entity, replacement) in NAMED {
-            if bytes[i..].starts_with(entity.as_bytes()) {
-                out.push_str(replacement);
-                i += entity.len();
-                matched = true;
-                break;
-            }
-        }
-        if matched {
-            continue;
-        }
+#[tauri::command]
+pub fn hex_to_string(hex: ByteInput, output: Option<ByteOutput>) -> Result<String, String> {
+    let bytes = byte_io::decode_hex(&hex.into_text()?)?;
+    output.unwrap_or_default().write(&bytes)
+}
 
-        // Numeric entities: &#NN; (decimal) or &#xNN; (hex).
-        if bytes[i..].starts_with(b"&#") {
-            let after_amp = i + 2;
-            let (digits_start, radix) = match bytes.get(after_amp) {
-                Some(b'x') | Some(b'X') => (after_amp + 1, 16),
-                _ => (after_amp, 10),
-            };
-            if let Some(semi) = bytes[digits_start..].iter().position(|&b| b == b';') {
-                let num_end = digits_start + semi;
-                if let Ok(code) = u32::from_str_radix(
-                    std::str::from_utf8(&bytes[digits_start..num_end]).unwrap_or(""),
-                    radix,
-                ) {
-                    if let Some(ch) = char::from_u32(code) {
-                        out.push(ch);
-                        i = num_end + 1;
-                        continue;
-                    }
-                }
-            }
-        }
+// ==================== HTML实体编解码 ====================
 
-        // Unknown escape — copy the '&' verbatim and move on.
-        out.push('&');
-        i += 1;
-    }
+/// `mode`: html（默认，`& < > " '`）/ text / attribute / js / css / xml；
+/// `ascii_only` 为 true 时同时转义所有非 ASCII 字符
+#[tauri::command]
+pub fn html_encode(
+    input: ByteInput,
+    mode: Option<EscapeMode>,
+    ascii_only: Option<bool>,
+    output: Option<ByteOutput>,
+) -> Result<String, String> {
+    let escaped = html_codec::escape(&input.into_text()?, mode.unwrap_or_default(), ascii_only.unwrap_or(false))?;
+    output.unwrap_or_default().write_text(escaped)
+}
 
-    Ok(out)
+#[derive(Serialize)]
+pub struct HtmlDecodeResult {
+    output: String,
+    /// 形如 `&name;` 但不是已知实体的片段，原样保留
+    unknown_entities: Vec<UnknownEntity>,
+}
+
+/// 按 HTML5 规则解码全部 2231 个命名实体及数字实体
+///
+/// `in_attribute` 为 true 时按属性值规则处理不带分号的旧式实体。
+#[tauri::command]
+pub fn html_decode(
+    input: ByteInput,
+    in_attribute: Option<bool>,
+    output: Option<ByteOutput>,
+) -> Result<HtmlDecodeResult, String> {
+    let (decoded, unknown_entities) = html_codec::decode(&input.into_text()?, in_attribute.unwrap_or(false));
+    Ok(HtmlDecodeResult {
+        output: output.unwrap_or_default().write_text(decoded)?,
+        unknown_entities,
+    })
 }
 
 // ==================== Punycode 编解码 ====================
 
 #[tauri::command]
-pub fn punycode_encode(input: String) -> Result<String, String> {
-    punycode::encode(&input).map_err(|_| "Punycode 编码失败".to_string())
+pub fn punycode_encode(input: ByteInput, output: Option<ByteOutput>) -> Result<String, String> {
+    let encoded = punycode::encode(&input.into_text()?).map_err(|_| "Punycode 编码失败".to_string())?;
+    output.unwrap_or_default().write_text(encoded)
 }
 
 #[tauri::command]
-pub fn punycode_decode(input: String) -> Result<String, String> {
-    punycode::decode(&input).map_err(|_| "Punycode 解码失败".to_string())
+pub fn punycode_decode(input: ByteInput, output: Option<ByteOutput>) -> Result<String, String> {
+    let decoded = punycode::decode(&input.into_text()?).map_err(|_| "Punycode 解码失败".to_string())?;
+    output.unwrap_or_default().write_text(decoded)
 }
 
 // ==================== 进制转换辅助 ====================
 
+/// 二进制位串转字节，默认以 `AA BB` 形式输出。
+///
+/// 位数不足整字节时在左侧补齐：默认补 0；`signed` 为 true 时把最高位视为
+/// 符号位做符号扩展（`1010` → `FA`）。带 `-` 号的输入按最少字节数的补码输出
+/// （`-101` → `FB`）。允许 `0b` 前缀和 `_` 分隔。
 #[tauri::command]
-pub fn binary_to_hex(input: String) -> Result<String, String> {
-    // 移除空格
-    let binary = input.replace(" ", "").replace("\n", "").replace("\r", "");
+pub fn binary_to_hex(input: ByteInput, signed: Option<bool>, output: Option<ByteOutput>) -> Result<String, String> {
+    // 移除空格和分隔符
+    let binary: String = input
+        .into_text()?
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != '_')
+        .collect();
+    if binary.is_empty() {
+        return Ok(String::new());
+    }
+    let (negative, digits) = match binary.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, binary.strip_prefix('+').unwrap_or(&binary)),
+    };
+    let digits = digits
+        .strip_prefix("0b")
+        .or_else(|| digits.strip_prefix("0B"))
+        .unwrap_or(digits);
+    if digits.is_empty() {
+        return Err("缺少二进制数字".to_string());
+    }
 
     // 验证二进制格式
-    for c in binary.chars() {
+    for c in digits.chars() {
         if c != '0' && c != '1' {
             return Err(format!("无效的二进制字符: {}", c));
         }
     }
 
-    // 补齐到8的倍数
-    let len = binary.len();
-    let padded_len = (len + 7) / 8 * 8;
-    let padded = format!("{:0>width$}", binary, width = padded_len);
-
-    let mut result = String::new();
-    for chunk in padded.as_bytes().chunks(8) {
-        let byte = u8::from_str_radix(std::str::from_utf8(chunk).unwrap(), 2)
-            .map_err(|e| format!("二进制转换失败: {}", e))?;
-        result.push_str(&format!("{:02X} ", byte));
-    }
-
-    Ok(result.trim().to_string())
+    let bytes = if negative {
+        let value = -number_tools::parse_integer(digits, Some(2))?;
+        let bits = number_tools::min_signed_bits(&value);
+        let pattern = number_tools::to_twos_complement(&value, bits)?.to_bytes_be();
+        let mut bytes = vec![0u8; bits as usize / 8 - pattern.len()];
+        bytes.extend(pattern);
+        bytes
+    } else {
+        // 补齐到8的倍数
+        let fill = if signed.unwrap_or(false) && digits.starts_with('1') { '1' } else { '0' };
+        let padded_len = digits.len().div_ceil(8) * 8;
+        let mut padded: String = std::iter::repeat_n(fill, padded_len - digits.len()).collect();
+        padded.push_str(digits);
+
+        let mut bytes = Vec::with_capacity(padded_len / 8);
+        for chunk in padded.as_bytes().chunks(8) {
+            let byte = u8::from_str_radix(std::str::from_utf8(chunk).unwrap(), 2)
+                .map_err(|e| format!("二进制转换失败: {}", e))?;
+            bytes.push(byte);
+        }
+        bytes
+    };
+
+    output
+        .unwrap_or(ByteOutput::Hex {
+            uppercase: true,
+            separator: Some(" ".to_string()),
+        })
+        .write(&bytes)
 }
 
 #[tauri::command]
-pub fn hex_to_binary(hex: String) -> Result<String, String> {
-    // 移除空格
-    let hex = hex.replace(" ", "").replace("\n", "").replace("\r", "");
-
-    // 验证十六进制格式
-    if hex.len() % 2 != 0 {
-        return Err("十六进制字符串长度必须是偶数".to_string());
-    }
-
-    for c in hex.chars() {
-        if !c.is_ascii_hexdigit() {
-            return Err(format!("无效的十六进制字符: {}", c));
-        }
-    }
-
-    let bytes = hex::decode(&hex).map_err(|e| format!("十六进制解码失败: {}", e))?;
+pub fn hex_to_binary(hex: ByteInput, output: Option<ByteOutput>) -> Result<String, String> {
+    let bytes = byte_io::decode_hex(&hex.into_text()?)?;
 
     let result = bytes
         .iter()
@@ -485,5 +577,40 @@ pub fn hex_to_binary(hex: String) -> Result<String, String> {
         .collect::<Vec<_>>()
         .join(" ");
 
-    Ok(result)
+    output.unwrap_or_default().write_text(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plain(s: &str) -> ByteInput {
+        ByteInput::Plain(s.to_string())
+    }
+
+    #[test]
+    fn url_encode_form() {
+        let encoded = url_encode(plain("a b&c=d*~"), Some(UrlComponent::Form), None).unwrap();
+        assert_eq!(encoded, "a+b%26c%3Dd*%7E");
+        let input = ByteInput::Typed(byte_io::TypedInput::Text {
+            value: "中 文".to_string(),
+            charset: Some("gbk".to_string()),
+        });
+        let encoded = url_encode(input, Some(UrlComponent::Form), None).unwrap();
+        assert_eq!(encoded, "%D6%D0+%CE%C4");
+    }
+
+    #[test]
+    fn binary_to_hex_empty_input() {
+        assert_eq!(binary_to_hex(plain(""), None, None).unwrap(), "");
+        assert_eq!(binary_to_hex(plain(" \n "), None, None).unwrap(), "");
+        assert!(binary_to_hex(plain("0b"), None, None).is_err());
+    }
+
+    #[test]
+    fn binary_to_hex_pads_and_sign_extends() {
+        assert_eq!(binary_to_hex(plain("1010"), None, None).unwrap(), "0A");
+        assert_eq!(binary_to_hex(plain("1010"), Some(true), None).unwrap(), "FA");
+        assert_eq!(binary_to_hex(plain("-101"), None, None).unwrap(), "FB");
+    }
 }
@@ -0,0 +1,478 @@
+// 国密算法：SM2、SM3、SM4
+//
+// 密钥与密文的写法沿用国内系统的习惯：默认输出小写十六进制，输入时
+// 十六进制、Base64 均可识别。SM2 公钥接受 04 开头的非压缩点、02/03 开头
+// 的压缩点，以及省略 04 前缀的 128 位十六进制。
+//
+// SM2 密文有两种排列：GB/T 32918-2016 规定的 C1C3C2，以及旧标准与不少
+// 旧系统使用的 C1C2C3；C1 省略 04 前缀（sm-crypto 等 JS 库的输出）也能
+// 解密。签名默认用户 ID 为 `1234567812345678`，签名值可以是 DER 或
+// 64 字节的 r||s。
+
+use libsm::sm2::ecc::Point;
+use libsm::sm2::encrypt::{DecryptCtx, EncryptCtx};
+use libsm::sm2::signature::{SigCtx, Signature};
+use libsm::sm3::hash::Sm3Hash;
+use libsm::sm4::cipher::Sm4Cipher;
+use num_bigint::BigUint;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+
+use super::byte_io::{decode_base64, decode_hex, encode_hex, ByteInput, ByteOutput};
+use super::crypto::{pad, unpad, BlockPadding};
+
+/// GM/T 0009 推荐的默认用户 ID
+const DEFAULT_USER_ID: &str = "1234567812345678";
+const SM2_C1_LEN: usize = 65;
+const SM3_LEN: usize = 32;
+const SM4_BLOCK: usize = 16;
+const SM3_BLOCK: usize = 64;
+
+fn hex_output(output: Option<ByteOutput>) -> ByteOutput {
+    output.unwrap_or(ByteOutput::Hex {
+        uppercase: false,
+        separator: None,
+    })
+}
+
+/// 密文、签名等二进制值：直接粘贴的字符串按十六进制或 Base64 解码
+fn binary_input(input: ByteInput, what: &str) -> Result<Vec<u8>, String> {
+    match input {
+        ByteInput::Plain(text) => decode_hex(&text)
+            .or_else(|_| decode_base64(&text))
+            .map_err(|_| format!("{}应为十六进制或 Base64", what)),
+        typed => typed.into_bytes(),
+    }
+}
+
+/// 固定长度的密钥：原始字节长度正好时直接使用，否则按十六进制、Base64 解码
+fn key_input(input: ByteInput, lens: &[usize], what: &str) -> Result<Vec<u8>, String> {
+    let bytes = input.into_bytes()?;
+    if lens.contains(&bytes.len()) {
+        return Ok(bytes);
+    }
+    let text = String::from_utf8_lossy(&bytes);
+    let decoded = decode_hex(&text).or_else(|_| decode_base64(&text));
+    match decoded {
+        Ok(key) if lens.contains(&key.len()) => Ok(key),
+        _ => Err(format!(
+            "{}长度应为 {} 字节（十六进制 {} 位），当前 {} 字节",
+            what,
+            lens[0],
+            lens[0] * 2,
+            bytes.len()
+        )),
+    }
+}
+
+// ==================== SM3 ====================
+
+/// SM3 摘要，默认输出十六进制
+#[tauri::command]
+pub fn sm3_hash(input: ByteInput, output: Option<ByteOutput>) -> Result<String, String> {
+    hex_output(output).write(&sm3(&input.into_bytes()?))
+}
+
+/// HMAC-SM3，默认输出十六进制
+#[tauri::command]
+pub fn hmac_sm3(message: ByteInput, key: ByteInput, output: Option<ByteOutput>) -> Result<String, String> {
+    let message = message.into_bytes()?;
+    let mut key = key.into_bytes()?;
+    if key.len() > SM3_BLOCK {
+        key = sm3(&key).to_vec();
+    }
+    key.resize(SM3_BLOCK, 0);
+
+    let mut inner: Vec<u8> = key.iter().map(|b| b ^ 0x36).collect();
+    inner.extend_from_slice(&message);
+    let mut outer: Vec<u8> = key.iter().map(|b| b ^ 0x5c).collect();
+    outer.extend_from_slice(&sm3(&inner));
+    hex_output(output).write(&sm3(&outer))
+}
+
+fn sm3(data: &[u8]) -> [u8; SM3_LEN] {
+    Sm3Hash::new(data).get_hash()
+}
+
+// ==================== SM4 ====================
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Sm4Mode {
+    Ecb,
+    Cbc,
+}
+
+/// 生成随机 SM4 密钥（十六进制）
+#[tauri::command]
+pub fn generate_sm4_key() -> String {
+    let mut key = [0u8; SM4_BLOCK];
+    rand::rngs::OsRng.fill_bytes(&mut key);
+    encode_hex(&key, false, "")
+}
+
+/// SM4 加密，默认输出十六进制。`mode` 缺省时有 IV 用 CBC，否则 ECB；
+/// 填充默认 PKCS7
+#[tauri::command]
+pub fn sm4_encrypt(
+    plaintext: ByteInput,
+    key: ByteInput,
+    iv: Option<ByteInput>,
+    mode: Option<Sm4Mode>,
+    padding: Option<BlockPadding>,
+    output: Option<ByteOutput>,
+) -> Result<String, String> {
+    let data = pad(&plaintext.into_bytes()?, padding.unwrap_or(BlockPadding::Pkcs7));
+    if !data.len().is_multiple_of(SM4_BLOCK) {
+        return Err(format!("不填充时明文长度必须是 16 的倍数，当前 {} 字节", data.len()));
+    }
+    let (cipher, mode, iv) = sm4_setup(key, iv, mode)?;
+    let mut out = Vec::with_capacity(data.len());
+    let mut prev = iv;
+    for block in data.chunks(SM4_BLOCK) {
+        let encrypted = match mode {
+            Sm4Mode::Ecb => cipher.encrypt(block),
+            Sm4Mode::Cbc => cipher.encrypt(&xor_block(block, &prev)),
+        }
+        .map_err(|e| format!("SM4 加密失败: {}", e))?;
+        prev = encrypted.to_vec();
+        out.extend_from_slice(&encrypted);
+    }
+    hex_output(output).write(&out)
+}
+
+/// SM4 解密，默认输出 UTF-8 文本
+#[tauri::command]
+pub fn sm4_decrypt(
+    ciphertext: ByteInput,
+    key: ByteInput,
+    iv: Option<ByteInput>,
+    mode: Option<Sm4Mode>,
+    padding: Option<BlockPadding>,
+    output: Option<ByteOutput>,
+) -> Result<String, String> {
+    let data = binary_input(ciphertext, "密文")?;
+    if data.is_empty() || !data.len().is_multiple_of(SM4_BLOCK) {
+        return Err(format!("密文长度必须是 16 的倍数，当前 {} 字节", data.len()));
+    }
+    let (cipher, mode, iv) = sm4_setup(key, iv, mode)?;
+    let mut out = Vec::with_capacity(data.len());
+    let mut prev = iv;
+    for block in data.chunks(SM4_BLOCK) {
+        let decrypted = cipher.decrypt(block).map_err(|e| format!("SM4 解密失败: {}", e))?;
+        match mode {
+            Sm4Mode::Ecb => out.extend_from_slice(&decrypted),
+            Sm4Mode::Cbc => {
+                out.extend_from_slice(&xor_block(&decrypted, &prev));
+                prev = block.to_vec();
+            }
+        }
+    }
+    let plaintext = unpad(out, padding.unwrap_or(BlockPadding::Pkcs7))?;
+    output.unwrap_or_default().write(&plaintext)
+}
+
+fn sm4_setup(
+    key: ByteInput,
+    iv: Option<ByteInput>,
+    mode: Option<Sm4Mode>,
+) -> Result<(Sm4Cipher, Sm4Mode, Vec<u8>), String> {
+    let key = key_input(key, &[SM4_BLOCK], "SM4 密钥")?;
+    let cipher = Sm4Cipher::new(&key).map_err(|e| format!("SM4 密钥无效: {}", e))?;
+    // 前端未填写 IV 时传空字符串
+    let iv = match iv {
+        Some(ByteInput::Plain(text)) if text.trim().is_empty() => None,
+        Some(iv) => Some(key_input(iv, &[SM4_BLOCK], "IV")?),
+        None => None,
+    };
+    let mode = mode.unwrap_or(if iv.is_some() { Sm4Mode::Cbc } else { Sm4Mode::Ecb });
+    match (mode, iv) {
+        (Sm4Mode::Cbc, None) => Err("CBC 模式需要 16 字节 IV".to_string()),
+        (mode, iv) => Ok((cipher, mode, iv.unwrap_or_default())),
+    }
+}
+
+fn xor_block(a: &[u8], b: &[u8]) -> Vec<u8> {
+    a.iter().zip(b).map(|(x, y)| x ^ y).collect()
+}
+
+// ==================== SM2 ====================
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum Sm2CipherOrder {
+    #[default]
+    C1c3c2,
+    C1c2c3,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum Sm2SignatureFormat {
+    #[default]
+    Der,
+    /// 64 字节 r||s
+    Raw,
+}
+
+/// 生成 SM2 密钥对，返回 (私钥, 公钥) 十六进制；公钥默认 04 开头的非压缩格式
+#[tauri::command]
+pub fn generate_sm2_keypair(compressed: Option<bool>) -> Result<(String, String), String> {
+    let ctx = SigCtx::new();
+    let (pk, sk) = ctx.new_keypair().map_err(|e| format!("生成密钥失败: {}", e))?;
+    let private = ctx.serialize_seckey(&sk).map_err(|e| format!("私钥编码失败: {}", e))?;
+    let public = ctx
+        .serialize_pubkey(&pk, compressed.unwrap_or(false))
+        .map_err(|e| format!("公钥编码失败: {}", e))?;
+    Ok((encode_hex(&private, false, ""), encode_hex(&public, false, "")))
+}
+
+/// 由私钥计算公钥
+#[tauri::command]
+pub fn sm2_public_key(private_key: ByteInput, compressed: Option<bool>) -> Result<String, String> {
+    let ctx = SigCtx::new();
+    let sk = sm2_private_key(&ctx, private_key)?;
+    let pk = ctx.pk_from_sk(&sk).map_err(|e| format!("私钥无效: {}", e))?;
+    let public = ctx
+        .serialize_pubkey(&pk, compressed.unwrap_or(false))
+        .map_err(|e| format!("公钥编码失败: {}", e))?;
+    Ok(encode_hex(&public, false, ""))
+}
+
+/// SM2 公钥加密，默认 C1C3C2 顺序、十六进制输出
+#[tauri::command]
+pub fn sm2_encrypt(
+    plaintext: ByteInput,
+    public_key: ByteInput,
+    order: Option<Sm2CipherOrder>,
+    output: Option<ByteOutput>,
+) -> Result<String, String> {
+    let data = plaintext.into_bytes()?;
+    if data.is_empty() {
+        return Err("明文不能为空".to_string());
+    }
+    let pk = sm2_public_point(&SigCtx::new(), public_key)?;
+    // libsm 输出 C1C2C3
+    let encrypted = EncryptCtx::new(data.len(), pk)
+        .encrypt(&data)
+        .map_err(|e| format!("SM2 加密失败: {}", e))?;
+    let encrypted = match order.unwrap_or_default() {
+        Sm2CipherOrder::C1c2c3 => encrypted,
+        Sm2CipherOrder::C1c3c2 => {
+            let (c1, rest) = encrypted.split_at(SM2_C1_LEN);
+            let (c2, c3) = rest.split_at(rest.len() - SM3_LEN);
+            [c1, c3, c2].concat()
+        }
+    };
+    hex_output(output).write(&encrypted)
+}
+
+/// SM2 私钥解密，默认按 C1C3C2 解析、输出 UTF-8 文本
+#[tauri::command]
+pub fn sm2_decrypt(
+    ciphertext: ByteInput,
+    private_key: ByteInput,
+    order: Option<Sm2CipherOrder>,
+    output: Option<ByteOutput>,
+) -> Result<String, String> {
+    let ctx = SigCtx::new();
+    let sk = sm2_private_key(&ctx, private_key)?;
+    let data = binary_input(ciphertext, "密文")?;
+    let order = order.unwrap_or_default();
+    // sm-crypto 等库输出的 C1 不带 04 前缀；x 坐标恰好以 04 开头时两种都试
+    let with_prefix = || sm2_decrypt_raw(&sk, [&[0x04], &data[..]].concat(), order);
+    let plaintext = if data.first() == Some(&0x04) {
+        sm2_decrypt_raw(&sk, data.clone(), order).or_else(|e| with_prefix().map_err(|_| e))
+    } else {
+        with_prefix()
+    }?;
+    output.unwrap_or_default().write(&plaintext)
+}
+
+/// 解密 04 开头的完整密文
+fn sm2_decrypt_raw(sk: &BigUint, data: Vec<u8>, order: Sm2CipherOrder) -> Result<Vec<u8>, String> {
+    if data.len() <= SM2_C1_LEN + SM3_LEN {
+        return Err(format!("密文过短：至少需要 {} 字节", SM2_C1_LEN + SM3_LEN + 1));
+    }
+    let data = match order {
+        Sm2CipherOrder::C1c2c3 => data,
+        Sm2CipherOrder::C1c3c2 => {
+            let (c1, rest) = data.split_at(SM2_C1_LEN);
+            let (c3, c2) = rest.split_at(SM3_LEN);
+            [c1, c2, c3].concat()
+        }
+    };
+    let klen = data.len() - SM2_C1_LEN - SM3_LEN;
+    DecryptCtx::new(klen, sk.clone())
+        .decrypt(&data)
+        .map_err(|_| "SM2 解密失败：私钥不匹配、密文已损坏或 C1C3C2/C1C2C3 顺序不对".to_string())
+}
+
+/// SM2 签名，默认用户 ID `1234567812345678`、DER 编码、十六进制输出
+#[tauri::command]
+pub fn sm2_sign(
+    message: ByteInput,
+    private_key: ByteInput,
+    user_id: Option<String>,
+    format: Option<Sm2SignatureFormat>,
+    output: Option<ByteOutput>,
+) -> Result<String, String> {
+    let message = message.into_bytes()?;
+    let ctx = SigCtx::new();
+    let sk = sm2_private_key(&ctx, private_key)?;
+    let pk = ctx.pk_from_sk(&sk).map_err(|e| format!("私钥无效: {}", e))?;
+    let digest = ctx
+        .hash(user_id.as_deref().unwrap_or(DEFAULT_USER_ID), &pk, &message)
+        .map_err(|e| format!("计算签名摘要失败: {}", e))?;
+    let signature = ctx.sign_raw(&digest, &sk).map_err(|e| format!("SM2 签名失败: {}", e))?;
+    let encoded = match format.unwrap_or_default() {
+        Sm2SignatureFormat::Der => signature.der_encode(),
+        Sm2SignatureFormat::Raw => [to_fixed_32(signature.get_r()), to_fixed_32(signature.get_s())].concat(),
+    };
+    hex_output(output).write(&encoded)
+}
+
+/// SM2 验签，签名格式（DER 或 r||s）自动识别
+#[tauri::command]
+pub fn sm2_verify(
+    message: ByteInput,
+    signature: ByteInput,
+    public_key: ByteInput,
+    user_id: Option<String>,
+) -> Result<bool, String> {
+    let message = message.into_bytes()?;
+    let signature = binary_input(signature, "签名")?;
+    let signature = if signature.len() == 64 {
+        Signature::new(&signature[..32], &signature[32..])
+    } else {
+        Signature::der_decode(&signature).map_err(|_| "签名格式无效：应为 DER 编码或 64 字节 r||s".to_string())?
+    };
+    let ctx = SigCtx::new();
+    let pk = sm2_public_point(&ctx, public_key)?;
+    let digest = ctx
+        .hash(user_id.as_deref().unwrap_or(DEFAULT_USER_ID), &pk, &message)
+        .map_err(|e| format!("计算签名摘要失败: {}", e))?;
+    ctx.verify_raw(&digest, &pk, &signature)
+        .map_err(|e| format!("SM2 验签失败: {}", e))
+}
+
+fn sm2_private_key(ctx: &SigCtx, input: ByteInput) -> Result<BigUint, String> {
+    let mut key = key_input(input, &[32, 33], "SM2 私钥")?;
+    // 部分工具导出的私钥带 00 前导字节
+    if key.len() == 33 {
+        if key[0] != 0 {
+            return Err("SM2 私钥长度应为 32 字节".to_string());
+        }
+        key.remove(0);
+    }
+    ctx.load_seckey(&key).map_err(|e| format!("SM2 私钥无效: {}", e))
+}
+
+fn sm2_public_point(ctx: &SigCtx, input: ByteInput) -> Result<Point, String> {
+    let mut key = key_input(input, &[65, 64, 33], "SM2 公钥")?;
+    if key.len() == 64 {
+        key.insert(0, 0x04);
+    }
+    ctx.load_pubkey(&key).map_err(|e| format!("SM2 公钥无效: {}", e))
+}
+
+fn to_fixed_32(n: &BigUint) -> Vec<u8> {
+    let bytes = n.to_bytes_be();
+    let mut out = vec![0u8; 32usize.saturating_sub(bytes.len())];
+    out.extend_from_slice(&bytes);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::byte_io::TypedInput;
+    use super::*;
+
+    fn plain(s: &str) -> ByteInput {
+        ByteInput::Plain(s.to_string())
+    }
+
+    // GB/T 32905-2016 附录 A 示例 1、示例 2
+    #[test]
+    fn sm3_standard_vectors() {
+        assert_eq!(
+            sm3_hash(plain("abc"), None).unwrap(),
+            "66c7f0f462eeedd9d1f2d46bdc10e4e24167c4875cf2f7a2297da02b8f4ba8e0"
+        );
+        assert_eq!(
+            sm3_hash(plain(&"abcd".repeat(16)), None).unwrap(),
+            "debe9ff92275b8a138604889c18e5a4d6fdb70e5387e5765293dcba39c0c5732"
+        );
+    }
+
+    // printf hello | openssl dgst -sm3 -hmac key
+    #[test]
+    fn hmac_sm3_matches_openssl() {
+        assert_eq!(
+            hmac_sm3(plain("hello"), plain("key"), None).unwrap(),
+            "798a6ca374c9ac891982f87a4f4c4d60bd6cdddfe8fb0fa344393621a0c02d96"
+        );
+    }
+
+    // GB/T 32907-2016 附录 A 示例 1
+    #[test]
+    fn sm4_standard_vector() {
+        let key = "0123456789abcdeffedcba9876543210";
+        let block = ByteInput::Typed(TypedInput::Hex { value: key.to_string() });
+        let encrypted = sm4_encrypt(block, plain(key), None, None, Some(BlockPadding::None), None).unwrap();
+        assert_eq!(encrypted, "681edf34d206965e86b3e94f536e4246");
+        let decrypted = sm4_decrypt(
+            plain(&encrypted),
+            plain(key),
+            None,
+            None,
+            Some(BlockPadding::None),
+            Some(ByteOutput::Hex {
+                uppercase: false,
+                separator: None,
+            }),
+        )
+        .unwrap();
+        assert_eq!(decrypted, key);
+    }
+
+    // printf 'hello sm4' | openssl enc -sm4-cbc -K ... -iv ...
+    #[test]
+    fn sm4_cbc_matches_openssl() {
+        let key = "0123456789abcdeffedcba9876543210";
+        let iv = "000102030405060708090a0b0c0d0e0f";
+        let encrypted = sm4_encrypt(plain("hello sm4"), plain(key), Some(plain(iv)), None, None, None).unwrap();
+        assert_eq!(encrypted, "17af1c8f53078de719c8821199836285");
+        let decrypted = sm4_decrypt(plain(&encrypted), plain(key), Some(plain(iv)), None, None, None).unwrap();
+        assert_eq!(decrypted, "hello sm4");
+    }
+
+    #[test]
+    fn sm2_encrypt_round_trip() {
+        let (private, public) = generate_sm2_keypair(None).unwrap();
+        for order in [Sm2CipherOrder::C1c2c3, Sm2CipherOrder::C1c3c2] {
+            let encrypted = sm2_encrypt(plain("国密 SM2"), plain(&public), Some(order), None).unwrap();
+            let decrypted = sm2_decrypt(plain(&encrypted), plain(&private), Some(order), None).unwrap();
+            assert_eq!(decrypted, "国密 SM2", "{:?}", order);
+            // sm-crypto 输出的 C1 不带 04 前缀
+            let stripped = encrypted.strip_prefix("04").unwrap();
+            let decrypted = sm2_decrypt(plain(stripped), plain(&private), Some(order), None).unwrap();
+            assert_eq!(decrypted, "国密 SM2", "{:?}", order);
+        }
+        let encrypted = sm2_encrypt(plain("国密 SM2"), plain(&public), Some(Sm2CipherOrder::C1c2c3), None).unwrap();
+        assert!(sm2_decrypt(plain(&encrypted), plain(&private), Some(Sm2CipherOrder::C1c3c2), None).is_err());
+    }
+
+    #[test]
+    fn sm2_sign_round_trip() {
+        let (private, public) = generate_sm2_keypair(Some(true)).unwrap();
+        assert_eq!(sm2_public_key(plain(&private), Some(true)).unwrap(), public);
+        for format in [Sm2SignatureFormat::Der, Sm2SignatureFormat::Raw] {
+            let signature = sm2_sign(plain("message"), plain(&private), None, Some(format), None).unwrap();
+            assert!(sm2_verify(plain("message"), plain(&signature), plain(&public), None).unwrap());
+            assert!(!sm2_verify(plain("other"), plain(&signature), plain(&public), None).unwrap());
+            let other_id = Some("alice@example.com".to_string());
+            assert!(!sm2_verify(plain("message"), plain(&signature), plain(&public), other_id).unwrap());
+        }
+    }
+}
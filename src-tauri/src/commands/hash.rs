@@ -0,0 +1,761 @@
+// 哈希与校验和
+//
+// 支持 MD5、SHA-1、SHA-2、SHA-3、BLAKE2/BLAKE3、CRC32/CRC64 和 xxHash，
+// 密码学摘要可以带密钥计算 HMAC。文本、十六进制、Base64 这类小输入用
+// `hash` 一次算完；文件用 `hash_files` 分块流式读取，同时算多种算法，
+// 并通过 `hash:progress` 事件报告进度，几 GB 的镜像也不会整个读进内存。
+//
+// `verify_checksum` 把文件与粘贴的校验值比对，`verify_manifest` 校验
+// `sha256sum` 生成的清单（GNU 与 BSD 两种格式）。
+
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use digest::core_api::BlockSizeUser;
+use digest::{FixedOutput, KeyInit, Update};
+use hmac::SimpleHmac;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+
+use super::byte_io::{ByteInput, ByteOutput};
+
+/// 文件哈希进度事件
+pub const HASH_PROGRESS_EVENT: &str = "hash:progress";
+
+const CHUNK_SIZE: usize = 1024 * 1024;
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+/// CRC-64/XZ，xz、7-Zip 使用的变体
+static CRC64: crc::Crc<u64> = crc::Crc::<u64>::new(&crc::CRC_64_XZ);
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum HashAlgorithm {
+    Md5,
+    Sha1,
+    Sha224,
+    Sha256,
+    Sha384,
+    Sha512,
+    Sha512_224,
+    Sha512_256,
+    Sha3_224,
+    Sha3_256,
+    Sha3_384,
+    Sha3_512,
+    /// BLAKE2b-512
+    Blake2b,
+    /// BLAKE2s-256
+    Blake2s,
+    Blake3,
+    Crc32,
+    Crc64,
+    Xxh32,
+    Xxh64,
+    /// XXH3 64 位
+    Xxh3,
+    /// XXH3 128 位
+    Xxh128,
+}
+
+impl HashAlgorithm {
+    pub fn name(self) -> &'static str {
+        match self {
+            HashAlgorithm::Md5 => "MD5",
+            HashAlgorithm::Sha1 => "SHA-1",
+            HashAlgorithm::Sha224 => "SHA-224",
+            HashAlgorithm::Sha256 => "SHA-256",
+            HashAlgorithm::Sha384 => "SHA-384",
+            HashAlgorithm::Sha512 => "SHA-512",
+            HashAlgorithm::Sha512_224 => "SHA-512/224",
+            HashAlgorithm::Sha512_256 => "SHA-512/256",
+            HashAlgorithm::Sha3_224 => "SHA3-224",
+            HashAlgorithm::Sha3_256 => "SHA3-256",
+            HashAlgorithm::Sha3_384 => "SHA3-384",
+            HashAlgorithm::Sha3_512 => "SHA3-512",
+            HashAlgorithm::Blake2b => "BLAKE2b-512",
+            HashAlgorithm::Blake2s => "BLAKE2s-256",
+            HashAlgorithm::Blake3 => "BLAKE3",
+            HashAlgorithm::Crc32 => "CRC32",
+            HashAlgorithm::Crc64 => "CRC64",
+            HashAlgorithm::Xxh32 => "XXH32",
+            HashAlgorithm::Xxh64 => "XXH64",
+            HashAlgorithm::Xxh3 => "XXH3-64",
+            HashAlgorithm::Xxh128 => "XXH3-128",
+        }
+    }
+
+    /// 按名称识别算法，不区分大小写并忽略 `-`、`_`、`/`，
+    /// 如 `SHA256`、`sha-256`、`SHA512/256`、`b3`
+    pub fn from_label(label: &str) -> Option<Self> {
+        let key: String = label
+            .chars()
+            .filter(|c| !matches!(c, '-' | '_' | '/' | ' '))
+            .collect::<String>()
+            .to_ascii_lowercase();
+        let algorithm = match key.as_str() {
+            "md5" => HashAlgorithm::Md5,
+            "sha1" => HashAlgorithm::Sha1,
+            "sha224" => HashAlgorithm::Sha224,
+            "sha256" => HashAlgorithm::Sha256,
+            "sha384" => HashAlgorithm::Sha384,
+            "sha512" => HashAlgorithm::Sha512,
+            "sha512224" => HashAlgorithm::Sha512_224,
+            "sha512256" => HashAlgorithm::Sha512_256,
+            "sha3224" => HashAlgorithm::Sha3_224,
+            "sha3256" => HashAlgorithm::Sha3_256,
+            "sha3384" => HashAlgorithm::Sha3_384,
+            "sha3512" => HashAlgorithm::Sha3_512,
+            "blake2b" | "blake2b512" | "b2" => HashAlgorithm::Blake2b,
+            "blake2s" | "blake2s256" => HashAlgorithm::Blake2s,
+            "blake3" | "b3" => HashAlgorithm::Blake3,
+            "crc32" => HashAlgorithm::Crc32,
+            "crc64" => HashAlgorithm::Crc64,
+            "xxh32" => HashAlgorithm::Xxh32,
+            "xxh64" => HashAlgorithm::Xxh64,
+            "xxh3" | "xxh364" => HashAlgorithm::Xxh3,
+            "xxh128" | "xxh3128" => HashAlgorithm::Xxh128,
+            _ => return None,
+        };
+        Some(algorithm)
+    }
+
+    /// 仅凭十六进制长度猜测算法，只覆盖发布校验里常见的几种；
+    /// 同长度时优先 SHA-2
+    fn from_hex_len(len: usize) -> Option<Self> {
+        match len {
+            32 => Some(HashAlgorithm::Md5),
+            40 => Some(HashAlgorithm::Sha1),
+            56 => Some(HashAlgorithm::Sha224),
+            64 => Some(HashAlgorithm::Sha256),
+            96 => Some(HashAlgorithm::Sha384),
+            128 => Some(HashAlgorithm::Sha512),
+            _ => None,
+        }
+    }
+
+    fn hasher(self) -> Box<dyn StreamHasher> {
+        match self {
+            HashAlgorithm::Md5 => Box::new(Fixed(md5::Md5::default())),
+            HashAlgorithm::Sha1 => Box::new(Fixed(sha1::Sha1::default())),
+            HashAlgorithm::Sha224 => Box::new(Fixed(sha2::Sha224::default())),
+            HashAlgorithm::Sha256 => Box::new(Fixed(sha2::Sha256::default())),
+            HashAlgorithm::Sha384 => Box::new(Fixed(sha2::Sha384::default())),
+            HashAlgorithm::Sha512 => Box::new(Fixed(sha2::Sha512::default())),
+            HashAlgorithm::Sha512_224 => Box::new(Fixed(sha2::Sha512_224::default())),
+            HashAlgorithm::Sha512_256 => Box::new(Fixed(sha2::Sha512_256::default())),
+            HashAlgorithm::Sha3_224 => Box::new(Fixed(sha3::Sha3_224::default())),
+            HashAlgorithm::Sha3_256 => Box::new(Fixed(sha3::Sha3_256::default())),
+            HashAlgorithm::Sha3_384 => Box::new(Fixed(sha3::Sha3_384::default())),
+            HashAlgorithm::Sha3_512 => Box::new(Fixed(sha3::Sha3_512::default())),
+            HashAlgorithm::Blake2b => Box::new(Fixed(blake2::Blake2b512::default())),
+            HashAlgorithm::Blake2s => Box::new(Fixed(blake2::Blake2s256::default())),
+            HashAlgorithm::Blake3 => Box::new(blake3::Hasher::new()),
+            HashAlgorithm::Crc32 => Box::new(crc32fast::Hasher::new()),
+            HashAlgorithm::Crc64 => Box::new(CRC64.digest()),
+            HashAlgorithm::Xxh32 => Box::new(xxhash_rust::xxh32::Xxh32::new(0)),
+            HashAlgorithm::Xxh64 => Box::new(xxhash_rust::xxh64::Xxh64::new(0)),
+            HashAlgorithm::Xxh3 => Box::new(Xxh3Hasher::new(false)),
+            HashAlgorithm::Xxh128 => Box::new(Xxh3Hasher::new(true)),
+        }
+    }
+
+    fn hmac(self, key: &[u8]) -> Result<Box<dyn StreamHasher>, String> {
+        fn keyed<D>(key: &[u8]) -> Box<dyn StreamHasher>
+        where
+            D: digest::Digest + BlockSizeUser + Send + 'static,
+        {
+            // HMAC 接受任意长度的密钥，这里不会失败
+            Box::new(Fixed(SimpleHmac::<D>::new_from_slice(key).expect("HMAC 密钥长度不受限")))
+        }
+        let hasher = match self {
+            HashAlgorithm::Md5 => keyed::<md5::Md5>(key),
+            HashAlgorithm::Sha1 => keyed::<sha1::Sha1>(key),
+            HashAlgorithm::Sha224 => keyed::<sha2::Sha224>(key),
+            HashAlgorithm::Sha256 => keyed::<sha2::Sha256>(key),
+            HashAlgorithm::Sha384 => keyed::<sha2::Sha384>(key),
+            HashAlgorithm::Sha512 => keyed::<sha2::Sha512>(key),
+            HashAlgorithm::Sha512_224 => keyed::<sha2::Sha512_224>(key),
+            HashAlgorithm::Sha512_256 => keyed::<sha2::Sha512_256>(key),
+            HashAlgorithm::Sha3_224 => keyed::<sha3::Sha3_224>(key),
+            HashAlgorithm::Sha3_256 => keyed::<sha3::Sha3_256>(key),
+            HashAlgorithm::Sha3_384 => keyed::<sha3::Sha3_384>(key),
+            HashAlgorithm::Sha3_512 => keyed::<sha3::Sha3_512>(key),
+            HashAlgorithm::Blake2b => keyed::<blake2::Blake2b512>(key),
+            HashAlgorithm::Blake2s => keyed::<blake2::Blake2s256>(key),
+            _ => return Err(format!("{} 不支持 HMAC", self.name())),
+        };
+        Ok(hasher)
+    }
+}
+
+// ==================== 流式哈希 ====================
+
+trait StreamHasher: Send {
+    fn feed(&mut self, data: &[u8]);
+    fn finish(self: Box<Self>) -> Vec<u8>;
+}
+
+/// RustCrypto 摘要与 HMAC
+struct Fixed<T>(T);
+
+impl<T: Update + FixedOutput + Send> StreamHasher for Fixed<T> {
+    fn feed(&mut self, data: &[u8]) {
+        self.0.update(data);
+    }
+
+    fn finish(self: Box<Self>) -> Vec<u8> {
+        self.0.finalize_fixed().to_vec()
+    }
+}
+
+impl StreamHasher for blake3::Hasher {
+    fn feed(&mut self, data: &[u8]) {
+        self.update(data);
+    }
+
+    fn finish(self: Box<Self>) -> Vec<u8> {
+        self.finalize().as_bytes().to_vec()
+    }
+}
+
+// 非密码学校验和按大端序输出，与 crc32、xxhsum 等命令行工具一致
+
+impl StreamHasher for crc32fast::Hasher {
+    fn feed(&mut self, data: &[u8]) {
+        self.update(data);
+    }
+
+    fn finish(self: Box<Self>) -> Vec<u8> {
+        self.finalize().to_be_bytes().to_vec()
+    }
+}
+
+impl StreamHasher for crc::Digest<'static, u64> {
+    fn feed(&mut self, data: &[u8]) {
+        self.update(data);
+    }
+
+    fn finish(self: Box<Self>) -> Vec<u8> {
+        self.finalize().to_be_bytes().to_vec()
+    }
+}
+
+impl StreamHasher for xxhash_rust::xxh32::Xxh32 {
+    fn feed(&mut self, data: &[u8]) {
+        self.update(data);
+    }
+
+    fn finish(self: Box<Self>) -> Vec<u8> {
+        self.digest().to_be_bytes().to_vec()
+    }
+}
+
+impl StreamHasher for xxhash_rust::xxh64::Xxh64 {
+    fn feed(&mut self, data: &[u8]) {
+        self.update(data);
+    }
+
+    fn finish(self: Box<Self>) -> Vec<u8> {
+        self.digest().to_be_bytes().to_vec()
+    }
+}
+
+struct Xxh3Hasher {
+    state: Box<xxhash_rust::xxh3::Xxh3>,
+    wide: bool,
+}
+
+impl Xxh3Hasher {
+    fn new(wide: bool) -> Self {
+        Xxh3Hasher {
+            state: Box::default(),
+            wide,
+        }
+    }
+}
+
+impl StreamHasher for Xxh3Hasher {
+    fn feed(&mut self, data: &[u8]) {
+        self.state.update(data);
+    }
+
+    fn finish(self: Box<Self>) -> Vec<u8> {
+        if self.wide {
+            self.state.digest128().to_be_bytes().to_vec()
+        } else {
+            self.state.digest().to_be_bytes().to_vec()
+        }
+    }
+}
+
+/// 同时计算多种算法，数据只读一遍
+struct MultiHasher {
+    hashers: Vec<(HashAlgorithm, Box<dyn StreamHasher>)>,
+    hmac: bool,
+}
+
+impl MultiHasher {
+    fn new(algorithms: &[HashAlgorithm], hmac_key: Option<&[u8]>) -> Result<Self, String> {
+        if algorithms.is_empty() {
+            return Err("请至少选择一种算法".to_string());
+        }
+        let hashers = algorithms
+            .iter()
+            .map(|&algorithm| {
+                let hasher = match hmac_key {
+                    Some(key) => algorithm.hmac(key)?,
+                    None => algorithm.hasher(),
+                };
+                Ok((algorithm, hasher))
+            })
+            .collect::<Result<_, String>>()?;
+        Ok(MultiHasher {
+            hashers,
+            hmac: hmac_key.is_some(),
+        })
+    }
+
+    fn feed(&mut self, data: &[u8]) {
+        for (_, hasher) in &mut self.hashers {
+            hasher.feed(data);
+        }
+    }
+
+    fn finish(self, output: &ByteOutput) -> Result<Vec<Digest>, String> {
+        let hmac = self.hmac;
+        self.hashers
+            .into_iter()
+            .map(|(algorithm, hasher)| {
+                let name = if hmac {
+                    format!("HMAC-{}", algorithm.name())
+                } else {
+                    algorithm.name().to_string()
+                };
+                Ok(Digest {
+                    algorithm,
+                    name,
+                    digest: output.write(&hasher.finish())?,
+                })
+            })
+            .collect()
+    }
+}
+
+// ==================== 计算 ====================
+
+#[derive(Debug, Serialize)]
+pub struct Digest {
+    algorithm: HashAlgorithm,
+    /// 展示名，如 `SHA-256`、`HMAC-SHA-256`
+    name: String,
+    digest: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FileHashResult {
+    path: String,
+    size: Option<u64>,
+    success: bool,
+    error: Option<String>,
+    digests: Vec<Digest>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct HashProgress<'a> {
+    path: &'a str,
+    /// 当前文件序号，从 0 开始
+    index: usize,
+    count: usize,
+    processed: u64,
+    total: u64,
+}
+
+fn default_output(output: Option<ByteOutput>) -> ByteOutput {
+    output.unwrap_or(ByteOutput::Hex {
+        uppercase: false,
+        separator: None,
+    })
+}
+
+fn hmac_key_bytes(hmac_key: Option<ByteInput>) -> Result<Option<Vec<u8>>, String> {
+    hmac_key.map(ByteInput::into_bytes).transpose()
+}
+
+/// 计算输入的摘要，`hmac_key` 不为空时计算 HMAC；默认输出小写十六进制
+#[tauri::command]
+pub async fn hash(
+    input: ByteInput,
+    algorithms: Vec<HashAlgorithm>,
+    hmac_key: Option<ByteInput>,
+    output: Option<ByteOutput>,
+) -> Result<Vec<Digest>, String> {
+    tokio::task::spawn_blocking(move || {
+        let key = hmac_key_bytes(hmac_key)?;
+        let mut hasher = MultiHasher::new(&algorithms, key.as_deref())?;
+        hasher.feed(&input.into_bytes()?);
+        hasher.finish(&default_output(output))
+    })
+    .await
+    .map_err(|e| format!("任务执行失败: {}", e))?
+}
+
+/// 流式计算多个文件的摘要，单个文件失败不影响其余文件
+#[tauri::command]
+pub async fn hash_files(
+    app: AppHandle,
+    paths: Vec<String>,
+    algorithms: Vec<HashAlgorithm>,
+    hmac_key: Option<ByteInput>,
+    output: Option<ByteOutput>,
+) -> Result<Vec<FileHashResult>, String> {
+    let key = hmac_key_bytes(hmac_key)?;
+    // 先校验算法与 HMAC 组合，避免每个文件报同样的错
+    MultiHasher::new(&algorithms, key.as_deref())?;
+
+    tokio::task::spawn_blocking(move || {
+        let output = default_output(output);
+        let count = paths.len();
+        paths
+            .into_iter()
+            .enumerate()
+            .map(|(index, path)| {
+                let result = MultiHasher::new(&algorithms, key.as_deref()).and_then(|mut hasher| {
+                    let size = stream_file(&path, &mut hasher, |processed, total| {
+                        emit_progress(&app, &path, index, count, processed, total);
+                    })?;
+                    Ok((size, hasher.finish(&output)?))
+                });
+                match result {
+                    Ok((size, digests)) => FileHashResult {
+                        path,
+                        size: Some(size),
+                        success: true,
+                        error: None,
+                        digests,
+                    },
+                    Err(e) => FileHashResult {
+                        path,
+                        size: None,
+                        success: false,
+                        error: Some(e),
+                        digests: Vec::new(),
+                    },
+                }
+            })
+            .collect()
+    })
+    .await
+    .map_err(|e| format!("任务执行失败: {}", e))
+}
+
+fn emit_progress(app: &AppHandle, path: &str, index: usize, count: usize, processed: u64, total: u64) {
+    let progress = HashProgress {
+        path,
+        index,
+        count,
+        processed,
+        total,
+    };
+    if let Err(e) = app.emit(HASH_PROGRESS_EVENT, progress) {
+        eprintln!("[tbox] 派发哈希进度事件失败: {}", e);
+    }
+}
+
+/// 分块读取文件喂给哈希器，按固定间隔回调进度，返回文件大小
+fn stream_file(
+    path: &str,
+    hasher: &mut MultiHasher,
+    mut on_progress: impl FnMut(u64, u64),
+) -> Result<u64, String> {
+    let mut file = File::open(path).map_err(|e| format!("无法打开文件 {}: {}", path, e))?;
+    let total = file.metadata().map(|m| m.len()).unwrap_or(0);
+    let mut buf = vec![0u8; CHUNK_SIZE];
+    let mut processed = 0u64;
+    let mut last_report = Instant::now();
+    on_progress(0, total);
+    loop {
+        let n = match file.read(&mut buf) {
+            Ok(0) => break,
+            Ok(n) => n,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(format!("读取文件失败 {}: {}", path, e)),
+        };
+        hasher.feed(&buf[..n]);
+        processed += n as u64;
+        if last_report.elapsed() >= PROGRESS_INTERVAL {
+            on_progress(processed, total);
+            last_report = Instant::now();
+        }
+    }
+    on_progress(processed, total);
+    Ok(processed)
+}
+
+// ==================== 校验 ====================
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ChecksumStatus {
+    Ok,
+    Mismatch,
+    Missing,
+    Error,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ChecksumCheck {
+    /// 清单里写的文件名；单文件校验时为路径
+    file: String,
+    path: String,
+    algorithm: Option<HashAlgorithm>,
+    expected: String,
+    actual: Option<String>,
+    status: ChecksumStatus,
+    error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ManifestReport {
+    total: usize,
+    passed: usize,
+    failed: usize,
+    missing: usize,
+    errors: usize,
+    /// 无法解析、已跳过的行号（从 1 开始）
+    skipped_lines: Vec<usize>,
+    entries: Vec<ChecksumCheck>,
+}
+
+/// 清单或粘贴内容里解析出的一条校验值
+#[derive(Debug, PartialEq, Eq)]
+struct ChecksumLine {
+    algorithm: Option<HashAlgorithm>,
+    /// 小写十六进制
+    digest: String,
+    file: Option<String>,
+}
+
+/// 校验单个文件。`expected` 可以是纯十六进制，也可以直接粘贴
+/// `sha256sum` 输出的一行、BSD 格式的 `SHA256 (file) = …` 或 `sha256:…`；
+/// 算法依次取参数、粘贴内容里的标记、摘要长度
+#[tauri::command]
+pub async fn verify_checksum(
+    app: AppHandle,
+    path: String,
+    expected: String,
+    algorithm: Option<HashAlgorithm>,
+) -> Result<ChecksumCheck, String> {
+    let line = parse_checksum_line(expected.trim()).ok_or_else(|| "无法识别校验值".to_string())?;
+    let algorithm = algorithm
+        .or(line.algorithm)
+        .or_else(|| HashAlgorithm::from_hex_len(line.digest.len()))
+        .ok_or_else(|| format!("无法根据 {} 位摘要判断算法，请指定算法", line.digest.len()))?;
+
+    tokio::task::spawn_blocking(move || {
+        let progress_path = path.clone();
+        Ok(check_file(path.clone(), path, algorithm, line.digest, |processed, total| {
+            emit_progress(&app, &progress_path, 0, 1, processed, total);
+        }))
+    })
+    .await
+    .map_err(|e| format!("任务执行失败: {}", e))?
+}
+
+/// 按校验清单逐个校验文件。
+///
+/// 清单中的相对路径相对于 `base_dir`，缺省为清单所在目录。算法依次取
+/// 参数、BSD 行里的标记、清单文件名（`SHA256SUMS`、`app.tar.gz.sha512`）
+/// 和摘要长度。
+#[tauri::command]
+pub async fn verify_manifest(
+    app: AppHandle,
+    manifest: String,
+    base_dir: Option<String>,
+    algorithm: Option<HashAlgorithm>,
+) -> Result<ManifestReport, String> {
+    tokio::task::spawn_blocking(move || {
+        let content = std::fs::read_to_string(&manifest).map_err(|e| format!("无法读取校验清单: {}", e))?;
+        let manifest_path = Path::new(&manifest);
+        let base_dir = match base_dir {
+            Some(dir) => PathBuf::from(dir),
+            None => manifest_path.parent().map(Path::to_path_buf).unwrap_or_default(),
+        };
+        let from_name = manifest_path
+            .file_name()
+            .and_then(|name| algorithm_from_manifest_name(&name.to_string_lossy()));
+
+        let mut skipped_lines = Vec::new();
+        let mut lines = Vec::new();
+        for (number, raw) in content.lines().enumerate() {
+            let raw = raw.trim_end_matches('\r');
+            if raw.trim().is_empty() || raw.trim_start().starts_with('#') {
+                continue;
+            }
+            match parse_checksum_line(raw) {
+                Some(ChecksumLine {
+                    algorithm: line_algorithm,
+                    digest,
+                    file: Some(file),
+                }) => lines.push((algorithm.or(line_algorithm).or(from_name), digest, file)),
+                _ => skipped_lines.push(number + 1),
+            }
+        }
+        if lines.is_empty() {
+            return Err("校验清单中没有可识别的条目".to_string());
+        }
+
+        let count = lines.len();
+        let entries: Vec<ChecksumCheck> = lines
+            .into_iter()
+            .enumerate()
+            .map(|(index, (algorithm, digest, file))| {
+                let path = base_dir.join(&file).to_string_lossy().to_string();
+                let Some(algorithm) = algorithm.or_else(|| HashAlgorithm::from_hex_len(digest.len())) else {
+                    return ChecksumCheck {
+                        file,
+                        path,
+                        algorithm: None,
+                        error: Some(format!("无法根据 {} 位摘要判断算法", digest.len())),
+                        expected: digest,
+                        actual: None,
+                        status: ChecksumStatus::Error,
+                    };
+                };
+                let progress_path = path.clone();
+                check_file(file, path, algorithm, digest, |processed, total| {
+                    emit_progress(&app, &progress_path, index, count, processed, total);
+                })
+            })
+            .collect();
+
+        let tally = |status| entries.iter().filter(|e| e.status == status).count();
+        Ok(ManifestReport {
+            total: entries.len(),
+            passed: tally(ChecksumStatus::Ok),
+            failed: tally(ChecksumStatus::Mismatch),
+            missing: tally(ChecksumStatus::Missing),
+            errors: tally(ChecksumStatus::Error),
+            skipped_lines,
+            entries,
+        })
+    })
+    .await
+    .map_err(|e| format!("任务执行失败: {}", e))?
+}
+
+fn check_file(
+    file: String,
+    path: String,
+    algorithm: HashAlgorithm,
+    expected: String,
+    on_progress: impl FnMut(u64, u64),
+) -> ChecksumCheck {
+    let mut check = ChecksumCheck {
+        file,
+        path,
+        algorithm: Some(algorithm),
+        expected,
+        actual: None,
+        status: ChecksumStatus::Error,
+        error: None,
+    };
+    if !Path::new(&check.path).is_file() {
+        check.status = ChecksumStatus::Missing;
+        check.error = Some("文件不存在".to_string());
+        return check;
+    }
+    let result = MultiHasher::new(&[algorithm], None).and_then(|mut hasher| {
+        stream_file(&check.path, &mut hasher, on_progress)?;
+        hasher.finish(&default_output(None))
+    });
+    match result {
+        Ok(mut digests) => {
+            let actual = digests.remove(0).digest;
+            check.status = if actual == check.expected {
+                ChecksumStatus::Ok
+            } else {
+                ChecksumStatus::Mismatch
+            };
+            check.actual = Some(actual);
+        }
+        Err(e) => check.error = Some(e),
+    }
+    check
+}
+
+/// 解析一行校验值：
+///
+/// - GNU：`<hex>  <file>`、`<hex> *<file>`，以 `\` 开头时文件名含转义
+/// - BSD：`SHA256 (<file>) = <hex>`
+/// - 带前缀：`sha256:<hex>`
+/// - 纯十六进制
+fn parse_checksum_line(line: &str) -> Option<ChecksumLine> {
+    if let Some(parsed) = parse_bsd_line(line) {
+        return Some(parsed);
+    }
+
+    let (escaped, rest) = match line.strip_prefix('\\') {
+        Some(rest) => (true, rest),
+        None => (false, line),
+    };
+    let (head, file) = match rest.split_once([' ', '\t']) {
+        Some((head, tail)) => {
+            let tail = tail.strip_prefix([' ', '*']).unwrap_or(tail);
+            (head, (!tail.is_empty()).then(|| tail.to_string()))
+        }
+        None => (rest, None),
+    };
+    let (algorithm, digest) = match head.split_once(':') {
+        Some((label, digest)) => (Some(HashAlgorithm::from_label(label)?), digest),
+        None => (None, head),
+    };
+    let digest = normalize_hex(digest)?;
+    let file = if escaped { file.map(|f| unescape_file_name(&f)) } else { file };
+    Some(ChecksumLine { algorithm, digest, file })
+}
+
+fn parse_bsd_line(line: &str) -> Option<ChecksumLine> {
+    let (label, rest) = line.split_once(" (")?;
+    let (file, digest) = rest.rsplit_once(") = ")?;
+    Some(ChecksumLine {
+        algorithm: Some(HashAlgorithm::from_label(label.trim_start_matches('\\'))?),
+        digest: normalize_hex(digest)?,
+        file: Some(file.to_string()),
+    })
+}
+
+fn normalize_hex(text: &str) -> Option<String> {
+    let text = text.trim();
+    let valid = !text.is_empty() && text.len().is_multiple_of(2) && text.bytes().all(|b| b.is_ascii_hexdigit());
+    valid.then(|| text.to_ascii_lowercase())
+}
+
+/// GNU coreutils 对含 `\` 或换行的文件名的转义
+fn unescape_file_name(name: &str) -> String {
+    let mut out = String::with_capacity(name.len());
+    let mut chars = name.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some('r') => out.push('\r'),
+            Some(other) => out.push(other),
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
+/// 从清单文件名推断算法，如 `SHA256SUMS`、`b3sums`、`app.tar.gz.sha512`
+fn algorithm_from_manifest_name(name: &str) -> Option<HashAlgorithm> {
+    name.to_ascii_lowercase().rsplit('.').find_map(|part| {
+        let stem = part.strip_suffix("sums").or_else(|| part.strip_suffix("sum")).unwrap_or(part);
+        HashAlgorithm::from_label(stem)
+    })
+}
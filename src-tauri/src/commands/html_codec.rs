@@ -0,0 +1,217 @@
+// `html_decode` / `html_encode` 背后的 HTML 字符引用解码与按上下文转义
+//
+// 解码遵循 HTML5 分词器对字符引用的规则: 在完整实体表中取最长的命名匹配；
+// 旧式名称可以不带 `;`（在属性中后接 `=` 或字母数字时除外）；数字引用中的
+// C1 控制字符按 windows-1252 重新映射，无效码点替换为 U+FFFD。形如 `&name;`
+// 却无法匹配的引用会被报告出来，而不是静默保留。
+
+use serde::{Deserialize, Serialize};
+
+use super::html_entities::{ENTITIES, MAX_NAME_LEN};
+
+/// 每次调用报告的未知引用数上限
+const MAX_REPORTED: usize = 100;
+
+/// 数字引用 `&#128;`..`&#159;` 对应的 windows-1252 字符
+const C1_REMAP: [u32; 32] = [
+    0x20AC, 0x81, 0x201A, 0x0192, 0x201E, 0x2026, 0x2020, 0x2021, 0x02C6, 0x2030, 0x0160, 0x2039, 0x0152, 0x8D,
+    0x017D, 0x8F, 0x90, 0x2018, 0x2019, 0x201C, 0x201D, 0x2022, 0x2013, 0x2014, 0x02DC, 0x2122, 0x0161, 0x203A,
+    0x0153, 0x9D, 0x017E, 0x0178,
+];
+
+#[derive(Serialize)]
+pub struct UnknownEntity {
+    pub entity: String,
+    pub line: usize,
+    pub column: usize,
+}
+
+/// 解码字符引用。`in_attribute` 时对旧式实体应用属性值规则
+/// （`?a=1&copy=2` 保留 `&copy`）。
+pub fn decode(input: &str, in_attribute: bool) -> (String, Vec<UnknownEntity>) {
+    let mut out = String::with_capacity(input.len());
+    let mut unknown = Vec::new();
+    let mut i = 0;
+    while i < input.len() {
+        let Some(amp) = input[i..].find('&') else {
+            out.push_str(&input[i..]);
+            break;
+        };
+        out.push_str(&input[i..i + amp]);
+        i += amp;
+        let rest = &input[i + 1..];
+
+        let consumed = if let Some(numeric) = rest.strip_prefix('#') {
+            decode_numeric(numeric, &mut out).map(|len| len + 1)
+        } else {
+            decode_named(rest, in_attribute, &mut out)
+        };
+        match consumed {
+            Some(len) => i += 1 + len,
+            None => {
+                if let Some(name) = entity_shape(rest) {
+                    if unknown.len() < MAX_REPORTED {
+                        let before = &input[..i];
+                        let line_start = before.rfind('\n').map_or(0, |n| n + 1);
+                        unknown.push(UnknownEntity {
+                            entity: format!("&{}", name),
+                            line: before.matches('\n').count() + 1,
+                            column: before[line_start..].chars().count() + 1,
+                        });
+                    }
+                }
+                out.push('&');
+                i += 1;
+            }
+        }
+    }
+    (out, unknown)
+}
+
+/// `&#NN;` / `&#xHH;`（`;` 可省略），返回 `#` 之后消费的字节数
+fn decode_numeric(rest: &str, out: &mut String) -> Option<usize> {
+    let (radix, prefix) = match rest.as_bytes().first() {
+        Some(b'x' | b'X') => (16, 1),
+        _ => (10, 0),
+    };
+    let digits = rest[prefix..]
+        .bytes()
+        .take_while(|b| b.is_ascii_digit() || (radix == 16 && b.is_ascii_hexdigit()))
+        .count();
+    if digits == 0 {
+        return None;
+    }
+    let code = u32::from_str_radix(&rest[prefix..prefix + digits], radix).unwrap_or(u32::MAX);
+    let code = match code {
+        0x80..=0x9F => C1_REMAP[(code - 0x80) as usize],
+        _ => code,
+    };
+    out.push(match code {
+        0 => '\u{FFFD}',
+        _ => char::from_u32(code).unwrap_or('\u{FFFD}'),
+    });
+    let semicolon = rest[prefix + digits..].starts_with(';') as usize;
+    Some(prefix + digits + semicolon)
+}
+
+/// `rest` 开头最长的命名引用，返回消费的字节数
+fn decode_named(rest: &str, in_attribute: bool, out: &mut String) -> Option<usize> {
+    let alnum = rest.bytes().take_while(u8::is_ascii_alphanumeric).count();
+    let available = (alnum + rest[alnum..].starts_with(';') as usize).min(MAX_NAME_LEN);
+    for len in (2..=available).rev() {
+        let name = &rest[..len];
+        let Ok(index) = ENTITIES.binary_search_by(|(entity, _)| entity.as_bytes().cmp(name.as_bytes())) else {
+            continue;
+        };
+        if !name.ends_with(';') && in_attribute {
+            let next = rest.as_bytes().get(len);
+            if next.is_some_and(|&b| b == b'=' || b.is_ascii_alphanumeric()) {
+                return None;
+            }
+        }
+        out.push_str(ENTITIES[index].1);
+        return Some(len);
+    }
+    None
+}
+
+/// `&` 之后未匹配任何实体的 `name;`，仅当它看起来像引用时返回
+fn entity_shape(rest: &str) -> Option<&str> {
+    let start = rest.starts_with('#') as usize;
+    let end = start + rest[start..].bytes().take_while(u8::is_ascii_alphanumeric).count();
+    (end > start && rest[end..].starts_with(';')).then(|| &rest[..=end])
+}
+
+// ==================== 转义 ====================
+
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum EscapeMode {
+    /// `& < > " '`，可安全用于文本和带引号的属性
+    #[default]
+    Html,
+    /// 元素内容: 仅 `& < >`
+    Text,
+    /// 属性值，无论是否带引号: 另外转义 `` ` `` 和 `=`
+    Attribute,
+    /// JavaScript 字符串字面量内（任意引号），在 `<script>` 块中也安全
+    Js,
+    /// CSS 字符串或标识符内
+    Css,
+    /// XML 1.0 文本和属性
+    Xml,
+}
+
+/// 按 `mode` 转义 `input`。`ascii_only` 时还会用该模式自身的语法转义所有非 ASCII 字符。
+pub fn escape(input: &str, mode: EscapeMode, ascii_only: bool) -> Result<String, String> {
+    let mut out = String::with_capacity(input.len() + input.len() / 4);
+    for c in input.chars() {
+        match mode {
+            EscapeMode::Js => escape_js(c, ascii_only, &mut out),
+            EscapeMode::Css => escape_css(c, ascii_only, &mut out),
+            _ => escape_markup(c, mode, ascii_only, &mut out)?,
+        }
+    }
+    Ok(out)
+}
+
+fn escape_markup(c: char, mode: EscapeMode, ascii_only: bool, out: &mut String) -> Result<(), String> {
+    let entity = match (c, mode) {
+        ('&', _) => "&amp;",
+        ('<', _) => "&lt;",
+        ('>', _) => "&gt;",
+        ('"', EscapeMode::Html | EscapeMode::Attribute | EscapeMode::Xml) => "&quot;",
+        ('\'', EscapeMode::Xml) => "&apos;",
+        ('\'', EscapeMode::Html | EscapeMode::Attribute) => "&#39;",
+        ('`', EscapeMode::Attribute) => "&#96;",
+        ('=', EscapeMode::Attribute) => "&#61;",
+        _ => {
+            if mode == EscapeMode::Xml && (c as u32) < 0x20 && !matches!(c, '\t' | '\n' | '\r') {
+                return Err(format!("XML 1.0 不允许字符 U+{:04X}", c as u32));
+            }
+            if ascii_only && !c.is_ascii() {
+                out.push_str(&format!("&#x{:X};", c as u32));
+            } else {
+                out.push(c);
+            }
+            return Ok(());
+        }
+    };
+    out.push_str(entity);
+    Ok(())
+}
+
+fn escape_js(c: char, ascii_only: bool, out: &mut String) {
+    match c {
+        '\\' => out.push_str("\\\\"),
+        '\'' => out.push_str("\\'"),
+        '"' => out.push_str("\\\""),
+        '`' => out.push_str("\\`"),
+        '\n' => out.push_str("\\n"),
+        '\r' => out.push_str("\\r"),
+        '\t' => out.push_str("\\t"),
+        '\u{8}' => out.push_str("\\b"),
+        '\u{c}' => out.push_str("\\f"),
+        '/' => out.push_str("\\/"),
+        // `</script>`、`<!--` 和 HTML 实体都无法跳出字面量
+        '<' | '>' | '&' => out.push_str(&format!("\\u{:04X}", c as u32)),
+        // 在 JavaScript 中是行终止符，在 JSON 中则不是
+        '\u{2028}' | '\u{2029}' => out.push_str(&format!("\\u{:04X}", c as u32)),
+        c if c.is_control() || (ascii_only && !c.is_ascii()) => {
+            let mut units = [0u16; 2];
+            for unit in c.encode_utf16(&mut units) {
+                out.push_str(&format!("\\u{:04X}", unit));
+            }
+        }
+        c => out.push(c),
+    }
+}
+
+fn escape_css(c: char, ascii_only: bool, out: &mut String) {
+    if c.is_ascii_alphanumeric() || (!c.is_ascii() && !ascii_only && !c.is_control()) {
+        out.push(c);
+    } else {
+        // 十六进制转义在下一个空白处结束，而该空白会被 CSS 吞掉
+        out.push_str(&format!("\\{:X} ", c as u32));
+    }
+}
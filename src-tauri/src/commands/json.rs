@@ -1,6 +1,16 @@
 use serde::Serialize;
 use serde_json::Value;
 use std::collections::HashSet;
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use super::json_format::{self, FormatOptions};
+use super::json_parse::{self, JsonParseMode, JsonRepair};
+use super::json_profile::{self, JsonProfile};
+use super::json_stream::{Event, EventReader};
+use super::query_string::{self, QueryArrayStyle, QueryNestingStyle};
 
 #[derive(Serialize)]
 pub struct JsonFormatResult {
@@ -20,35 +30,36 @@ pub struct JsonUnescapeResult {
 }
 
 /// 美化JSON（格式化）
+///
+/// 保留原始的键顺序和数字字面量；`sort_keys` 递归排序键，`use_tabs` 使用制表符缩进，
+/// `inline_width` 让单行宽度不超过该值的数组保持在一行。
 #[tauri::command]
-pub fn format_json_pretty(json_str: String, indent_size: Option<usize>) -> Result<JsonFormatResult, String> {
+pub fn format_json_pretty(
+    json_str: String,
+    indent_size: Option<usize>,
+    mode: Option<JsonParseMode>,
+    use_tabs: Option<bool>,
+    sort_keys: Option<bool>,
+    inline_width: Option<usize>,
+) -> Result<JsonFormatResult, String> {
     let indent_size = indent_size.unwrap_or(2);
+    let indent = if use_tabs.unwrap_or(false) {
+        "\t".to_string()
+    } else {
+        " ".repeat(indent_size)
+    };
 
-    // 解析JSON
-    let value: Value = serde_json::from_str(&json_str)
+    // 宽松模式先规范化为标准JSON
+    let text = json_parse::to_strict_json(&json_str, mode)
         .map_err(|e| format!("JSON解析错误: {}", e))?;
 
-    // 格式化输出（默认使用2个空格缩进）
-    let formatted = serde_json::to_string_pretty(&value)
-        .map_err(|e| format!("JSON格式化错误: {}", e))?;
-
-    // 如果指定了自定义缩进大小，替换默认的2个空格
-    let formatted = if indent_size != 2 {
-        formatted
-            .split('\n')
-            .map(|line| {
-                // 计算当前行的缩进级别（默认每级2个空格）
-                let leading_spaces = line.chars().take_while(|c| *c == ' ').count();
-                let indent_level = leading_spaces / 2;
-                // 生成新的缩进
-                let new_indent = " ".repeat(indent_level * indent_size);
-                format!("{}{}", new_indent, line.trim_start())
-            })
-            .collect::<Vec<_>>()
-            .join("\n")
-    } else {
-        formatted
+    let options = FormatOptions {
+        indent: Some(indent),
+        sort_keys: sort_keys.unwrap_or(false),
+        inline_width,
     };
+    let formatted = json_format::format_str(&text, &options)
+        .map_err(|e| format!("JSON解析错误: {}", e))?;
 
     Ok(JsonFormatResult {
         formatted,
@@ -58,15 +69,23 @@ pub fn format_json_pretty(json_str: String, indent_size: Option<usize>) -> Resul
 
 /// 压缩JSON（去除所有空格和换行）
 #[tauri::command]
-pub fn compress_json(json_str: String) -> Result<JsonFormatResult, String> {
-    // 先验证JSON是否有效
-    let value: Value = serde_json::from_str(&json_str)
+pub fn compress_json(
+    json_str: String,
+    mode: Option<JsonParseMode>,
+    sort_keys: Option<bool>,
+) -> Result<JsonFormatResult, String> {
+    let text = json_parse::to_strict_json(&json_str, mode)
         .map_err(|e| format!("JSON解析错误: {}", e))?;
-    
-    // 压缩输出（无空格）
-    let compressed = serde_json::to_string(&value)
-        .map_err(|e| format!("JSON压缩错误: {}", e))?;
-    
+
+    // 压缩输出（无空格），同样保留键顺序和数字字面量
+    let options = FormatOptions {
+        indent: None,
+        sort_keys: sort_keys.unwrap_or(false),
+        inline_width: None,
+    };
+    let compressed = json_format::format_str(&text, &options)
+        .map_err(|e| format!("JSON解析错误: {}", e))?;
+
     Ok(JsonFormatResult {
         formatted: compressed,
         is_valid: true,
@@ -75,9 +94,9 @@ pub fn compress_json(json_str: String) -> Result<JsonFormatResult, String> {
 
 /// JSON转义（将JSON字符串转义为可以在代码中使用的字符串）
 #[tauri::command]
-pub fn escape_json(json_str: String) -> Result<JsonEscapeResult, String> {
+pub fn escape_json(json_str: String, mode: Option<JsonParseMode>) -> Result<JsonEscapeResult, String> {
     // 验证JSON是否有效
-    let _: Value = serde_json::from_str(&json_str)
+    json_parse::parse_value(&json_str, mode)
         .map_err(|e| format!("JSON解析错误: {}", e))?;
     
     // 转义JSON字符串
@@ -95,7 +114,7 @@ pub fn escape_json(json_str: String) -> Result<JsonEscapeResult, String> {
 
 /// JSON去转义（将转义的JSON字符串还原）
 #[tauri::command]
-pub fn unescape_json(escaped_str: String) -> Result<JsonUnescapeResult, String> {
+pub fn unescape_json(escaped_str: String, mode: Option<JsonParseMode>) -> Result<JsonUnescapeResult, String> {
     // 去转义
     let unescaped = escaped_str
         .replace("\\n", "\n")   // 换行
@@ -105,7 +124,7 @@ pub fn unescape_json(escaped_str: String) -> Result<JsonUnescapeResult, String>
         .replace("\\\\", "\\"); // 反斜杠（最后处理，避免重复转义）
     
     // 验证去转义后的JSON是否有效
-    let is_valid = serde_json::from_str::<Value>(&unescaped).is_ok();
+    let is_valid = json_parse::parse_value(&unescaped, mode).is_ok();
     
     Ok(JsonUnescapeResult {
         unescaped,
@@ -115,8 +134,8 @@ pub fn unescape_json(escaped_str: String) -> Result<JsonUnescapeResult, String>
 
 /// 验证JSON是否有效
 #[tauri::command]
-pub fn validate_json(json_str: String) -> Result<bool, String> {
-    match serde_json::from_str::<Value>(&json_str) {
+pub fn validate_json(json_str: String, mode: Option<JsonParseMode>) -> Result<bool, String> {
+    match json_parse::parse_value(&json_str, mode) {
         Ok(_) => Ok(true),
         Err(e) => Err(format!("JSON无效: {}", e)),
     }
@@ -127,68 +146,385 @@ pub fn validate_json(json_str: String) -> Result<bool, String> {
 pub struct JsonInfo {
     is_valid: bool,
     size: usize,
-    depth: usize,
-    key_count: usize,
-    value_types: Vec<String>,
+    #[serde(flatten)]
+    profile: JsonProfile,
 }
 
+/// 统计JSON结构：深度、键数量、按路径（数组下标合并为 `[*]`）的类型分布、
+/// 空值比例、数值范围、字符串/数组长度分布、体积最大的子树以及重复键
 #[tauri::command]
-pub fn get_json_info(json_str: String) -> Result<JsonInfo, String> {
-    let value: Value = serde_json::from_str(&json_str)
+pub fn get_json_info(json_str: String, mode: Option<JsonParseMode>) -> Result<JsonInfo, String> {
+    let text = json_parse::to_strict_json(&json_str, mode)
         .map_err(|e| format!("JSON解析错误: {}", e))?;
-    
-    let size = json_str.len();
-    let (depth, key_count, value_types) = analyze_json_value(&value, 0);
-    
+    let profile = json_profile::profile(text.as_bytes())
+        .map_err(|e| format!("JSON解析错误: {}", e))?;
+
     Ok(JsonInfo {
         is_valid: true,
-        size,
-        depth,
-        key_count,
-        value_types: value_types.into_iter().collect(),
+        size: json_str.len(),
+        profile,
     })
 }
 
-fn analyze_json_value(value: &Value, current_depth: usize) -> (usize, usize, std::collections::HashSet<String>) {
-    let mut max_depth = current_depth;
-    let mut key_count = 0;
-    let mut value_types = std::collections::HashSet::new();
-    
-    match value {
-        Value::Object(map) => {
-            value_types.insert("object".to_string());
-            key_count += map.len();
-            for (_, v) in map {
-                let (d, k, t) = analyze_json_value(v, current_depth + 1);
-                max_depth = max_depth.max(d);
-                key_count += k;
-                value_types.extend(t);
-            }
+// ==================== 大文件处理 ====================
+//
+// 以下命令直接读写文件路径，按 token 流式处理，避免把几百MB的JSON整体通过IPC
+// 传给前端。不排序键时内存占用与文件大小无关；sort_keys 会整体缓存每个对象，
+// 内存随最大对象增长。文件版本只接受标准JSON，嵌套不超过 512 层。
+
+#[derive(Serialize)]
+pub struct JsonFileResult {
+    output_path: String,
+    input_size: u64,
+    output_size: u64,
+    elapsed_ms: u128,
+}
+
+/// 美化JSON文件，结果写入 `output_path`（默认 `<文件名>.formatted.json`）
+#[tauri::command]
+pub async fn format_json_file(
+    input_path: String,
+    output_path: Option<String>,
+    indent_size: Option<usize>,
+    use_tabs: Option<bool>,
+    sort_keys: Option<bool>,
+    inline_width: Option<usize>,
+) -> Result<JsonFileResult, String> {
+    let indent = if use_tabs.unwrap_or(false) {
+        "\t".to_string()
+    } else {
+        " ".repeat(indent_size.unwrap_or(2))
+    };
+    let options = FormatOptions {
+        indent: Some(indent),
+        sort_keys: sort_keys.unwrap_or(false),
+        inline_width,
+    };
+    let output = output_path
+        .map(PathBuf::from)
+        .unwrap_or_else(|| sibling_path(&input_path, "formatted"));
+
+    run_blocking(move || format_file(Path::new(&input_path), &output, &options)).await
+}
+
+/// 压缩JSON文件，结果写入 `output_path`（默认 `<文件名>.min.json`）
+#[tauri::command]
+pub async fn compress_json_file(
+    input_path: String,
+    output_path: Option<String>,
+    sort_keys: Option<bool>,
+) -> Result<JsonFileResult, String> {
+    let options = FormatOptions {
+        indent: None,
+        sort_keys: sort_keys.unwrap_or(false),
+        inline_width: None,
+    };
+    let output = output_path
+        .map(PathBuf::from)
+        .unwrap_or_else(|| sibling_path(&input_path, "min"));
+
+    run_blocking(move || format_file(Path::new(&input_path), &output, &options)).await
+}
+
+/// 验证JSON文件是否有效
+#[tauri::command]
+pub async fn validate_json_file(input_path: String) -> Result<bool, String> {
+    run_blocking(move || {
+        let mut reader = open_events(&input_path)?;
+        while reader
+            .next_event()
+            .map_err(|e| format!("JSON无效: {}", e))?
+            .is_some()
+        {}
+        Ok(true)
+    })
+    .await
+}
+
+/// 获取JSON文件结构信息（统计项同 `get_json_info`）
+#[tauri::command]
+pub async fn get_json_info_file(input_path: String) -> Result<JsonInfo, String> {
+    run_blocking(move || {
+        let size = fs::metadata(&input_path)
+            .map_err(|e| format!("无法读取文件信息: {}", e))?
+            .len();
+        let file = File::open(&input_path).map_err(|e| format!("无法打开文件: {}", e))?;
+        let profile = json_profile::profile(BufReader::new(file))
+            .map_err(|e| format!("JSON解析错误: {}", e))?;
+
+        Ok(JsonInfo {
+            is_valid: true,
+            size: size as usize,
+            profile,
+        })
+    })
+    .await
+}
+
+#[derive(Serialize)]
+pub struct JsonTreeNode {
+    /// 对象的键，或数组下标
+    key: String,
+    /// 该节点的 JSON Pointer，可直接用于继续展开
+    pointer: String,
+    value_type: String,
+    /// 标量的原始文本（过长时截断）
+    preview: Option<String>,
+    /// 对象/数组的直接子节点数量
+    child_count: Option<usize>,
+}
+
+#[derive(Serialize)]
+pub struct JsonTreePage {
+    pointer: String,
+    value_type: String,
+    offset: usize,
+    children: Vec<JsonTreeNode>,
+    has_more: bool,
+}
+
+/// 树形视图分页加载：列出 `pointer`（RFC 6901 JSON Pointer，空串为根）
+/// 所指对象/数组从 `offset` 起的至多 `limit` 个子节点。
+/// 每次调用都从文件头流式扫描，取满一页即停止，不会把整个文件读入内存。
+#[tauri::command]
+pub async fn json_tree_children(
+    path: String,
+    pointer: String,
+    offset: Option<usize>,
+    limit: Option<usize>,
+) -> Result<JsonTreePage, String> {
+    let offset = offset.unwrap_or(0);
+    let limit = limit.unwrap_or(TREE_PAGE_SIZE).max(1);
+    run_blocking(move || {
+        let segments = parse_json_pointer(&pointer)?;
+        let mut reader = open_events(&path)?;
+        let parse_err = |e: String| format!("JSON解析错误: {}", e);
+
+        // 沿着 pointer 逐级下降，跳过不相关的兄弟节点
+        let mut event = next_required(&mut reader).map_err(parse_err)?;
+        for segment in &segments {
+            event = match event {
+                Event::BeginObject => loop {
+                    match next_required(&mut reader).map_err(parse_err)? {
+                        Event::Key(raw) => {
+                            let value = next_required(&mut reader).map_err(parse_err)?;
+                            if decode_key(&raw) == *segment {
+                                break value;
+                            }
+                            reader.skip_value(&value).map_err(parse_err)?;
+                        }
+                        _ => return Err(format!("未找到节点: {}", pointer)),
+                    }
+                },
+                Event::BeginArray => {
+                    let index: usize = segment
+                        .parse()
+                        .map_err(|_| format!("无效的数组下标: {}", segment))?;
+                    let mut i = 0;
+                    loop {
+                        let value = next_required(&mut reader).map_err(parse_err)?;
+                        if value == Event::EndArray {
+                            return Err(format!("未找到节点: {}", pointer));
+                        }
+                        if i == index {
+                            break value;
+                        }
+                        reader.skip_value(&value).map_err(parse_err)?;
+                        i += 1;
+                    }
+                }
+                _ => return Err(format!("未找到节点: {}", pointer)),
+            };
         }
-        Value::Array(arr) => {
-            value_types.insert("array".to_string());
-            for v in arr {
-                let (d, k, t) = analyze_json_value(v, current_depth + 1);
-                max_depth = max_depth.max(d);
-                key_count += k;
-                value_types.extend(t);
+
+        let is_object = match event {
+            Event::BeginObject => true,
+            Event::BeginArray => false,
+            _ => return Err("该节点不是对象或数组".to_string()),
+        };
+
+        let mut children = Vec::new();
+        let mut has_more = false;
+        let mut index = 0;
+        loop {
+            let (key, value) = if is_object {
+                match next_required(&mut reader).map_err(parse_err)? {
+                    Event::Key(raw) => (decode_key(&raw), next_required(&mut reader).map_err(parse_err)?),
+                    _ => break,
+                }
+            } else {
+                match next_required(&mut reader).map_err(parse_err)? {
+                    Event::EndArray => break,
+                    value => (index.to_string(), value),
+                }
+            };
+
+            if index >= offset + limit {
+                has_more = true;
+                break;
             }
+            let child_count = reader.skip_value(&value).map_err(parse_err)?;
+            if index >= offset {
+                let (value_type, preview, child_count) = match &value {
+                    Event::BeginObject => ("object", None, Some(child_count)),
+                    Event::BeginArray => ("array", None, Some(child_count)),
+                    Event::Scalar(raw) => (scalar_type(raw), Some(truncate_preview(raw)), None),
+                    _ => unreachable!("next_required only yields value starts here"),
+                };
+                children.push(JsonTreeNode {
+                    pointer: format!("{}/{}", pointer, escape_pointer_segment(&key)),
+                    key,
+                    value_type: value_type.to_string(),
+                    preview,
+                    child_count,
+                });
+            }
+            index += 1;
         }
-        Value::String(_) => {
-            value_types.insert("string".to_string());
-        }
-        Value::Number(_) => {
-            value_types.insert("number".to_string());
-        }
-        Value::Bool(_) => {
-            value_types.insert("boolean".to_string());
-        }
-        Value::Null => {
-            value_types.insert("null".to_string());
+
+        Ok(JsonTreePage {
+            pointer,
+            value_type: if is_object { "object" } else { "array" }.to_string(),
+            offset,
+            children,
+            has_more,
+        })
+    })
+    .await
+}
+
+const TREE_PAGE_SIZE: usize = 100;
+const PREVIEW_CHARS: usize = 200;
+
+/// 在阻塞线程池中执行文件IO，避免卡住前端
+async fn run_blocking<T, F>(f: F) -> Result<T, String>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T, String> + Send + 'static,
+{
+    tokio::task::spawn_blocking(f)
+        .await
+        .map_err(|e| format!("任务执行失败: {}", e))?
+}
+
+fn open_events(path: &str) -> Result<EventReader<BufReader<File>>, String> {
+    let file = File::open(path).map_err(|e| format!("无法打开文件: {}", e))?;
+    Ok(EventReader::new(BufReader::new(file)))
+}
+
+fn next_required<R: std::io::BufRead>(reader: &mut EventReader<R>) -> Result<Event, String> {
+    reader
+        .next_event()?
+        .ok_or_else(|| "意外的输入结束".to_string())
+}
+
+fn format_file(input: &Path, output: &Path, options: &FormatOptions) -> Result<JsonFileResult, String> {
+    let started = Instant::now();
+    if let (Ok(a), Ok(b)) = (input.canonicalize(), output.canonicalize()) {
+        if a == b {
+            return Err("输出文件不能与输入文件相同".to_string());
         }
     }
-    
-    (max_depth, key_count, value_types)
+
+    let input_size = fs::metadata(input)
+        .map_err(|e| format!("无法读取文件信息: {}", e))?
+        .len();
+    let reader = BufReader::new(File::open(input).map_err(|e| format!("无法打开文件: {}", e))?);
+    let writer = BufWriter::new(File::create(output).map_err(|e| format!("无法创建输出文件: {}", e))?);
+
+    if let Err(e) = json_format::format_to(reader, writer, options) {
+        // 不留下写了一半的输出文件
+        let _ = fs::remove_file(output);
+        return Err(format!("JSON解析错误: {}", e));
+    }
+
+    let output_size = fs::metadata(output)
+        .map_err(|e| format!("无法读取输出文件信息: {}", e))?
+        .len();
+    Ok(JsonFileResult {
+        output_path: output.to_string_lossy().to_string(),
+        input_size,
+        output_size,
+        elapsed_ms: started.elapsed().as_millis(),
+    })
+}
+
+/// `data.json` -> `data.<suffix>.json`，与输入文件同目录
+fn sibling_path(input_path: &str, suffix: &str) -> PathBuf {
+    let mut path = PathBuf::from(input_path);
+    let stem = path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("output")
+        .to_string();
+    path.set_file_name(format!("{}.{}.json", stem, suffix));
+    path
+}
+
+fn scalar_type(raw: &str) -> &'static str {
+    match raw.as_bytes().first() {
+        Some(b'"') => "string",
+        Some(b't') | Some(b'f') => "boolean",
+        Some(b'n') => "null",
+        _ => "number",
+    }
+}
+
+fn decode_key(raw: &str) -> String {
+    serde_json::from_str(raw).unwrap_or_else(|_| raw.to_string())
+}
+
+fn truncate_preview(raw: &str) -> String {
+    match raw.char_indices().nth(PREVIEW_CHARS) {
+        Some((end, _)) => format!("{}…", &raw[..end]),
+        None => raw.to_string(),
+    }
+}
+
+fn parse_json_pointer(pointer: &str) -> Result<Vec<String>, String> {
+    if pointer.is_empty() {
+        return Ok(Vec::new());
+    }
+    let Some(rest) = pointer.strip_prefix('/') else {
+        return Err(format!("无效的 JSON Pointer: {}", pointer));
+    };
+    Ok(rest
+        .split('/')
+        .map(|s| s.replace("~1", "/").replace("~0", "~"))
+        .collect())
+}
+
+fn escape_pointer_segment(segment: &str) -> String {
+    segment.replace('~', "~0").replace('/', "~1")
+}
+
+// ==================== JSON修复功能 ====================
+
+#[derive(Serialize)]
+pub struct JsonRepairResult {
+    repaired: String,
+    changed: bool,
+    repairs: Vec<JsonRepair>,
+}
+
+/// 修复常见的JSON损坏（注释、单引号、尾随逗号、Python字面量、截断等），并报告修改内容
+#[tauri::command]
+pub fn repair_json(json_str: String) -> Result<JsonRepairResult, String> {
+    let (strict, repairs) = json_parse::repair(&json_str)
+        .map_err(|e| format!("JSON修复失败: {}", e))?;
+
+    let options = FormatOptions {
+        indent: Some("  ".to_string()),
+        ..FormatOptions::default()
+    };
+    let repaired = json_format::format_str(&strict, &options)
+        .map_err(|e| format!("JSON修复失败: {}", e))?;
+
+    Ok(JsonRepairResult {
+        repaired,
+        changed: !repairs.is_empty(),
+        repairs,
+    })
 }
 
 // ==================== JSON转Query参数功能 ====================
@@ -197,83 +533,91 @@ fn analyze_json_value(value: &Value, current_depth: usize) -> (usize, usize, std
 pub struct JsonToQueryResult {
     query_string: String,
     encoded: String,
+    /// application/x-www-form-urlencoded 请求体（空格编码为 `+`）
+    form_body: String,
+    /// multipart/form-data 请求体预览及对应的 Content-Type
+    multipart_body: String,
+    multipart_content_type: String,
 }
 
 /// 将JSON对象转换为URL Query参数
+///
+/// `array_style`: indices `a[0]=x` / brackets `a[]=x` / repeat `a=x&a=y` / comma `a=x,y`；
+/// `nesting_style`: brackets `a[b]=x` / dots `a.b=x`
 #[tauri::command]
-pub fn json_to_query_params(json_str: String) -> Result<JsonToQueryResult, String> {
-    let value: Value = serde_json::from_str(&json_str)
+pub fn json_to_query_params(
+    json_str: String,
+    mode: Option<JsonParseMode>,
+    array_style: Option<QueryArrayStyle>,
+    nesting_style: Option<QueryNestingStyle>,
+) -> Result<JsonToQueryResult, String> {
+    let value = json_parse::parse_value(&json_str, mode)
         .map_err(|e| format!("JSON解析错误: {}", e))?;
 
-    let mut pairs = Vec::new();
-    flatten_json_value(&value, String::new(), &mut pairs);
+    let pairs = query_string::flatten(
+        &value,
+        array_style.unwrap_or_default(),
+        nesting_style.unwrap_or_default(),
+    );
 
     let query_string = pairs
         .iter()
         .map(|(k, v)| format!("{}={}", k, v))
         .collect::<Vec<_>>()
         .join("&");
-
-    let encoded = pairs
-        .iter()
-        .map(|(k, v)| format!("{}={}", urlencoding_encode(k), urlencoding_encode(v)))
-        .collect::<Vec<_>>()
-        .join("&");
+    let (multipart_content_type, multipart_body) = query_string::encode_multipart(&pairs);
 
     Ok(JsonToQueryResult {
         query_string,
-        encoded,
+        encoded: query_string::encode_query(&pairs),
+        form_body: query_string::encode_form(&pairs),
+        multipart_body,
+        multipart_content_type,
     })
 }
 
-fn flatten_json_value(value: &Value, prefix: String, pairs: &mut Vec<(String, String)>) {
-    match value {
-        Value::Object(map) => {
-            for (k, v) in map {
-                let new_key = if prefix.is_empty() {
-                    k.clone()
-                } else {
-                    format!("{}[{}]", prefix, k)
-                };
-                flatten_json_value(v, new_key, pairs);
-            }
-        }
-        Value::Array(arr) => {
-            for (i, v) in arr.iter().enumerate() {
-                let new_key = format!("{}[{}]", prefix, i);
-                flatten_json_value(v, new_key, pairs);
-            }
-        }
-        Value::String(s) => {
-            pairs.push((prefix, s.clone()));
-        }
-        Value::Number(n) => {
-            pairs.push((prefix, n.to_string()));
-        }
-        Value::Bool(b) => {
-            pairs.push((prefix, b.to_string()));
-        }
-        Value::Null => {
-            pairs.push((prefix, String::new()));
-        }
-    }
+#[derive(Serialize)]
+pub struct QueryParam {
+    key: String,
+    value: String,
 }
 
-fn urlencoding_encode(s: &str) -> String {
-    let mut result = String::new();
-    for c in s.chars() {
-        match c {
-            'A'..='Z' | 'a'..='z' | '0'..='9' | '-' | '_' | '.' | '~' => {
-                result.push(c);
-            }
-            _ => {
-                for b in c.to_string().as_bytes() {
-                    result.push_str(&format!("%{:02X}", b));
-                }
-            }
-        }
-    }
-    result
+#[derive(Serialize)]
+pub struct QueryToJsonResult {
+    json: String,
+    params: Vec<QueryParam>,
+}
+
+/// 将URL Query参数（或完整URL、表单请求体）还原为JSON
+///
+/// 始终识别方括号嵌套和重复键；`nesting_style` 为 dots 时额外按 `.` 拆分键，
+/// `array_style` 为 comma 时按 `,` 拆分值。`plus_as_space` 默认开启（表单解码）。
+/// `infer_types` 将 `42`、`true`、`null` 还原为对应的JSON类型。
+#[tauri::command]
+pub fn query_params_to_json(
+    query: String,
+    array_style: Option<QueryArrayStyle>,
+    nesting_style: Option<QueryNestingStyle>,
+    plus_as_space: Option<bool>,
+    infer_types: Option<bool>,
+) -> Result<QueryToJsonResult, String> {
+    let pairs = query_string::parse_pairs(&query, plus_as_space.unwrap_or(true));
+    let value = query_string::unflatten(
+        &pairs,
+        array_style.unwrap_or_default(),
+        nesting_style.unwrap_or_default(),
+        infer_types.unwrap_or(false),
+    );
+    let json = serde_json::to_string_pretty(&value)
+        .map_err(|e| format!("JSON格式化错误: {}", e))?;
+
+    Ok(QueryToJsonResult {
+        json,
+        params: pairs
+            .into_iter()
+            .map(|(key, value)| QueryParam { key, value })
+            .collect(),
+    })
 }
 
 // ==================== JSON对比功能 ====================
@@ -295,11 +639,11 @@ pub struct JsonDiffResult {
 
 /// 对比两个JSON的差异
 #[tauri::command]
-pub fn compare_json(json1: String, json2: String) -> Result<JsonDiffResult, String> {
+pub fn compare_json(json1: String, json2: String, mode: Option<JsonParseMode>) -> Result<JsonDiffResult, String> {
     // 解析两个JSON
-    let value1: Value = serde_json::from_str(&json1)
+    let value1 = json_parse::parse_value(&json1, mode)
         .map_err(|e| format!("第一个JSON解析错误: {}", e))?;
-    let value2: Value = serde_json::from_str(&json2)
+    let value2 = json_parse::parse_value(&json2, mode)
         .map_err(|e| format!("第二个JSON解析错误: {}", e))?;
 
     // 执行对比
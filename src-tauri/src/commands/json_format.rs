@@ -0,0 +1,390 @@
+// 保持原样的 JSON 格式化器
+//
+// 经过 `serde_json::Value` 时数字要经过 i64/u64/f64 转换，
+// `123456789012345678901234567890` 或 `1.0` 出来就变了样，重复的键也只剩最后
+// 一个，而且整个文档都要载入内存。本格式化器从不构建
+// `Value`: `json_stream::Lexer` 把严格 JSON 切分为记号，保留字符串和数字的原始
+// 文本，`Formatter` 用新的空白重新输出这些记号。键、转义和数字字面量逐字节保持不变。
+//
+// 输出以流的方式写入任意 `Write`。有两个选项需要向前查看:
+//   sort_keys    — 每个对象缓冲到右花括号为止，以便（递归地）重排成员。
+//   inline_width — 数组缓冲到闭合或单行形式超出宽度为止，以先到者为准。
+// 不用这两个选项时，内存占用以最长的单个记号为界；开启 sort_keys 时最大的
+// 对象会整个留在内存中。格式化器按容器递归，嵌套深度上限为 `MAX_DEPTH`。
+
+use std::collections::VecDeque;
+use std::io::{BufRead, Write};
+
+use super::json_stream::{Kind, Lexer, Token};
+
+/// 嵌套上限，与 `json_parse` 相同，用于保护递归格式化器的栈
+const MAX_DEPTH: usize = 512;
+
+#[derive(Debug, Clone, Default)]
+pub struct FormatOptions {
+    /// 一级缩进（如两个空格或一个制表符），`None` 输出压缩格式
+    pub indent: Option<String>,
+    /// 递归排序对象的键（按解码后的键，码点顺序）
+    pub sort_keys: bool,
+    /// 数组的单行形式（含嵌套值）不超过此宽度时保持在一行
+    pub inline_width: Option<usize>,
+}
+
+/// 格式化内存中的严格 JSON 文本
+pub fn format_str(input: &str, options: &FormatOptions) -> Result<String, String> {
+    let mut out = Vec::with_capacity(input.len() + input.len() / 2);
+    format_to(input.as_bytes(), &mut out, options)?;
+    String::from_utf8(out).map_err(|e| format!("输出不是有效的 UTF-8: {}", e))
+}
+
+/// 把从 `reader` 读入的严格 JSON 格式化后写入 `writer`
+pub fn format_to<R: BufRead, W: Write>(reader: R, writer: W, options: &FormatOptions) -> Result<(), String> {
+    Formatter {
+        lexer: Lexer::new(reader),
+        pushback: VecDeque::new(),
+        out: writer,
+        options,
+    }
+    .run()
+}
+
+// ==================== 格式化器 ====================
+
+/// 完整缓冲的子树，在 `sort_keys` 或 `inline_width` 需要看到整个容器才能输出时使用
+enum Node {
+    Scalar(String),
+    Array(Vec<Node>),
+    Object(Vec<(String, Node)>),
+}
+
+struct Formatter<'o, R, W> {
+    lexer: Lexer<R>,
+    /// `lookahead_array` 预读、尚待格式化的记号
+    pushback: VecDeque<Token>,
+    out: W,
+    options: &'o FormatOptions,
+}
+
+impl<R: BufRead, W: Write> Formatter<'_, R, W> {
+    fn run(mut self) -> Result<(), String> {
+        let first = self.next()?;
+        self.value(first, 0)?;
+        if let Some(extra) = self.pull()? {
+            return Err(extra.error("JSON 之后存在多余的内容"));
+        }
+        self.out.flush().map_err(|e| format!("写入输出失败: {}", e))
+    }
+
+    fn pull(&mut self) -> Result<Option<Token>, String> {
+        match self.pushback.pop_front() {
+            Some(token) => Ok(Some(token)),
+            None => self.lexer.next_token(),
+        }
+    }
+
+    fn next(&mut self) -> Result<Token, String> {
+        self.pull()?
+            .ok_or_else(|| self.lexer.error("意外的输入结束"))
+    }
+
+    fn write(&mut self, s: &str) -> Result<(), String> {
+        self.out
+            .write_all(s.as_bytes())
+            .map_err(|e| format!("写入输出失败: {}", e))
+    }
+
+    fn newline(&mut self, depth: usize) -> Result<(), String> {
+        if let Some(indent) = &self.options.indent {
+            let line = format!("\n{}", indent.repeat(depth));
+            self.write(&line)?;
+        }
+        Ok(())
+    }
+
+    fn colon(&self) -> &'static str {
+        if self.options.indent.is_some() {
+            ": "
+        } else {
+            ":"
+        }
+    }
+
+    fn value(&mut self, token: Token, depth: usize) -> Result<(), String> {
+        check_depth(&token, depth)?;
+        match token.kind {
+            Kind::Scalar(s) => self.write(&s),
+            Kind::BeginObject if self.options.sort_keys => {
+                let node = self.read_object(depth)?;
+                self.render(&node, depth)
+            }
+            Kind::BeginObject => self.stream_object(depth),
+            Kind::BeginArray => {
+                if let Some(node) = self.lookahead_array(depth)? {
+                    return self.render(&node, depth);
+                }
+                self.stream_array(depth)
+            }
+            _ => Err(token.error("期望一个值")),
+        }
+    }
+
+    fn stream_object(&mut self, depth: usize) -> Result<(), String> {
+        self.write("{")?;
+        let mut token = self.next()?;
+        if token.kind == Kind::EndObject {
+            return self.write("}");
+        }
+        loop {
+            let key = token.into_key()?;
+            self.newline(depth + 1)?;
+            self.write(&key)?;
+            let colon = self.next()?;
+            if colon.kind != Kind::Colon {
+                return Err(colon.error("期望 ':'"));
+            }
+            self.write(self.colon())?;
+            let value = self.next()?;
+            self.value(value, depth + 1)?;
+
+            let sep = self.next()?;
+            match sep.kind {
+                Kind::Comma => {
+                    self.write(",")?;
+                    token = self.next()?;
+                }
+                Kind::EndObject => {
+                    self.newline(depth)?;
+                    return self.write("}");
+                }
+                _ => return Err(sep.error("期望 ',' 或 '}'")),
+            }
+        }
+    }
+
+    fn stream_array(&mut self, depth: usize) -> Result<(), String> {
+        self.write("[")?;
+        let mut token = self.next()?;
+        if token.kind == Kind::EndArray {
+            return self.write("]");
+        }
+        loop {
+            self.newline(depth + 1)?;
+            self.value(token, depth + 1)?;
+
+            let sep = self.next()?;
+            match sep.kind {
+                Kind::Comma => {
+                    self.write(",")?;
+                    token = self.next()?;
+                }
+                Kind::EndArray => {
+                    self.newline(depth)?;
+                    return self.write("]");
+                }
+                _ => return Err(sep.error("期望 ',' 或 ']'")),
+            }
+        }
+    }
+
+    /// 在 `[` 之后预读，判断数组能否放在一行。能则返回缓冲的数组；
+    /// 否则把已读的记号退回，由调用方照常流式输出数组。
+    fn lookahead_array(&mut self, depth: usize) -> Result<Option<Node>, String> {
+        let Some(max) = self.options.inline_width else {
+            return Ok(None);
+        };
+        if self.options.indent.is_none() {
+            return Ok(None);
+        }
+
+        let mut buffered = Vec::new();
+        let mut width = 1;
+        let mut open = 1usize;
+        let fits = loop {
+            let token = self.next()?;
+            width += token_width(&token);
+            match token.kind {
+                Kind::BeginObject | Kind::BeginArray => open += 1,
+                Kind::EndObject | Kind::EndArray => open -= 1,
+                _ => {}
+            }
+            buffered.push(token);
+            if open == 0 {
+                break width <= max;
+            }
+            if width > max {
+                break false;
+            }
+        };
+
+        for token in buffered.into_iter().rev() {
+            self.pushback.push_front(token);
+        }
+        if fits {
+            self.read_array(depth).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn read_node(&mut self, token: Token, depth: usize) -> Result<Node, String> {
+        check_depth(&token, depth)?;
+        match token.kind {
+            Kind::Scalar(s) => Ok(Node::Scalar(s)),
+            Kind::BeginObject => self.read_object(depth),
+            Kind::BeginArray => self.read_array(depth),
+            _ => Err(token.error("期望一个值")),
+        }
+    }
+
+    fn read_object(&mut self, depth: usize) -> Result<Node, String> {
+        let mut members = Vec::new();
+        let mut token = self.next()?;
+        if token.kind == Kind::EndObject {
+            return Ok(Node::Object(members));
+        }
+        loop {
+            let key = token.into_key()?;
+            let colon = self.next()?;
+            if colon.kind != Kind::Colon {
+                return Err(colon.error("期望 ':'"));
+            }
+            let value = self.next()?;
+            members.push((key, self.read_node(value, depth + 1)?));
+
+            let sep = self.next()?;
+            match sep.kind {
+                Kind::Comma => token = self.next()?,
+                Kind::EndObject => return Ok(Node::Object(members)),
+                _ => return Err(sep.error("期望 ',' 或 '}'")),
+            }
+        }
+    }
+
+    fn read_array(&mut self, depth: usize) -> Result<Node, String> {
+        let mut items = Vec::new();
+        let mut token = self.next()?;
+        if token.kind == Kind::EndArray {
+            return Ok(Node::Array(items));
+        }
+        loop {
+            items.push(self.read_node(token, depth + 1)?);
+            let sep = self.next()?;
+            match sep.kind {
+                Kind::Comma => token = self.next()?,
+                Kind::EndArray => return Ok(Node::Array(items)),
+                _ => return Err(sep.error("期望 ',' 或 ']'")),
+            }
+        }
+    }
+
+    /// 按输出顺序排列的对象成员
+    fn ordered<'n>(&self, members: &'n [(String, Node)]) -> Vec<&'n (String, Node)> {
+        let mut ordered: Vec<_> = members.iter().collect();
+        if self.options.sort_keys {
+            ordered.sort_by_cached_key(|(key, _)| serde_json::from_str::<String>(key).unwrap_or_else(|_| key.clone()));
+        }
+        ordered
+    }
+
+    fn fits_inline(&self, node: &Node) -> bool {
+        match self.options.inline_width {
+            Some(max) if self.options.indent.is_some() => inline_width(node) <= max,
+            _ => false,
+        }
+    }
+
+    fn render(&mut self, node: &Node, depth: usize) -> Result<(), String> {
+        match node {
+            Node::Scalar(s) => self.write(s),
+            Node::Array(items) if items.is_empty() => self.write("[]"),
+            Node::Object(members) if members.is_empty() => self.write("{}"),
+            Node::Array(_) if self.fits_inline(node) => self.render_inline(node),
+            Node::Array(items) => {
+                self.write("[")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        self.write(",")?;
+                    }
+                    self.newline(depth + 1)?;
+                    self.render(item, depth + 1)?;
+                }
+                self.newline(depth)?;
+                self.write("]")
+            }
+            Node::Object(members) => {
+                self.write("{")?;
+                for (i, (key, value)) in self.ordered(members).into_iter().enumerate() {
+                    if i > 0 {
+                        self.write(",")?;
+                    }
+                    self.newline(depth + 1)?;
+                    self.write(key)?;
+                    self.write(self.colon())?;
+                    self.render(value, depth + 1)?;
+                }
+                self.newline(depth)?;
+                self.write("}")
+            }
+        }
+    }
+
+    fn render_inline(&mut self, node: &Node) -> Result<(), String> {
+        match node {
+            Node::Scalar(s) => self.write(s),
+            Node::Array(items) => {
+                self.write("[")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        self.write(", ")?;
+                    }
+                    self.render_inline(item)?;
+                }
+                self.write("]")
+            }
+            Node::Object(members) => {
+                self.write("{")?;
+                for (i, (key, value)) in self.ordered(members).into_iter().enumerate() {
+                    if i > 0 {
+                        self.write(", ")?;
+                    }
+                    self.write(key)?;
+                    self.write(": ")?;
+                    self.render_inline(value)?;
+                }
+                self.write("}")
+            }
+        }
+    }
+}
+
+/// `depth` 是 `token` 所在的嵌套层级，此处出现的容器会开启第 `depth + 1` 层
+fn check_depth(token: &Token, depth: usize) -> Result<(), String> {
+    if depth >= MAX_DEPTH && matches!(token.kind, Kind::BeginObject | Kind::BeginArray) {
+        return Err(token.error("嵌套层级过深"));
+    }
+    Ok(())
+}
+
+/// 记号在单行输出中的宽度，含分隔符
+fn token_width(token: &Token) -> usize {
+    match &token.kind {
+        Kind::Scalar(s) => s.chars().count(),
+        Kind::Colon | Kind::Comma => 2,
+        _ => 1,
+    }
+}
+
+/// `node` 以 `", "` / `": "` 渲染为单行时的宽度
+fn inline_width(node: &Node) -> usize {
+    match node {
+        Node::Scalar(s) => s.chars().count(),
+        Node::Array(items) => 2 + items.iter().map(inline_width).sum::<usize>() + items.len().saturating_sub(1) * 2,
+        Node::Object(members) => {
+            2 + members
+                .iter()
+                .map(|(key, value)| key.chars().count() + 2 + inline_width(value))
+                .sum::<usize>()
+                + members.len().saturating_sub(1) * 2
+        }
+    }
+}
@@ -0,0 +1,783 @@
+// `commands::json` 各工具共用的 JSON 解析前端
+//
+// 每个 JSON 命令都接受可选的 `mode`:
+//   strict  — 直接使用 `serde_json`，与命令一直以来的行为一致。
+//   lenient — JSON5 / JSONC: 注释、单引号字符串、不带引号的键、
+//             尾随逗号、十六进制数字、前导 `+` / `.`。
+//
+// 宽松输入先由 `Normalizer` 改写为严格 JSON 文本，再交给 `serde_json`，
+// 后续流程看到的始终是标准 JSON。`repair_json` 以 `repair` 模式运行同一个
+// 规范化器，额外修复任何 JSON 方言都不允许的损坏（Python 的 `True/None`、
+// 缺失的逗号、被截断的输入、日志前缀），并记录每一处修改。
+
+use std::borrow::Cow;
+
+use num_bigint::BigUint;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// 规范化器的嵌套上限。它按容器递归，此限制用于保护栈；
+/// `serde_json` 自身在 128 层时就会放弃。
+const MAX_DEPTH: usize = 512;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum JsonParseMode {
+    #[default]
+    Strict,
+    Lenient,
+}
+
+/// 修复时做出的一处修改。`line` / `column` 从 1 开始，指向*原始*输入中的位置。
+#[derive(Debug, Clone, Serialize)]
+pub struct JsonRepair {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+/// 按 `mode` 解析 `input`（`None` 表示严格模式）。错误信息不带前缀，
+/// 调用方可以沿用各自的措辞。
+pub fn parse_value(input: &str, mode: Option<JsonParseMode>) -> Result<Value, String> {
+    let text = to_strict_json(input, mode)?;
+    serde_json::from_str(&text).map_err(|e| e.to_string())
+}
+
+/// 把 `input` 改写为严格 JSON 文本。严格模式直接借用输入，
+/// 宽松模式返回紧凑的规范化文本。
+pub fn to_strict_json(input: &str, mode: Option<JsonParseMode>) -> Result<Cow<'_, str>, String> {
+    match mode.unwrap_or_default() {
+        JsonParseMode::Strict => Ok(Cow::Borrowed(input)),
+        JsonParseMode::Lenient => Normalizer::new(input, false).run().map(|(text, _)| Cow::Owned(text)),
+    }
+}
+
+/// 把 `input` 修复为严格 JSON 文本，同时返回修改列表
+pub fn repair(input: &str) -> Result<(String, Vec<JsonRepair>), String> {
+    if input.trim().is_empty() {
+        return Err("输入不能为空".to_string());
+    }
+    let first = Normalizer::new(input, true).run();
+
+    // 日志行通常带有前缀（"2024-05-01 INFO payload={...}"）。
+    // 若从头解析得不到干净的文档，且输入不以容器开头，则从第一个括号处重试。
+    let opens_with_container = matches!(input.trim_start().chars().next(), Some('{') | Some('['));
+    let clean = matches!(&first, Ok((_, repairs)) if !repairs.iter().any(|r| r.message == TRAILING_CONTENT));
+    if clean || opens_with_container {
+        return first;
+    }
+    let Some(start) = input.find(['{', '[']) else {
+        return first;
+    };
+    let mut retry = Normalizer::new(&input[start..], true);
+    // 报告的位置仍相对于原始输入
+    for c in input[..start].chars() {
+        retry.advance_position(c);
+    }
+    match retry.run() {
+        Ok((text, mut repairs)) => {
+            let skipped = input[..start].chars().count();
+            repairs.insert(
+                0,
+                JsonRepair {
+                    line: 1,
+                    column: 1,
+                    message: format!("跳过开头 {} 个非 JSON 字符", skipped),
+                },
+            );
+            Ok((text, repairs))
+        }
+        Err(_) => first,
+    }
+}
+
+const TRAILING_CONTENT: &str = "删除 JSON 之后多余的内容";
+
+struct Normalizer<'a> {
+    src: &'a str,
+    pos: usize,
+    line: usize,
+    column: usize,
+    repair: bool,
+    depth: usize,
+    out: String,
+    repairs: Vec<JsonRepair>,
+}
+
+impl<'a> Normalizer<'a> {
+    fn new(src: &'a str, repair: bool) -> Self {
+        Normalizer {
+            src,
+            pos: 0,
+            line: 1,
+            column: 1,
+            repair,
+            depth: 0,
+            out: String::with_capacity(src.len()),
+            repairs: Vec::new(),
+        }
+    }
+
+    fn run(mut self) -> Result<(String, Vec<JsonRepair>), String> {
+        self.skip_trivia()?;
+        self.value()?;
+        self.skip_trivia()?;
+        if self.peek().is_some() {
+            if !self.repair {
+                return Err(self.error("JSON 之后存在多余的内容"));
+            }
+            self.note(TRAILING_CONTENT);
+        }
+        Ok((self.out, self.repairs))
+    }
+
+    // ==================== 游标 ====================
+
+    fn peek(&self) -> Option<char> {
+        self.src[self.pos..].chars().next()
+    }
+
+    fn peek_second(&self) -> Option<char> {
+        self.src[self.pos..].chars().nth(1)
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        self.advance_position(c);
+        Some(c)
+    }
+
+    fn advance_position(&mut self, c: char) {
+        if c == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+    }
+
+    fn note(&mut self, message: impl Into<String>) {
+        self.repairs.push(JsonRepair {
+            line: self.line,
+            column: self.column,
+            message: message.into(),
+        });
+    }
+
+    fn error(&self, message: impl AsRef<str>) -> String {
+        format!("第{}行第{}列: {}", self.line, self.column, message.as_ref())
+    }
+
+    /// 宽松模式遇到损坏直接失败，修复模式记录后继续
+    fn fixable(&mut self, message: &str) -> Result<(), String> {
+        if self.repair {
+            self.note(message);
+            Ok(())
+        } else {
+            Err(self.error(message))
+        }
+    }
+
+    // ==================== 空白与注释 ====================
+
+    fn skip_trivia(&mut self) -> Result<(), String> {
+        loop {
+            match self.peek() {
+                Some(c) if c.is_whitespace() || c == '\u{FEFF}' => {
+                    self.bump();
+                }
+                Some('/') if self.peek_second() == Some('/') => {
+                    while let Some(c) = self.bump() {
+                        if c == '\n' {
+                            break;
+                        }
+                    }
+                }
+                Some('/') if self.peek_second() == Some('*') => {
+                    self.bump();
+                    self.bump();
+                    let mut closed = false;
+                    while let Some(c) = self.bump() {
+                        if c == '*' && self.peek() == Some('/') {
+                            self.bump();
+                            closed = true;
+                            break;
+                        }
+                    }
+                    if !closed {
+                        self.fixable("删除未闭合的块注释")?;
+                    }
+                }
+                Some('#') if self.repair => {
+                    self.note("删除 # 注释");
+                    while let Some(c) = self.bump() {
+                        if c == '\n' {
+                            break;
+                        }
+                    }
+                }
+                _ => return Ok(()),
+            }
+        }
+    }
+
+    // ==================== 值 ====================
+
+    fn value(&mut self) -> Result<(), String> {
+        match self.peek() {
+            None => {
+                self.fixable("补全缺失的值为 null")?;
+                self.out.push_str("null");
+                Ok(())
+            }
+            Some('{') => self.object(),
+            Some('[') => self.array(),
+            Some(q @ ('"' | '\'')) => self.string(q),
+            Some(c) if c.is_ascii_digit() || matches!(c, '-' | '+' | '.') => self.number(),
+            Some(c) if is_ident_char(c) => self.word(),
+            Some(c) => Err(self.error(format!("意外的字符 '{}'", c))),
+        }
+    }
+
+    fn enter(&mut self) -> Result<(), String> {
+        self.depth += 1;
+        if self.depth > MAX_DEPTH {
+            return Err(self.error("嵌套层级过深"));
+        }
+        Ok(())
+    }
+
+    fn object(&mut self) -> Result<(), String> {
+        self.enter()?;
+        self.bump();
+        self.out.push('{');
+        let mut members = 0usize;
+        let mut expect_comma = false;
+        let mut trailing_comma: Option<(usize, usize)> = None;
+        loop {
+            self.skip_trivia()?;
+            match self.peek() {
+                None => {
+                    self.fixable("补全缺失的 '}'")?;
+                    break;
+                }
+                Some('}') => {
+                    self.bump();
+                    break;
+                }
+                Some(']') => {
+                    // `{"a": 1]` —— 这个闭合符属于外层数组
+                    self.fixable("补全缺失的 '}'")?;
+                    break;
+                }
+                Some(',') => {
+                    if expect_comma {
+                        trailing_comma = Some((self.line, self.column));
+                        expect_comma = false;
+                    } else {
+                        self.fixable("删除多余的逗号")?;
+                    }
+                    self.bump();
+                    continue;
+                }
+                Some(_) if expect_comma => {
+                    self.fixable("补全缺失的逗号")?;
+                }
+                Some(_) => {}
+            }
+
+            trailing_comma = None;
+            if members > 0 {
+                self.out.push(',');
+            }
+            self.key()?;
+            self.skip_trivia()?;
+            self.out.push(':');
+            match self.peek() {
+                Some(':') => {
+                    self.bump();
+                    self.member_value()?;
+                }
+                Some('=') if self.repair => {
+                    self.note("将 '=' 改为 ':'");
+                    self.bump();
+                    self.member_value()?;
+                }
+                None | Some('}') | Some(']') | Some(',') => {
+                    self.fixable("补全缺失的值为 null")?;
+                    self.out.push_str("null");
+                }
+                Some(_) => {
+                    self.fixable("补全缺失的冒号")?;
+                    self.value()?;
+                }
+            }
+            members += 1;
+            expect_comma = true;
+        }
+        if let Some((line, column)) = trailing_comma {
+            self.repairs.push(JsonRepair {
+                line,
+                column,
+                message: "删除尾随逗号".to_string(),
+            });
+        }
+        self.out.push('}');
+        self.depth -= 1;
+        Ok(())
+    }
+
+    /// 键后面的值。`{"a": }`、`{"a": ]` 和截断的 `{"a":` 都补全为 null。
+    fn member_value(&mut self) -> Result<(), String> {
+        self.skip_trivia()?;
+        if matches!(self.peek(), None | Some('}') | Some(']') | Some(',')) {
+            self.fixable("补全缺失的值为 null")?;
+            self.out.push_str("null");
+            Ok(())
+        } else {
+            self.value()
+        }
+    }
+
+    fn array(&mut self) -> Result<(), String> {
+        self.enter()?;
+        self.bump();
+        self.out.push('[');
+        let mut items = 0usize;
+        let mut expect_comma = false;
+        let mut trailing_comma: Option<(usize, usize)> = None;
+        loop {
+            self.skip_trivia()?;
+            match self.peek() {
+                None => {
+                    self.fixable("补全缺失的 ']'")?;
+                    break;
+                }
+                Some(']') => {
+                    self.bump();
+                    break;
+                }
+                Some('}') => {
+                    self.fixable("补全缺失的 ']'")?;
+                    break;
+                }
+                Some(',') => {
+                    if expect_comma {
+                        trailing_comma = Some((self.line, self.column));
+                        expect_comma = false;
+                    } else {
+                        self.fixable("删除多余的逗号")?;
+                    }
+                    self.bump();
+                    continue;
+                }
+                Some(_) if expect_comma => {
+                    self.fixable("补全缺失的逗号")?;
+                }
+                Some(_) => {}
+            }
+
+            trailing_comma = None;
+            if items > 0 {
+                self.out.push(',');
+            }
+            self.value()?;
+            items += 1;
+            expect_comma = true;
+        }
+        if let Some((line, column)) = trailing_comma {
+            self.repairs.push(JsonRepair {
+                line,
+                column,
+                message: "删除尾随逗号".to_string(),
+            });
+        }
+        self.out.push(']');
+        self.depth -= 1;
+        Ok(())
+    }
+
+    fn key(&mut self) -> Result<(), String> {
+        match self.peek() {
+            Some(q @ ('"' | '\'')) => self.string(q),
+            Some(c) if is_ident_char(c) => {
+                let start = self.pos;
+                while self.peek().is_some_and(is_ident_char) {
+                    self.bump();
+                }
+                let ident = &self.src[start..self.pos];
+                self.note(format!("为键 {} 补全引号", ident));
+                push_json_string(&mut self.out, ident);
+                Ok(())
+            }
+            Some(c) => Err(self.error(format!("期望对象的键，实际为 '{}'", c))),
+            None => Err(self.error("期望对象的键")),
+        }
+    }
+
+    fn string(&mut self, quote: char) -> Result<(), String> {
+        if quote == '\'' {
+            self.note("将单引号字符串改为双引号");
+        }
+        self.bump();
+        self.out.push('"');
+        loop {
+            let Some(c) = self.bump() else {
+                self.fixable("补全未闭合的字符串")?;
+                break;
+            };
+            match c {
+                c if c == quote => break,
+                '"' => self.out.push_str("\\\""),
+                '\\' => self.escape()?,
+                '\n' | '\r' | '\t' => {
+                    self.note("转义字符串中的换行或制表符");
+                    self.out.push_str(match c {
+                        '\n' => "\\n",
+                        '\r' => "\\r",
+                        _ => "\\t",
+                    });
+                }
+                c if (c as u32) < 0x20 => {
+                    self.note("转义字符串中的控制字符");
+                    self.out.push_str(&format!("\\u{:04x}", c as u32));
+                }
+                c => self.out.push(c),
+            }
+        }
+        self.out.push('"');
+        Ok(())
+    }
+
+    /// 转换一个转义序列（反斜杠已被消费）
+    fn escape(&mut self) -> Result<(), String> {
+        let Some(c) = self.bump() else {
+            return self.fixable("补全未闭合的字符串");
+        };
+        match c {
+            '"' | '\\' | '/' | 'b' | 'f' | 'n' | 'r' | 't' => {
+                self.out.push('\\');
+                self.out.push(c);
+            }
+            '\'' => self.out.push('\''),
+            'u' => {
+                let digits: String = self.src[self.pos..].chars().take(4).collect();
+                if digits.len() == 4 && digits.chars().all(|d| d.is_ascii_hexdigit()) {
+                    for _ in 0..4 {
+                        self.bump();
+                    }
+                    self.out.push_str("\\u");
+                    self.out.push_str(&digits);
+                } else {
+                    self.fixable("转义无效的 \\u 序列")?;
+                    self.out.push_str("\\\\u");
+                }
+            }
+            'x' => {
+                let digits: String = self.src[self.pos..].chars().take(2).collect();
+                if digits.len() == 2 && digits.chars().all(|d| d.is_ascii_hexdigit()) {
+                    self.bump();
+                    self.bump();
+                    self.out.push_str("\\u00");
+                    self.out.push_str(&digits);
+                } else {
+                    self.fixable("转义无效的 \\x 序列")?;
+                    self.out.push_str("\\\\x");
+                }
+            }
+            '0' if !self.peek().is_some_and(|d| d.is_ascii_digit()) => self.out.push_str("\\u0000"),
+            'v' => self.out.push_str("\\u000b"),
+            // JSON5 续行: 反斜杠加换行符整体消失
+            '\n' | '\u{2028}' | '\u{2029}' => {}
+            '\r' => {
+                if self.peek() == Some('\n') {
+                    self.bump();
+                }
+            }
+            c if self.repair => {
+                // `"C:\dir"`: 保留反斜杠而不是丢弃
+                self.note(format!("转义无效的反斜杠 '\\{}'", c));
+                self.out.push_str("\\\\");
+                push_json_string_char(&mut self.out, c);
+            }
+            // JSON5 恒等转义: `\q` 就是 `q`
+            c => push_json_string_char(&mut self.out, c),
+        }
+        Ok(())
+    }
+
+    fn number(&mut self) -> Result<(), String> {
+        let mut sign = "";
+        match self.peek() {
+            Some('+') => {
+                self.note("删除数字前的 '+'");
+                self.bump();
+            }
+            Some('-') => {
+                sign = "-";
+                self.bump();
+            }
+            _ => {}
+        }
+        let body_start = self.pos;
+        let mut prev = '\0';
+        while let Some(c) = self.peek() {
+            let exponent_sign = matches!(c, '+' | '-')
+                && matches!(prev, 'e' | 'E')
+                && !is_hex_prefixed(&self.src[body_start..self.pos]);
+            if c.is_ascii_alphanumeric() || c == '.' || exponent_sign {
+                prev = c;
+                self.bump();
+            } else {
+                break;
+            }
+        }
+        let body = &self.src[body_start..self.pos];
+
+        match body {
+            "Infinity" | "NaN" => {
+                if !self.repair {
+                    return Err(self.error(format!("JSON 无法表示 {}{}", sign, body)));
+                }
+                self.note(format!("将 {}{} 替换为 null", sign, body));
+                self.out.push_str("null");
+                return Ok(());
+            }
+            _ if !body.chars().any(|c| c.is_ascii_digit()) => {
+                return Err(self.error(format!("无效的数字 '{}{}'", sign, body)));
+            }
+            _ => {}
+        }
+
+        if is_hex_prefixed(body) {
+            let digits = &body[2..];
+            let value = BigUint::parse_bytes(digits.as_bytes(), 16)
+                .ok_or_else(|| self.error(format!("无效的十六进制数字 '{}'", body)))?;
+            self.note(format!("将十六进制数字 {} 转换为十进制", body));
+            self.out.push_str(sign);
+            self.out.push_str(&value.to_string());
+            return Ok(());
+        }
+
+        let mut literal = body.to_string();
+        if literal.starts_with('.') {
+            literal.insert(0, '0');
+            self.note("为小数补全整数部分");
+        }
+        if let Some(dot) = literal.find('.') {
+            let after = literal[dot + 1..].chars().next();
+            if !after.is_some_and(|c| c.is_ascii_digit()) {
+                literal.remove(dot);
+                self.note("删除数字末尾的小数点");
+            }
+        }
+        let int_len = literal.find(['.', 'e', 'E']).unwrap_or(literal.len());
+        if int_len > 1 && literal.starts_with('0') {
+            let zeros = literal[..int_len - 1].chars().take_while(|&c| c == '0').count();
+            literal.replace_range(..zeros, "");
+            self.note("删除数字的前导零");
+        }
+        if !is_json_number(&literal) {
+            return Err(self.error(format!("无效的数字 '{}{}'", sign, body)));
+        }
+        self.out.push_str(sign);
+        self.out.push_str(&literal);
+        Ok(())
+    }
+
+    fn word(&mut self) -> Result<(), String> {
+        let start = self.pos;
+        while self.peek().is_some_and(is_ident_char) {
+            self.bump();
+        }
+        let word = &self.src[start..self.pos];
+        match word {
+            "true" | "false" | "null" => self.out.push_str(word),
+            "Infinity" | "NaN" => {
+                if !self.repair {
+                    return Err(self.error(format!("JSON 无法表示 {}", word)));
+                }
+                self.note(format!("将 {} 替换为 null", word));
+                self.out.push_str("null");
+            }
+            "True" | "False" | "None" | "undefined" | "TRUE" | "FALSE" | "NULL" | "nil" if self.repair => {
+                let replacement = match word {
+                    "True" | "TRUE" => "true",
+                    "False" | "FALSE" => "false",
+                    _ => "null",
+                };
+                self.note(format!("将 {} 替换为 {}", word, replacement));
+                self.out.push_str(replacement);
+            }
+            _ if self.repair => {
+                self.note(format!("为未加引号的字符串 {} 补全引号", word));
+                push_json_string(&mut self.out, word);
+            }
+            _ => return Err(self.error(format!("无法识别的标识符 '{}'", word))),
+        }
+        Ok(())
+    }
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '$'
+}
+
+fn is_hex_prefixed(s: &str) -> bool {
+    s.starts_with("0x") || s.starts_with("0X")
+}
+
+/// 按 JSON 语法校验数字的无符号部分
+pub fn is_json_number(s: &str) -> bool {
+    let b = s.as_bytes();
+    let mut i = 0;
+    let digits = |i: &mut usize| {
+        let start = *i;
+        while *i < b.len() && b[*i].is_ascii_digit() {
+            *i += 1;
+        }
+        *i > start
+    };
+    if !digits(&mut i) {
+        return false;
+    }
+    if b.len() > 1 && b[0] == b'0' && b[1].is_ascii_digit() {
+        return false;
+    }
+    if i < b.len() && b[i] == b'.' {
+        i += 1;
+        if !digits(&mut i) {
+            return false;
+        }
+    }
+    if i < b.len() && (b[i] == b'e' || b[i] == b'E') {
+        i += 1;
+        if i < b.len() && (b[i] == b'+' || b[i] == b'-') {
+            i += 1;
+        }
+        if !digits(&mut i) {
+            return false;
+        }
+    }
+    i == b.len()
+}
+
+fn push_json_string(out: &mut String, s: &str) {
+    out.push('"');
+    for c in s.chars() {
+        push_json_string_char(out, c);
+    }
+    out.push('"');
+}
+
+fn push_json_string_char(out: &mut String, c: char) {
+    match c {
+        '"' => out.push_str("\\\""),
+        '\\' => out.push_str("\\\\"),
+        '\n' => out.push_str("\\n"),
+        '\r' => out.push_str("\\r"),
+        '\t' => out.push_str("\\t"),
+        c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+        c => out.push(c),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    /// 修复 `input`，返回解析后的值和修改说明
+    fn fixed(input: &str) -> (Value, Vec<String>) {
+        let (text, repairs) = repair(input).unwrap();
+        let value = serde_json::from_str(&text).unwrap();
+        (value, repairs.into_iter().map(|r| r.message).collect())
+    }
+
+    #[test]
+    fn lenient_accepts_json5() {
+        let input = "{\n  // 行注释\n  /* 块注释 */ name: 'a\"b', list: [1, 2,], hex: 0x1F, dot: .5, plus: +1,\n}";
+        let value = parse_value(input, Some(JsonParseMode::Lenient)).unwrap();
+        assert_eq!(value, json!({"name": "a\"b", "list": [1, 2], "hex": 31, "dot": 0.5, "plus": 1}));
+        assert!(parse_value(input, None).is_err());
+    }
+
+    #[test]
+    fn lenient_rejects_damage() {
+        for input in ["{\"a\": True}", "[1 2]", "{\"a\": 1", "{\"a\": ]"] {
+            assert!(parse_value(input, Some(JsonParseMode::Lenient)).is_err(), "{}", input);
+        }
+    }
+
+    #[test]
+    fn repairs_comments() {
+        let (value, repairs) = fixed("{\"a\": 1, # 注释\n\"b\": 2 /* 未闭合");
+        assert_eq!(value, json!({"a": 1, "b": 2}));
+        assert!(repairs.contains(&"删除 # 注释".to_string()));
+        assert!(repairs.contains(&"删除未闭合的块注释".to_string()));
+    }
+
+    #[test]
+    fn repairs_quotes_and_keys() {
+        let (value, repairs) = fixed("{name: 'x', \"y\": 'it\\'s'}");
+        assert_eq!(value, json!({"name": "x", "y": "it's"}));
+        assert!(repairs.contains(&"为键 name 补全引号".to_string()));
+        assert!(repairs.contains(&"将单引号字符串改为双引号".to_string()));
+    }
+
+    #[test]
+    fn repairs_trailing_and_missing_commas() {
+        let (value, repairs) = fixed("{\"a\": [1 2,], \"b\": 3,}");
+        assert_eq!(value, json!({"a": [1, 2], "b": 3}));
+        assert_eq!(repairs.iter().filter(|m| *m == "删除尾随逗号").count(), 2);
+        assert!(repairs.contains(&"补全缺失的逗号".to_string()));
+    }
+
+    #[test]
+    fn repairs_python_literals() {
+        let (value, repairs) = fixed("{'ok': True, 'no': False, 'none': None}");
+        assert_eq!(value, json!({"ok": true, "no": false, "none": null}));
+        assert!(repairs.contains(&"将 True 替换为 true".to_string()));
+        assert!(repairs.contains(&"将 None 替换为 null".to_string()));
+    }
+
+    #[test]
+    fn repairs_truncation() {
+        let (value, repairs) = fixed("{\"a\": [1, {\"b\": \"tex");
+        assert_eq!(value, json!({"a": [1, {"b": "tex"}]}));
+        assert!(repairs.contains(&"补全未闭合的字符串".to_string()));
+        assert!(repairs.contains(&"补全缺失的 ']'".to_string()));
+        assert!(repairs.contains(&"补全缺失的 '}'".to_string()));
+
+        let (value, repairs) = fixed("{\"a\":");
+        assert_eq!(value, json!({"a": null}));
+        assert!(repairs.contains(&"补全缺失的值为 null".to_string()));
+    }
+
+    #[test]
+    fn repairs_missing_value_before_bracket() {
+        for input in ["[{\"a\": ]", "[{\"a\":]", "[{\"a\" ]"] {
+            let (value, repairs) = fixed(input);
+            assert_eq!(value, json!([{"a": null}]), "{}", input);
+            assert!(repairs.contains(&"补全缺失的值为 null".to_string()), "{}", input);
+        }
+        let (value, repairs) = fixed("{\"a\": ]");
+        assert_eq!(value, json!({"a": null}));
+        assert!(repairs.contains(&"补全缺失的值为 null".to_string()));
+    }
+
+    #[test]
+    fn repairs_log_prefix() {
+        let (value, repairs) = fixed("2024-05-01 INFO payload={\"id\": 7}");
+        assert_eq!(value, json!({"id": 7}));
+        assert_eq!(repairs[0], "跳过开头 24 个非 JSON 字符");
+    }
+
+    #[test]
+    fn reports_original_position() {
+        let (_, repairs) = repair("{\n  \"a\": True\n}").unwrap();
+        assert_eq!(repairs[0].line, 2);
+        assert_eq!(repairs[0].message, "将 True 替换为 true");
+    }
+}
@@ -0,0 +1,388 @@
+// `get_json_info` / `get_json_info_file` 背后的单遍 JSON 结构统计
+//
+// 只遍历一次 `json_stream` 事件流，按*形状路径*聚合统计，数组下标合并为
+// `[*]`（`$.users[*].name`），一万条记录的列表每个字段只产生一行，而不是一万行。
+// 子树体积取自开闭记号的字节偏移，度量的是文档的书写形式（宽松输入则是规范化
+// 后的紧凑形式）。重复键在这里检出，因为 serde_json 会静默保留最后一个。
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::io::BufRead;
+
+use serde::Serialize;
+
+use super::json_stream::{Event, EventReader};
+
+/// 记录的不同形状路径上限，超出后丢弃新路径，避免类 map 对象
+/// （`{"user_1": .., "user_2": ..}`）导致结果膨胀
+const MAX_PATHS: usize = 1000;
+const MAX_SUBTREES: usize = 10;
+const MAX_DUPLICATES: usize = 100;
+
+const LENGTH_BUCKETS: [(usize, &str); 7] = [
+    (0, "0"),
+    (8, "1-8"),
+    (32, "9-32"),
+    (128, "33-128"),
+    (512, "129-512"),
+    (2048, "513-2048"),
+    (usize::MAX, "2049+"),
+];
+
+#[derive(Serialize)]
+pub struct JsonProfile {
+    pub depth: usize,
+    pub key_count: usize,
+    pub value_types: Vec<String>,
+    /// 各形状路径的统计，按首次出现的顺序
+    pub paths: Vec<JsonPathStats>,
+    /// 不同路径数超过 `MAX_PATHS` 时为 `true`
+    pub paths_truncated: bool,
+    /// 根以下体积最大的值，按序列化后的字节数
+    pub largest_subtrees: Vec<JsonSubtree>,
+    pub duplicate_keys: Vec<JsonDuplicateKey>,
+}
+
+#[derive(Serialize)]
+pub struct JsonPathStats {
+    pub path: String,
+    pub count: usize,
+    /// 类型名 -> 在此路径上出现的次数
+    pub types: BTreeMap<String, usize>,
+    pub null_ratio: f64,
+    pub number: Option<NumberStats>,
+    pub string_length: Option<LengthStats>,
+    pub array_length: Option<LengthStats>,
+}
+
+#[derive(Serialize)]
+pub struct NumberStats {
+    pub min: f64,
+    pub max: f64,
+    pub avg: f64,
+}
+
+#[derive(Serialize)]
+pub struct LengthStats {
+    pub min: usize,
+    pub max: usize,
+    pub avg: f64,
+    pub buckets: Vec<LengthBucket>,
+}
+
+#[derive(Serialize)]
+pub struct LengthBucket {
+    pub range: &'static str,
+    pub count: usize,
+}
+
+#[derive(Serialize, Clone)]
+pub struct JsonSubtree {
+    /// 具体路径，保留真实的数组下标
+    pub path: String,
+    pub value_type: String,
+    pub bytes: u64,
+}
+
+#[derive(Serialize)]
+pub struct JsonDuplicateKey {
+    /// 包含重复键的对象的具体路径
+    pub path: String,
+    pub key: String,
+    pub line: usize,
+    pub column: usize,
+}
+
+/// 统计从 `reader` 读入的严格 JSON
+pub fn profile<R: BufRead>(reader: R) -> Result<JsonProfile, String> {
+    let mut events = EventReader::new(reader);
+    let mut profiler = Profiler::default();
+    while let Some(event) = events.next_event()? {
+        profiler.feed(event, &events);
+    }
+    Ok(profiler.finish())
+}
+
+// ==================== 累加器 ====================
+
+#[derive(Default)]
+struct LengthAcc {
+    min: usize,
+    max: usize,
+    sum: u64,
+    n: usize,
+    buckets: [usize; LENGTH_BUCKETS.len()],
+}
+
+impl LengthAcc {
+    fn add(&mut self, len: usize) {
+        if self.n == 0 || len < self.min {
+            self.min = len;
+        }
+        self.max = self.max.max(len);
+        self.sum += len as u64;
+        self.n += 1;
+        let bucket = LENGTH_BUCKETS.iter().position(|(upper, _)| len <= *upper).unwrap_or(0);
+        self.buckets[bucket] += 1;
+    }
+
+    fn stats(&self) -> Option<LengthStats> {
+        (self.n > 0).then(|| LengthStats {
+            min: self.min,
+            max: self.max,
+            avg: self.sum as f64 / self.n as f64,
+            buckets: LENGTH_BUCKETS
+                .iter()
+                .zip(self.buckets)
+                .map(|((_, range), count)| LengthBucket { range, count })
+                .collect(),
+        })
+    }
+}
+
+#[derive(Default)]
+struct PathAcc {
+    path: String,
+    count: usize,
+    types: BTreeMap<&'static str, usize>,
+    number_min: f64,
+    number_max: f64,
+    number_sum: f64,
+    numbers: usize,
+    strings: LengthAcc,
+    arrays: LengthAcc,
+}
+
+impl PathAcc {
+    fn add_number(&mut self, n: f64) {
+        if self.numbers == 0 {
+            self.number_min = n;
+            self.number_max = n;
+        } else {
+            self.number_min = self.number_min.min(n);
+            self.number_max = self.number_max.max(n);
+        }
+        self.number_sum += n;
+        self.numbers += 1;
+    }
+
+    fn stats(self) -> JsonPathStats {
+        let nulls = self.types.get("null").copied().unwrap_or(0);
+        JsonPathStats {
+            null_ratio: nulls as f64 / self.count.max(1) as f64,
+            number: (self.numbers > 0).then(|| NumberStats {
+                min: self.number_min,
+                max: self.number_max,
+                avg: self.number_sum / self.numbers as f64,
+            }),
+            string_length: self.strings.stats(),
+            array_length: self.arrays.stats(),
+            types: self.types.into_iter().map(|(k, v)| (k.to_string(), v)).collect(),
+            count: self.count,
+            path: self.path,
+        }
+    }
+}
+
+/// 一个尚未闭合的对象或数组
+struct Frame {
+    is_object: bool,
+    /// 此容器的形状路径
+    path: String,
+    /// 此容器相对父容器的具体路径片段
+    segment: String,
+    start: u64,
+    children: usize,
+    pending_key: Option<String>,
+    keys: HashSet<String>,
+}
+
+#[derive(Default)]
+struct Profiler {
+    stack: Vec<Frame>,
+    depth: usize,
+    key_count: usize,
+    value_types: HashSet<&'static str>,
+    paths: Vec<PathAcc>,
+    path_index: HashMap<String, usize>,
+    paths_truncated: bool,
+    largest: Vec<JsonSubtree>,
+    duplicates: Vec<JsonDuplicateKey>,
+}
+
+impl Profiler {
+    fn feed<R: BufRead>(&mut self, event: Event, events: &EventReader<R>) {
+        match event {
+            Event::Key(raw) => {
+                self.key_count += 1;
+                let key = decode_string(&raw);
+                let frame = self.stack.last_mut().expect("keys only appear inside objects");
+                let duplicate = !frame.keys.insert(key.clone());
+                if duplicate && self.duplicates.len() < MAX_DUPLICATES {
+                    let (line, column) = events.event_position();
+                    self.duplicates.push(JsonDuplicateKey {
+                        path: self.concrete_path(None),
+                        key: key.clone(),
+                        line,
+                        column,
+                    });
+                }
+                self.stack.last_mut().expect("keys only appear inside objects").pending_key = Some(key);
+            }
+            Event::BeginObject | Event::BeginArray => {
+                let is_object = event == Event::BeginObject;
+                let (path, segment) = self.child_path();
+                self.record(&path, if is_object { "object" } else { "array" }, |_| {});
+                self.stack.push(Frame {
+                    is_object,
+                    path,
+                    segment,
+                    start: events.event_offset(),
+                    children: 0,
+                    pending_key: None,
+                    keys: HashSet::new(),
+                });
+            }
+            Event::EndObject | Event::EndArray => {
+                let frame = self.stack.pop().expect("event reader balances containers");
+                if !frame.is_object {
+                    let len = frame.children;
+                    self.record_existing(&frame.path, |acc| acc.arrays.add(len));
+                }
+                if !self.stack.is_empty() {
+                    let bytes = events.offset() - frame.start;
+                    let value_type = if frame.is_object { "object" } else { "array" };
+                    self.consider_subtree(bytes, value_type, Some(&frame.segment));
+                }
+            }
+            Event::Scalar(raw) => {
+                let (path, segment) = self.child_path();
+                let value_type = match raw.as_bytes()[0] {
+                    b'"' => "string",
+                    b't' | b'f' => "boolean",
+                    b'n' => "null",
+                    _ => "number",
+                };
+                self.record(&path, value_type, |acc| match value_type {
+                    "string" => acc.strings.add(decode_string(&raw).chars().count()),
+                    "number" => {
+                        if let Ok(n) = raw.parse::<f64>() {
+                            acc.add_number(n);
+                        }
+                    }
+                    _ => {}
+                });
+                if !self.stack.is_empty() {
+                    let bytes = events.offset() - events.event_offset();
+                    self.consider_subtree(bytes, value_type, Some(&segment));
+                }
+            }
+        }
+    }
+
+    /// 即将开始的值的形状路径与具体路径片段
+    fn child_path(&mut self) -> (String, String) {
+        self.depth = self.depth.max(self.stack.len());
+        let Some(parent) = self.stack.last_mut() else {
+            return ("$".to_string(), String::new());
+        };
+        parent.children += 1;
+        if parent.is_object {
+            let key = parent.pending_key.take().unwrap_or_default();
+            let segment = key_segment(&key);
+            (format!("{}{}", parent.path, segment), segment)
+        } else {
+            let segment = format!("[{}]", parent.children - 1);
+            (format!("{}[*]", parent.path), segment)
+        }
+    }
+
+    fn record(&mut self, path: &str, value_type: &'static str, f: impl FnOnce(&mut PathAcc)) {
+        self.value_types.insert(value_type);
+        let index = match self.path_index.get(path) {
+            Some(&i) => i,
+            None if self.paths.len() < MAX_PATHS => {
+                self.path_index.insert(path.to_string(), self.paths.len());
+                self.paths.push(PathAcc {
+                    path: path.to_string(),
+                    ..PathAcc::default()
+                });
+                self.paths.len() - 1
+            }
+            None => {
+                self.paths_truncated = true;
+                return;
+            }
+        };
+        let acc = &mut self.paths[index];
+        acc.count += 1;
+        *acc.types.entry(value_type).or_insert(0) += 1;
+        f(acc);
+    }
+
+    fn record_existing(&mut self, path: &str, f: impl FnOnce(&mut PathAcc)) {
+        if let Some(&i) = self.path_index.get(path) {
+            f(&mut self.paths[i]);
+        }
+    }
+
+    /// 最内层未闭合容器的具体路径，再加上 `segment`
+    fn concrete_path(&self, segment: Option<&str>) -> String {
+        let mut path = String::from("$");
+        for frame in &self.stack {
+            path.push_str(&frame.segment);
+        }
+        if let Some(segment) = segment {
+            path.push_str(segment);
+        }
+        path
+    }
+
+    fn consider_subtree(&mut self, bytes: u64, value_type: &str, segment: Option<&str>) {
+        if self.largest.len() >= MAX_SUBTREES && self.largest.last().is_some_and(|s| s.bytes >= bytes) {
+            return;
+        }
+        let entry = JsonSubtree {
+            path: self.concrete_path(segment),
+            value_type: value_type.to_string(),
+            bytes,
+        };
+        let at = self.largest.partition_point(|s| s.bytes >= bytes);
+        self.largest.insert(at, entry);
+        self.largest.truncate(MAX_SUBTREES);
+    }
+
+    fn finish(self) -> JsonProfile {
+        JsonProfile {
+            depth: self.depth,
+            key_count: self.key_count,
+            value_types: self.value_types.into_iter().map(str::to_string).collect(),
+            paths: self.paths.into_iter().map(PathAcc::stats).collect(),
+            paths_truncated: self.paths_truncated,
+            largest_subtrees: self.largest,
+            duplicate_keys: self.duplicates,
+        }
+    }
+}
+
+/// 解码原始 JSON 字符串记号，不含转义的常见情况不经过 serde
+fn decode_string(raw: &str) -> String {
+    let inner = &raw[1..raw.len() - 1];
+    if !inner.contains('\\') {
+        return inner.to_string();
+    }
+    serde_json::from_str(raw).unwrap_or_else(|_| inner.to_string())
+}
+
+/// 类标识符的键用 `.name`，其余用 `["a b"]`
+fn key_segment(key: &str) -> String {
+    let mut chars = key.chars();
+    let identifier = chars
+        .next()
+        .is_some_and(|c| c.is_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_alphanumeric() || c == '_' || c == '$');
+    if identifier {
+        format!(".{}", key)
+    } else {
+        format!("[{}]", serde_json::to_string(key).unwrap_or_default())
+    }
+}
@@ -0,0 +1,418 @@
+// 格式化器与文件工具共用的流式 JSON 读取器
+//
+// `Lexer` 把从任意 `BufRead` 读入的严格 JSON 切分为记号，字符串和数字保留
+// 原始文本，不会因 `f64` 或键重排而丢失信息。`EventReader` 建立在它之上，
+// 用显式栈检查语法（不递归，深度只受内存限制），并产出 SAX 风格的事件。
+// 两者任何时候都最多只持有一个记号，`*_file` 命令因此能处理数百 MB 的文档。
+
+use std::io::BufRead;
+
+use super::json_parse::is_json_number;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Kind {
+    BeginObject,
+    EndObject,
+    BeginArray,
+    EndArray,
+    Colon,
+    Comma,
+    /// 字符串（原始文本，含引号与转义）、数字或字面量
+    Scalar(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct Token {
+    pub kind: Kind,
+    pub line: usize,
+    pub column: usize,
+    /// 记号首字节的字节偏移
+    pub offset: u64,
+}
+
+impl Token {
+    pub fn error(&self, message: &str) -> String {
+        format!("第{}行第{}列: {}", self.line, self.column, message)
+    }
+
+    pub fn into_key(self) -> Result<String, String> {
+        match self.kind {
+            Kind::Scalar(s) if s.starts_with('"') => Ok(s),
+            _ => Err(format!("第{}行第{}列: 期望字符串形式的键", self.line, self.column)),
+        }
+    }
+}
+
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+pub struct Lexer<R> {
+    reader: R,
+    line: usize,
+    column: usize,
+    /// 已读取的字节数
+    offset: u64,
+}
+
+impl<R: BufRead> Lexer<R> {
+    pub fn new(reader: R) -> Self {
+        Lexer {
+            reader,
+            line: 1,
+            column: 1,
+            offset: 0,
+        }
+    }
+
+    pub fn error(&self, message: &str) -> String {
+        format!("第{}行第{}列: {}", self.line, self.column, message)
+    }
+
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    fn peek_byte(&mut self) -> Result<Option<u8>, String> {
+        let buf = self
+            .reader
+            .fill_buf()
+            .map_err(|e| format!("读取输入失败: {}", e))?;
+        Ok(buf.first().copied())
+    }
+
+    fn next_byte(&mut self) -> Result<Option<u8>, String> {
+        let byte = self.peek_byte()?;
+        if let Some(b) = byte {
+            self.reader.consume(1);
+            self.offset += 1;
+            if b == b'\n' {
+                self.line += 1;
+                self.column = 1;
+            } else if b & 0xC0 != 0x80 {
+                // 按字符计数，跳过 UTF-8 后续字节
+                self.column += 1;
+            }
+        }
+        Ok(byte)
+    }
+
+    pub fn next_token(&mut self) -> Result<Option<Token>, String> {
+        if self.offset == 0 {
+            self.skip_bom()?;
+        }
+        while let Some(b' ' | b'\t' | b'\n' | b'\r') = self.peek_byte()? {
+            self.next_byte()?;
+        }
+        let (line, column, offset) = (self.line, self.column, self.offset);
+        let Some(b) = self.peek_byte()? else {
+            return Ok(None);
+        };
+        let kind = match b {
+            b'{' | b'}' | b'[' | b']' | b':' | b',' => {
+                self.next_byte()?;
+                match b {
+                    b'{' => Kind::BeginObject,
+                    b'}' => Kind::EndObject,
+                    b'[' => Kind::BeginArray,
+                    b']' => Kind::EndArray,
+                    b':' => Kind::Colon,
+                    _ => Kind::Comma,
+                }
+            }
+            b'"' => Kind::Scalar(self.string()?),
+            b'-' | b'0'..=b'9' => Kind::Scalar(self.number()?),
+            b'a'..=b'z' | b'A'..=b'Z' => Kind::Scalar(self.literal()?),
+            _ => {
+                let shown = self.peek_char()?;
+                return Err(self.error(&format!("意外的字符 '{}'", shown)));
+            }
+        };
+        Ok(Some(Token {
+            kind,
+            line,
+            column,
+            offset,
+        }))
+    }
+
+    /// 跳过开头的 UTF-8 BOM（记事本等编辑器保存的文件常带有）
+    fn skip_bom(&mut self) -> Result<(), String> {
+        let buf = self
+            .reader
+            .fill_buf()
+            .map_err(|e| format!("读取输入失败: {}", e))?;
+        if buf.starts_with(UTF8_BOM) {
+            self.reader.consume(UTF8_BOM.len());
+            self.offset += UTF8_BOM.len() as u64;
+        }
+        Ok(())
+    }
+
+    fn peek_char(&mut self) -> Result<char, String> {
+        let buf = self
+            .reader
+            .fill_buf()
+            .map_err(|e| format!("读取输入失败: {}", e))?;
+        let len = buf.len().min(4);
+        Ok(String::from_utf8_lossy(&buf[..len]).chars().next().unwrap_or('?'))
+    }
+
+    fn string(&mut self) -> Result<String, String> {
+        let mut raw = vec![b'"'];
+        self.next_byte()?;
+        loop {
+            let Some(b) = self.next_byte()? else {
+                return Err(self.error("字符串未闭合"));
+            };
+            raw.push(b);
+            match b {
+                b'"' => break,
+                b'\\' => match self.next_byte()? {
+                    Some(e @ (b'"' | b'\\' | b'/' | b'b' | b'f' | b'n' | b'r' | b't')) => raw.push(e),
+                    Some(b'u') => {
+                        raw.push(b'u');
+                        for _ in 0..4 {
+                            match self.next_byte()? {
+                                Some(h) if h.is_ascii_hexdigit() => raw.push(h),
+                                _ => return Err(self.error("无效的 \\u 转义序列")),
+                            }
+                        }
+                    }
+                    _ => return Err(self.error("无效的转义序列")),
+                },
+                0x00..=0x1F => return Err(self.error("字符串中包含未转义的控制字符")),
+                _ => {}
+            }
+        }
+        String::from_utf8(raw).map_err(|_| self.error("字符串不是有效的 UTF-8"))
+    }
+
+    fn number(&mut self) -> Result<String, String> {
+        let mut raw = String::new();
+        while let Some(b @ (b'0'..=b'9' | b'-' | b'+' | b'.' | b'e' | b'E')) = self.peek_byte()? {
+            raw.push(b as char);
+            self.next_byte()?;
+        }
+        if !is_json_number(raw.strip_prefix('-').unwrap_or(&raw)) {
+            return Err(self.error(&format!("无效的数字 '{}'", raw)));
+        }
+        Ok(raw)
+    }
+
+    fn literal(&mut self) -> Result<String, String> {
+        let mut raw = String::new();
+        while let Some(b) = self.peek_byte()?.filter(u8::is_ascii_alphabetic) {
+            raw.push(b as char);
+            self.next_byte()?;
+        }
+        match raw.as_str() {
+            "true" | "false" | "null" => Ok(raw),
+            _ => Err(self.error(&format!("无法识别的标识符 '{}'", raw))),
+        }
+    }
+}
+
+
+// ==================== 事件 ====================
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Event {
+    BeginObject,
+    EndObject,
+    BeginArray,
+    EndArray,
+    /// 对象的键，原始文本（引号与转义保持原样）
+    Key(String),
+    /// 字符串、数字或字面量，原始文本
+    Scalar(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Expect {
+    Value,
+    FirstValueOrEnd,
+    FirstKeyOrEnd,
+    Key,
+    Colon,
+    CommaOrEnd,
+    Eof,
+}
+
+pub struct EventReader<R> {
+    lexer: Lexer<R>,
+    /// 对象为 `true`，数组为 `false`
+    stack: Vec<bool>,
+    expect: Expect,
+    /// 最近一个事件对应记号的行、列与字节偏移
+    last: (usize, usize, u64),
+}
+
+impl<R: BufRead> EventReader<R> {
+    pub fn new(reader: R) -> Self {
+        EventReader {
+            lexer: Lexer::new(reader),
+            stack: Vec::new(),
+            expect: Expect::Value,
+            last: (1, 1, 0),
+        }
+    }
+
+    /// 最近一个事件的记号起始的行和列
+    pub fn event_position(&self) -> (usize, usize) {
+        (self.last.0, self.last.1)
+    }
+
+    /// 最近一个事件的记号起始的字节偏移
+    pub fn event_offset(&self) -> u64 {
+        self.last.2
+    }
+
+    /// 已读取的字节数，即刚好越过最近一个事件的偏移
+    pub fn offset(&self) -> u64 {
+        self.lexer.offset()
+    }
+
+    /// 包含下一个事件的容器层数
+    pub fn depth(&self) -> usize {
+        self.stack.len()
+    }
+
+    /// 下一个事件；唯一的顶层值结束后返回 `None`
+    pub fn next_event(&mut self) -> Result<Option<Event>, String> {
+        loop {
+            let Some(token) = self.lexer.next_token()? else {
+                return match self.expect {
+                    Expect::Eof => Ok(None),
+                    _ => Err(self.lexer.error("意外的输入结束")),
+                };
+            };
+            let expect = self.expect;
+            let start = (token.line, token.column, token.offset);
+            let event = match (expect, token.kind) {
+                (Expect::Eof, _) => return Err(self.lexer.error("JSON 之后存在多余的内容")),
+                (Expect::Value | Expect::FirstValueOrEnd, Kind::BeginObject) => {
+                    self.stack.push(true);
+                    self.expect = Expect::FirstKeyOrEnd;
+                    Event::BeginObject
+                }
+                (Expect::Value | Expect::FirstValueOrEnd, Kind::BeginArray) => {
+                    self.stack.push(false);
+                    self.expect = Expect::FirstValueOrEnd;
+                    Event::BeginArray
+                }
+                (Expect::Value | Expect::FirstValueOrEnd, Kind::Scalar(s)) => {
+                    self.after_value();
+                    Event::Scalar(s)
+                }
+                (Expect::FirstKeyOrEnd | Expect::Key, Kind::Scalar(s)) if s.starts_with('"') => {
+                    self.expect = Expect::Colon;
+                    Event::Key(s)
+                }
+                (Expect::Colon, Kind::Colon) => {
+                    self.expect = Expect::Value;
+                    continue;
+                }
+                (Expect::CommaOrEnd, Kind::Comma) => {
+                    self.expect = if self.stack.last() == Some(&true) {
+                        Expect::Key
+                    } else {
+                        Expect::Value
+                    };
+                    continue;
+                }
+                (Expect::FirstKeyOrEnd | Expect::CommaOrEnd, Kind::EndObject) if self.stack.last() == Some(&true) => {
+                    self.stack.pop();
+                    self.after_value();
+                    Event::EndObject
+                }
+                (Expect::FirstValueOrEnd | Expect::CommaOrEnd, Kind::EndArray) if self.stack.last() == Some(&false) => {
+                    self.stack.pop();
+                    self.after_value();
+                    Event::EndArray
+                }
+                (expect, _) => {
+                    let wanted = match expect {
+                        Expect::Value => "期望一个值",
+                        Expect::FirstValueOrEnd => "期望一个值或 ']'",
+                        Expect::FirstKeyOrEnd => "期望字符串形式的键或 '}'",
+                        Expect::Key => "期望字符串形式的键",
+                        Expect::Colon => "期望 ':'",
+                        _ if self.stack.last() == Some(&true) => "期望 ',' 或 '}'",
+                        _ => "期望 ',' 或 ']'",
+                    };
+                    return Err(format!("第{}行第{}列: {}", token.line, token.column, wanted));
+                }
+            };
+            self.last = start;
+            return Ok(Some(event));
+        }
+    }
+
+    fn after_value(&mut self) {
+        self.expect = if self.stack.is_empty() {
+            Expect::Eof
+        } else {
+            Expect::CommaOrEnd
+        };
+    }
+
+    /// 跳过首个事件为 `first` 的值的剩余部分，返回其直接子元素个数（标量为 0）
+    pub fn skip_value(&mut self, first: &Event) -> Result<usize, String> {
+        if !matches!(first, Event::BeginObject | Event::BeginArray) {
+            return Ok(0);
+        }
+        let base = self.depth();
+        let mut children = 0;
+        loop {
+            let at_top = self.depth() == base;
+            let event = self
+                .next_event()?
+                .ok_or_else(|| self.lexer.error("意外的输入结束"))?;
+            match event {
+                Event::EndObject | Event::EndArray if at_top => return Ok(children),
+                Event::Key(_) if at_top => children += 1,
+                Event::BeginObject | Event::BeginArray | Event::Scalar(_)
+                    if at_top && matches!(first, Event::BeginArray) =>
+                {
+                    children += 1
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn events(input: &str) -> Result<Vec<Event>, String> {
+        let mut reader = EventReader::new(input.as_bytes());
+        let mut events = Vec::new();
+        while let Some(event) = reader.next_event()? {
+            events.push(event);
+        }
+        Ok(events)
+    }
+
+    #[test]
+    fn skips_leading_bom() {
+        let expected = vec![
+            Event::BeginObject,
+            Event::Key("\"a\"".to_string()),
+            Event::Scalar("1".to_string()),
+            Event::EndObject,
+        ];
+        assert_eq!(events("\u{FEFF}{\"a\":1}").unwrap(), expected);
+        assert_eq!(events("{\"a\":1}").unwrap(), expected);
+
+        let mut lexer = Lexer::new("\u{FEFF} [1]".as_bytes());
+        let token = lexer.next_token().unwrap().unwrap();
+        assert_eq!((token.line, token.column, token.offset), (1, 2, 4));
+    }
+
+    #[test]
+    fn bom_only_allowed_at_start() {
+        assert!(events("{\"a\":\u{FEFF}1}").is_err());
+        assert_eq!(events("\u{FEFF}"), events(""));
+    }
+}
@@ -0,0 +1,228 @@
+// `magic_decode` 背后的多层编码识别
+//
+// 每一轮检查当前字节，按下列顺序尝试解码器，保留第一个看起来有意义的结果:
+// 可读文本，或以已知压缩魔数开头的字节。Base64 和十六进制几乎能接受任何短
+// 单词，所以它们的输出必须通过这项检查才算数。什么检查都通不过的二进制输出，
+// 仅在输入较长且明显经过编码时才接受，并且到此结束。
+//
+//   gzip / zlib   魔数字节，解压时限制大小
+//   jwt           header.payload.signature，header 是含 "alg" 的 JSON
+//   url           %XX 转义
+//   unicode       \uXXXX / \u{X} 转义
+//   html          &name; / &#NN; / &#xNN; 实体
+//   punycode      xn-- 标签
+//   hex           仅含十六进制数字（允许分隔符），长度为偶数
+//   base64        标准或 URL 安全字母表，填充可选
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::Serialize;
+
+use super::byte_io::{self, ByteInput};
+use super::compression::{self, is_zlib_header, CompressionAlgorithm};
+use super::encoding;
+use super::html_codec;
+
+/// 解压后超过此大小的输出会被拒绝
+const MAX_INFLATED: u64 = 64 * 1024 * 1024;
+const PREVIEW_BYTES: usize = 4096;
+/// 没有已知魔数的二进制结果，仅当输入至少有这么长时才接受
+const MIN_BINARY_INPUT: usize = 16;
+
+type Decoder = fn(&str) -> Option<Vec<u8>>;
+
+static URL_ESCAPE: Lazy<Regex> = Lazy::new(|| Regex::new(r"%[0-9A-Fa-f]{2}").unwrap());
+static UNICODE_ESCAPE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\\u(\{[0-9A-Fa-f]{1,6}\}|[0-9A-Fa-f]{4})").unwrap());
+static HTML_ENTITY: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"&(#[0-9]+|#[xX][0-9A-Fa-f]+|[A-Za-z][A-Za-z0-9]*);").unwrap());
+
+#[derive(Serialize, Clone)]
+pub struct DecodeLayer {
+    /// 此步骤去除的编码
+    pub encoding: String,
+    /// 解码后的数据: `is_text` 时为文本，否则为十六进制（会截断）
+    pub output: String,
+    pub is_text: bool,
+    pub size: usize,
+}
+
+/// 逐层剥离 `data` 的编码，直到没有可用的解码器或达到 `max_depth`
+pub fn peel(mut data: Vec<u8>, max_depth: usize) -> Vec<DecodeLayer> {
+    let mut layers = Vec::new();
+    while layers.len() < max_depth {
+        let Some((encoding, decoded, terminal)) = decode_once(&data) else {
+            break;
+        };
+        if decoded == data {
+            break;
+        }
+        layers.push(layer(encoding, &decoded));
+        data = decoded;
+        if terminal {
+            break;
+        }
+    }
+    layers
+}
+
+pub fn layer(encoding: &str, data: &[u8]) -> DecodeLayer {
+    let (output, is_text) = match std::str::from_utf8(data) {
+        Ok(text) => (text.to_string(), true),
+        Err(_) => {
+            let end = data.len().min(PREVIEW_BYTES);
+            (byte_io::encode_hex(&data[..end], false, " "), false)
+        }
+    };
+    DecodeLayer {
+        encoding: encoding.to_string(),
+        output,
+        is_text,
+        size: data.len(),
+    }
+}
+
+/// 一步解码: `(编码, 输出, 此步之后是否停止)`
+fn decode_once(data: &[u8]) -> Option<(&'static str, Vec<u8>, bool)> {
+    if let Some(inflated) = inflate(data) {
+        return Some(inflated);
+    }
+
+    let text = std::str::from_utf8(data).ok()?.trim();
+    if text.is_empty() {
+        return None;
+    }
+
+    if let Some(json) = decode_jwt(text) {
+        return Some(("jwt", json.into_bytes(), true));
+    }
+
+    let textual: [(&'static str, Decoder); 4] = [
+        ("url", decode_url),
+        ("unicode", decode_unicode_escapes),
+        ("html", decode_html),
+        ("punycode", decode_punycode),
+    ];
+    for (name, decode) in textual {
+        if let Some(decoded) = decode(text) {
+            return Some((name, decoded, false));
+        }
+    }
+
+    let binary: [(&'static str, Decoder); 2] = [("hex", decode_hex), ("base64", decode_base64)];
+    let mut fallback = None;
+    for (name, decode) in binary {
+        let Some(decoded) = decode(text) else {
+            continue;
+        };
+        if looks_like_text(&decoded) || has_magic(&decoded) {
+            return Some((name, decoded, false));
+        }
+        if fallback.is_none() && text.len() >= MIN_BINARY_INPUT {
+            fallback = Some((name, decoded, true));
+        }
+    }
+    fallback
+}
+
+fn inflate(data: &[u8]) -> Option<(&'static str, Vec<u8>, bool)> {
+    let algorithm = match data {
+        [0x1f, 0x8b, ..] => CompressionAlgorithm::Gzip,
+        [cmf, flg, ..] if is_zlib_header(*cmf, *flg) => CompressionAlgorithm::Zlib,
+        _ => return None,
+    };
+    let out = compression::decompress_limited(data, algorithm, MAX_INFLATED).ok()?;
+    Some((algorithm.name(), out, false))
+}
+
+fn has_magic(data: &[u8]) -> bool {
+    matches!(data, [0x1f, 0x8b, ..]) || matches!(data, [cmf, flg, ..] if is_zlib_header(*cmf, *flg))
+}
+
+/// 有效的 UTF-8，且几乎不含控制字符
+fn looks_like_text(data: &[u8]) -> bool {
+    let Ok(text) = std::str::from_utf8(data) else {
+        return false;
+    };
+    let total = text.chars().count();
+    let control = text
+        .chars()
+        .filter(|&c| (c.is_control() && !matches!(c, '\n' | '\r' | '\t')) || c == '\u{FFFD}')
+        .count();
+    total > 0 && control * 20 <= total
+}
+
+// ==================== 解码器 ====================
+
+fn decode_jwt(text: &str) -> Option<String> {
+    let mut parts = text.split('.');
+    let (header, payload, _signature) = (parts.next()?, parts.next()?, parts.next()?);
+    if parts.next().is_some() {
+        return None;
+    }
+    let header: serde_json::Value = serde_json::from_slice(&byte_io::decode_base64(header).ok()?).ok()?;
+    header.get("alg")?;
+    let payload_bytes = byte_io::decode_base64(payload).ok()?;
+    let payload = serde_json::from_slice(&payload_bytes)
+        .unwrap_or_else(|_| serde_json::Value::String(String::from_utf8_lossy(&payload_bytes).into_owned()));
+    serde_json::to_string_pretty(&serde_json::json!({ "header": header, "payload": payload })).ok()
+}
+
+fn decode_url(text: &str) -> Option<Vec<u8>> {
+    URL_ESCAPE.is_match(text).then(|| percent_encoding::percent_decode_str(text).collect())
+}
+
+fn decode_unicode_escapes(text: &str) -> Option<Vec<u8>> {
+    if !UNICODE_ESCAPE.is_match(text) {
+        return None;
+    }
+    encoding::unicode_to_chinese(ByteInput::Plain(text.to_string()), None)
+        .ok()
+        .map(String::into_bytes)
+}
+
+fn decode_html(text: &str) -> Option<Vec<u8>> {
+    if !HTML_ENTITY.is_match(text) {
+        return None;
+    }
+    let (decoded, _) = html_codec::decode(text, false);
+    (decoded != text).then(|| decoded.into_bytes())
+}
+
+fn decode_punycode(text: &str) -> Option<Vec<u8>> {
+    if !text.split('.').any(|label| label.to_ascii_lowercase().starts_with("xn--")) {
+        return None;
+    }
+    let labels = text
+        .split('.')
+        .map(|label| match label.get(..4) {
+            Some(prefix) if prefix.eq_ignore_ascii_case("xn--") => punycode::decode(&label[4..]).ok(),
+            _ => Some(label.to_string()),
+        })
+        .collect::<Option<Vec<_>>>()?;
+    Some(labels.join(".").into_bytes())
+}
+
+fn decode_hex(text: &str) -> Option<Vec<u8>> {
+    let digits = text
+        .chars()
+        .filter(|c| !c.is_whitespace() && !matches!(c, ':' | '-'))
+        .count();
+    if digits < 2 {
+        return None;
+    }
+    byte_io::decode_hex(text).ok()
+}
+
+fn decode_base64(text: &str) -> Option<Vec<u8>> {
+    let compact: String = text.chars().filter(|c| !c.is_whitespace()).collect();
+    let valid = compact.len() >= 4
+        && compact.len() % 4 != 1
+        && compact
+            .trim_end_matches('=')
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '/' | '-' | '_'));
+    if !valid {
+        return None;
+    }
+    byte_io::decode_base64(&compact).ok()
+}
@@ -6,7 +6,33 @@ pub mod pdf;
 pub mod code;
 pub mod file_ops;
 pub mod json;
+pub mod json_format;
+pub mod json_parse;
+pub mod json_profile;
+pub mod json_stream;
+pub mod jwt;
+pub mod basen;
+pub mod byte_io;
+pub mod charset_tools;
+pub mod chinese_tools;
+pub mod compression;
+pub mod crypto;
 pub mod encoding;
+pub mod gm_crypto;
+pub mod hash;
+pub mod html_codec;
+pub mod html_entities;
+pub mod magic;
+pub mod number_tools;
+pub mod otp;
+pub mod password_tools;
+pub mod password_words;
+pub mod pinyin_phrases;
+pub mod query_string;
+pub mod unicode_tools;
+pub mod url_parts;
+pub mod vault;
+pub mod x509;
 pub mod screen;
 pub mod search;
 pub mod role;
@@ -14,14 +40,12 @@ pub mod role;
 // 新增模块 (TODO: uncomment as modules are implemented)
 // pub mod data_convert;
 // pub mod json_diff;
-// pub mod crypto;
 // pub mod text_utils;
 // pub mod datetime;
 // pub mod network;
 // pub mod system;
 // pub mod xml_utils;
 // pub mod yaml_utils;
-// pub mod gm_crypto;
 // pub mod sql_utils;
 // pub mod db_tools;
 // pub mod image_utils;
@@ -31,5 +55,3 @@ pub mod role;
 // pub mod qrcode_tools;
 // pub mod uuid_tools;
 // pub mod cron_tools;
-// pub mod number_tools;
-// pub mod charset_tools;
@@ -0,0 +1,479 @@
+// 数值工具：任意精度整数的 2–36 进制转换、8/16/32/64/128 位补码、
+// IEEE-754 单/双精度位布局解析、字节序翻转、科学计数法展开、罗马数字。
+//
+// 整数输入统一经 `parse_integer` 解析：允许正负号、`_` 与空白分隔，
+// 以及与进制匹配的 `0x` / `0o` / `0b` 前缀；未指定进制时按前缀判断，
+// 没有前缀视为十进制。所有运算基于 num-bigint，不受 u64 范围限制。
+
+use num_bigint::{BigInt, BigUint, Sign};
+use num_traits::{One, Signed};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use super::byte_io::{self, ByteInput, ByteOutput};
+
+/// 科学计数法展开时允许的最大指数绝对值
+const MAX_EXPONENT: i64 = 10000;
+
+static SCIENTIFIC: Lazy<Regex> = Lazy::new(|| Regex::new(r"^([+-]?)(\d*)(?:\.(\d*))?(?:[eE]([+-]?\d+))?$").unwrap());
+
+// ==================== 进制转换 ====================
+
+/// 解析整数，`base` 为 None 时按前缀判断进制
+pub fn parse_integer(text: &str, base: Option<u32>) -> Result<BigInt, String> {
+    let compact: String = text.chars().filter(|c| !c.is_whitespace() && *c != '_').collect();
+    let (negative, rest) = match compact.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, compact.strip_prefix('+').unwrap_or(&compact)),
+    };
+    let prefixed = |prefix: &str| {
+        rest.get(..2)
+            .filter(|head| head.eq_ignore_ascii_case(prefix))
+            .map(|_| &rest[2..])
+    };
+    let (base, digits) = match base {
+        Some(base) => {
+            check_base(base)?;
+            let prefix = match base {
+                16 => prefixed("0x"),
+                8 => prefixed("0o"),
+                2 => prefixed("0b"),
+                _ => None,
+            };
+            (base, prefix.unwrap_or(rest))
+        }
+        None => {
+            if let Some(digits) = prefixed("0x") {
+                (16, digits)
+            } else if let Some(digits) = prefixed("0o") {
+                (8, digits)
+            } else if let Some(digits) = prefixed("0b") {
+                (2, digits)
+            } else {
+                (10, rest)
+            }
+        }
+    };
+    if digits.is_empty() {
+        return Err("输入不能为空".to_string());
+    }
+    if let Some(c) = digits.chars().find(|c| !c.is_digit(base)) {
+        return Err(format!("无效的{}进制数字: {}", base, c));
+    }
+    let magnitude = BigUint::parse_bytes(digits.as_bytes(), base).ok_or("数字解析失败")?;
+    let sign = if negative { Sign::Minus } else { Sign::Plus };
+    Ok(BigInt::from_biguint(sign, magnitude))
+}
+
+pub fn format_integer(value: &BigInt, base: u32, uppercase: bool) -> String {
+    let text = value.to_str_radix(base);
+    if uppercase {
+        text.to_uppercase()
+    } else {
+        text
+    }
+}
+
+fn check_base(base: u32) -> Result<(), String> {
+    if (2..=36).contains(&base) {
+        Ok(())
+    } else {
+        Err(format!("进制必须在 2 到 36 之间: {}", base))
+    }
+}
+
+/// 任意进制互转，`from_base` 缺省时按 `0x` / `0o` / `0b` 前缀判断
+#[tauri::command]
+pub fn convert_base(
+    value: String,
+    from_base: Option<u32>,
+    to_base: u32,
+    uppercase: Option<bool>,
+) -> Result<String, String> {
+    check_base(to_base)?;
+    let number = parse_integer(&value, from_base)?;
+    Ok(format_integer(&number, to_base, uppercase.unwrap_or(true)))
+}
+
+#[tauri::command]
+pub fn dec_to_hex(num: String) -> Result<String, String> {
+    Ok(format_integer(&parse_integer(&num, Some(10))?, 16, true))
+}
+
+#[tauri::command]
+pub fn dec_to_binary(num: String) -> Result<String, String> {
+    Ok(format_integer(&parse_integer(&num, Some(10))?, 2, false))
+}
+
+#[tauri::command]
+pub fn dec_to_octal(num: String) -> Result<String, String> {
+    Ok(format_integer(&parse_integer(&num, Some(10))?, 8, false))
+}
+
+#[tauri::command]
+pub fn hex_to_dec(hex: String) -> Result<String, String> {
+    Ok(parse_integer(&hex, Some(16))?.to_string())
+}
+
+#[tauri::command]
+pub fn binary_to_dec(binary: String) -> Result<String, String> {
+    Ok(parse_integer(&binary, Some(2))?.to_string())
+}
+
+#[tauri::command]
+pub fn octal_to_dec(octal: String) -> Result<String, String> {
+    Ok(parse_integer(&octal, Some(8))?.to_string())
+}
+
+// ==================== 补码 ====================
+
+#[derive(Serialize)]
+pub struct TwosComplement {
+    pub bits: u32,
+    /// 按有符号数解释的值
+    pub signed: String,
+    /// 按无符号数解释的值
+    pub unsigned: String,
+    pub hex: String,
+    /// 按字节分组的位串
+    pub binary: String,
+    pub signed_min: String,
+    pub signed_max: String,
+    pub unsigned_max: String,
+}
+
+/// 把 `value` 写成 `bits` 位补码的位模式，负数和超过有符号上限的正数都允许，
+/// 只要落在 [-2^(bits-1), 2^bits - 1] 内
+pub fn to_twos_complement(value: &BigInt, bits: u32) -> Result<BigUint, String> {
+    let modulus = BigInt::one() << bits;
+    let min = -(BigInt::one() << (bits - 1));
+    if *value < min || *value >= modulus {
+        return Err(format!("{} 超出 {} 位可表示范围", value, bits));
+    }
+    let pattern = if value.is_negative() { value + &modulus } else { value.clone() };
+    Ok(pattern.to_biguint().unwrap_or_default())
+}
+
+/// 按有符号数容纳 `value` 所需的最少位数（按字节对齐）
+pub fn min_signed_bits(value: &BigInt) -> u32 {
+    let mut bits = 8u32;
+    loop {
+        let half = BigInt::one() << (bits - 1);
+        if *value >= -&half && *value < half {
+            return bits;
+        }
+        bits += 8;
+    }
+}
+
+/// 在 8/16/32/64/128 位下查看整数的补码表示；输入可以是负数，也可以是
+/// `0xFF` 这样的位模式（按有符号数重新解释）
+#[tauri::command]
+pub fn twos_complement(value: String, base: Option<u32>, bits: u32) -> Result<TwosComplement, String> {
+    if !matches!(bits, 8 | 16 | 32 | 64 | 128) {
+        return Err(format!("位宽只支持 8/16/32/64/128: {}", bits));
+    }
+    let number = parse_integer(&value, base)?;
+    let pattern = to_twos_complement(&number, bits)?;
+    let pattern_int = BigInt::from(pattern.clone());
+    let half = BigInt::one() << (bits - 1);
+    let signed = if pattern_int >= half {
+        pattern_int - (BigInt::one() << bits)
+    } else {
+        pattern_int
+    };
+
+    let binary = format!("{:0>width$}", pattern.to_str_radix(2), width = bits as usize);
+    let binary = binary
+        .as_bytes()
+        .chunks(8)
+        .map(|chunk| std::str::from_utf8(chunk).unwrap_or_default())
+        .collect::<Vec<_>>()
+        .join(" ");
+    Ok(TwosComplement {
+        bits,
+        signed: signed.to_string(),
+        unsigned: pattern.to_string(),
+        hex: format!("{:0>width$}", pattern.to_str_radix(16).to_uppercase(), width = bits as usize / 4),
+        binary,
+        signed_min: (-&half).to_string(),
+        signed_max: (&half - BigInt::one()).to_string(),
+        unsigned_max: ((BigInt::one() << bits) - BigInt::one()).to_string(),
+    })
+}
+
+// ==================== IEEE-754 ====================
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum FloatFormat {
+    F32,
+    #[default]
+    F64,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum Endian {
+    #[default]
+    Big,
+    Little,
+}
+
+impl FloatFormat {
+    /// (总位数, 指数位数, 尾数位数, 偏置)
+    fn layout(self) -> (u32, u32, u32, i32) {
+        match self {
+            FloatFormat::F32 => (32, 8, 23, 127),
+            FloatFormat::F64 => (64, 11, 52, 1023),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct FloatLayout {
+    pub format: FloatFormat,
+    /// 最短的可往返十进制表示
+    pub value: String,
+    /// 该浮点数精确的十进制值
+    pub exact: String,
+    /// 大端位模式
+    pub hex: String,
+    pub little_endian: String,
+    /// 符号位 指数位 尾数位，空格分隔
+    pub binary: String,
+    pub sign: u8,
+    pub biased_exponent: u32,
+    /// 去掉偏置后的指数，非规格化数按 1 - bias 计
+    pub exponent: i32,
+    pub mantissa: String,
+    /// normal / subnormal / zero / infinity / nan
+    pub class: String,
+}
+
+/// 解析浮点数的位布局。`value` 为十进制数（也接受 inf / NaN），或带 `0x`
+/// 前缀的位模式；`from_bits` 为 true 时不带前缀也按十六进制位模式解析，
+/// 此时 `endian` 指定字节顺序
+#[tauri::command]
+pub fn decode_float(
+    value: String,
+    format: Option<FloatFormat>,
+    from_bits: Option<bool>,
+    endian: Option<Endian>,
+) -> Result<FloatLayout, String> {
+    let format = format.unwrap_or_default();
+    let (total, exponent_bits, mantissa_bits, bias) = format.layout();
+    let text = value.trim();
+    let hex = text.strip_prefix("0x").or_else(|| text.strip_prefix("0X"));
+    let bits = if from_bits.unwrap_or(false) || hex.is_some() {
+        let mut bytes = byte_io::decode_hex(hex.unwrap_or(text))?;
+        if bytes.len() * 8 != total as usize {
+            return Err(format!("{:?} 需要 {} 字节，实际 {} 字节", format, total / 8, bytes.len()));
+        }
+        if endian.unwrap_or_default() == Endian::Little {
+            bytes.reverse();
+        }
+        bytes.iter().fold(0u64, |acc, &b| acc << 8 | u64::from(b))
+    } else {
+        match format {
+            FloatFormat::F32 => u64::from(text.parse::<f32>().map_err(|_| format!("无效的数字: {}", text))?.to_bits()),
+            FloatFormat::F64 => text.parse::<f64>().map_err(|_| format!("无效的数字: {}", text))?.to_bits(),
+        }
+    };
+
+    let sign = (bits >> (total - 1)) as u8 & 1;
+    let biased_exponent = ((bits >> mantissa_bits) & ((1 << exponent_bits) - 1)) as u32;
+    let fraction = bits & ((1u64 << mantissa_bits) - 1);
+    let max_exponent = (1 << exponent_bits) - 1;
+    let class = match (biased_exponent, fraction) {
+        (0, 0) => "zero",
+        (0, _) => "subnormal",
+        (e, 0) if e == max_exponent => "infinity",
+        (e, _) if e == max_exponent => "nan",
+        _ => "normal",
+    };
+    let exponent = biased_exponent.max(1) as i32 - bias;
+    let value = match format {
+        FloatFormat::F32 => f32::from_bits(bits as u32).to_string(),
+        FloatFormat::F64 => f64::from_bits(bits).to_string(),
+    };
+    let exact = match class {
+        "infinity" | "nan" => value.clone(),
+        _ => {
+            let significand = if biased_exponent == 0 { fraction } else { fraction | 1 << mantissa_bits };
+            let digits = exact_decimal(significand, exponent - mantissa_bits as i32);
+            if sign == 1 {
+                format!("-{}", digits)
+            } else {
+                digits
+            }
+        }
+    };
+
+    let bytes = bits.to_be_bytes();
+    let bytes = &bytes[8 - total as usize / 8..];
+    let binary = format!("{:0>width$b}", bits, width = total as usize);
+    Ok(FloatLayout {
+        format,
+        value,
+        exact,
+        hex: byte_io::encode_hex(bytes, true, ""),
+        little_endian: byte_io::encode_hex(&bytes.iter().rev().copied().collect::<Vec<_>>(), true, " "),
+        binary: format!(
+            "{} {} {}",
+            &binary[..1],
+            &binary[1..1 + exponent_bits as usize],
+            &binary[1 + exponent_bits as usize..]
+        ),
+        sign,
+        biased_exponent,
+        exponent,
+        mantissa: format!("{:0>width$X}", fraction, width = mantissa_bits.div_ceil(4) as usize),
+        class: class.to_string(),
+    })
+}
+
+/// significand × 2^exponent 的精确十进制展开
+fn exact_decimal(significand: u64, exponent: i32) -> String {
+    if significand == 0 {
+        return "0".to_string();
+    }
+    if exponent >= 0 {
+        return (BigUint::from(significand) << exponent as u32).to_string();
+    }
+    // m / 2^k = m × 5^k / 10^k
+    let places = exponent.unsigned_abs() as usize;
+    let digits = (BigUint::from(significand) * BigUint::from(5u32).pow(places as u32)).to_string();
+    let digits = format!("{:0>width$}", digits, width = places + 1);
+    let (int, frac) = digits.split_at(digits.len() - places);
+    let frac = frac.trim_end_matches('0');
+    if frac.is_empty() {
+        int.to_string()
+    } else {
+        format!("{}.{}", int, frac)
+    }
+}
+
+// ==================== 字节序 ====================
+
+/// 按 `width` 字节分组翻转字节序，缺省时翻转整个输入；默认以 `AA BB` 形式输出
+#[tauri::command]
+pub fn swap_endian(input: ByteInput, width: Option<usize>, output: Option<ByteOutput>) -> Result<String, String> {
+    let mut bytes = input.into_bytes()?;
+    let width = width.unwrap_or(bytes.len());
+    if width == 0 {
+        return Err("分组宽度必须大于 0".to_string());
+    }
+    if !bytes.len().is_multiple_of(width) {
+        return Err(format!("长度 {} 不是分组宽度 {} 的整数倍", bytes.len(), width));
+    }
+    for group in bytes.chunks_mut(width) {
+        group.reverse();
+    }
+    output
+        .unwrap_or(ByteOutput::Hex {
+            uppercase: true,
+            separator: Some(" ".to_string()),
+        })
+        .write(&bytes)
+}
+
+// ==================== 科学计数法 ====================
+
+/// `1.23e4` → `12300`，`-1.5E-3` → `-0.0015`，按字符串运算不丢精度
+#[tauri::command]
+pub fn scientific_to_decimal(num: String) -> Result<String, String> {
+    let text = num.trim();
+    let caps = SCIENTIFIC
+        .captures(text)
+        .ok_or_else(|| format!("无效的科学计数法: {}", text))?;
+    let int = caps.get(2).map_or("", |m| m.as_str());
+    let frac = caps.get(3).map_or("", |m| m.as_str());
+    if int.is_empty() && frac.is_empty() {
+        return Err(format!("无效的科学计数法: {}", text));
+    }
+    let exponent: i64 = caps
+        .get(4)
+        .map_or(Ok(0), |m| m.as_str().parse())
+        .map_err(|_| "指数过大".to_string())?;
+    if exponent.abs() > MAX_EXPONENT {
+        return Err(format!("指数绝对值不能超过 {}", MAX_EXPONENT));
+    }
+
+    let digits = format!("{}{}", int, frac);
+    let point = int.len() as i64 + exponent;
+    let (int_part, frac_part) = if point <= 0 {
+        ("0".to_string(), format!("{}{}", "0".repeat(point.unsigned_abs() as usize), digits))
+    } else if point as usize >= digits.len() {
+        (format!("{}{}", digits, "0".repeat(point as usize - digits.len())), String::new())
+    } else {
+        (digits[..point as usize].to_string(), digits[point as usize..].to_string())
+    };
+    let int_part = int_part.trim_start_matches('0');
+    let frac_part = frac_part.trim_end_matches('0');
+    let mut result = if int_part.is_empty() { "0".to_string() } else { int_part.to_string() };
+    if !frac_part.is_empty() {
+        result.push('.');
+        result.push_str(frac_part);
+    }
+    if caps.get(1).is_some_and(|m| m.as_str() == "-") && result != "0" {
+        result.insert(0, '-');
+    }
+    Ok(result)
+}
+
+// ==================== 罗马数字 ====================
+
+const ROMAN: [(u32, &str); 13] = [
+    (1000, "M"),
+    (900, "CM"),
+    (500, "D"),
+    (400, "CD"),
+    (100, "C"),
+    (90, "XC"),
+    (50, "L"),
+    (40, "XL"),
+    (10, "X"),
+    (9, "IX"),
+    (5, "V"),
+    (4, "IV"),
+    (1, "I"),
+];
+
+#[tauri::command]
+pub fn to_roman(num: u32) -> Result<String, String> {
+    if !(1..=3999).contains(&num) {
+        return Err("罗马数字只能表示 1 到 3999".to_string());
+    }
+    let mut rest = num;
+    let mut result = String::new();
+    for (value, symbol) in ROMAN {
+        while rest >= value {
+            result.push_str(symbol);
+            rest -= value;
+        }
+    }
+    Ok(result)
+}
+
+/// 只接受规范写法（`IIII`、`VX`、`IC` 之类会报错）
+#[tauri::command]
+pub fn from_roman(roman: String) -> Result<u32, String> {
+    let roman = roman.trim().to_uppercase();
+    if roman.is_empty() {
+        return Err("输入不能为空".to_string());
+    }
+    let mut rest = roman.as_str();
+    let mut total = 0;
+    for (value, symbol) in ROMAN {
+        while let Some(next) = rest.strip_prefix(symbol) {
+            total += value;
+            rest = next;
+        }
+    }
+    if !rest.is_empty() || to_roman(total).ok().as_deref() != Some(roman.as_str()) {
+        return Err(format!("无效的罗马数字: {}", roman));
+    }
+    Ok(total)
+}
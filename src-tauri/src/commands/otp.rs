@@ -0,0 +1,563 @@
+// 一次性密码（OTP）工具
+//
+// HOTP（RFC 4226）与 TOTP（RFC 6238）的生成和校验，支持 SHA1/SHA256/SHA512、
+// 4–10 位数字和任意时间步长；otpauth:// URI（Google Authenticator Key URI
+// 格式）的解析与构建，并可渲染为二维码 PNG/SVG 供手机扫描。
+//
+// 密钥统一用 Base32（RFC 4648，忽略大小写、空格和填充）。凡是接收 OtpConfig
+// 的命令，secret 字段也可以直接填写完整的 otpauth:// URI，密码库条目的 totp
+// 字段无论存的是哪种形式都能直接使用。
+
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use hmac::{Hmac, Mac};
+use image::{ImageFormat, Luma};
+use percent_encoding::{percent_decode_str, utf8_percent_encode};
+use qrcode::render::svg;
+use qrcode::{EcLevel, QrCode};
+use rand::rngs::OsRng;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha1::Sha1;
+use sha2::{Sha256, Sha512};
+use std::io::Cursor;
+
+use super::basen::{base32_decode, base32_encode, BASE32_RFC4648};
+use super::url_parts::{QUERY_COMPONENT, UNRESERVED};
+
+const DEFAULT_DIGITS: u32 = 6;
+const DEFAULT_PERIOD: u64 = 30;
+const DEFAULT_WINDOW: u64 = 1;
+const MAX_WINDOW: u64 = 100;
+/// RFC 4226 要求密钥至少 128 位，推荐 160 位
+const MIN_SECRET_BYTES: usize = 10;
+const DEFAULT_SECRET_BYTES: usize = 20;
+
+// ==================== 参数 ====================
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum OtpKind {
+    #[default]
+    Totp,
+    Hotp,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum OtpAlgorithm {
+    #[default]
+    Sha1,
+    Sha256,
+    Sha512,
+}
+
+impl OtpAlgorithm {
+    fn parse(name: &str) -> Result<Self, String> {
+        match name.to_ascii_uppercase().replace('-', "").as_str() {
+            "SHA1" => Ok(OtpAlgorithm::Sha1),
+            "SHA256" => Ok(OtpAlgorithm::Sha256),
+            "SHA512" => Ok(OtpAlgorithm::Sha512),
+            _ => Err(format!("不支持的算法: {}", name)),
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            OtpAlgorithm::Sha1 => "SHA1",
+            OtpAlgorithm::Sha256 => "SHA256",
+            OtpAlgorithm::Sha512 => "SHA512",
+        }
+    }
+}
+
+fn default_digits() -> u32 {
+    DEFAULT_DIGITS
+}
+
+fn default_period() -> u64 {
+    DEFAULT_PERIOD
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct OtpConfig {
+    #[serde(default)]
+    kind: OtpKind,
+    /// Base32 密钥，或完整的 otpauth:// URI
+    secret: String,
+    #[serde(default)]
+    algorithm: OtpAlgorithm,
+    #[serde(default = "default_digits")]
+    digits: u32,
+    /// TOTP 时间步长（秒）
+    #[serde(default = "default_period")]
+    period: u64,
+    /// HOTP 计数器
+    #[serde(default)]
+    counter: u64,
+    issuer: Option<String>,
+    account: Option<String>,
+}
+
+impl OtpConfig {
+    /// secret 为 URI 时展开为 URI 中的参数，并校验各项取值
+    fn resolve(self) -> Result<(OtpConfig, Vec<u8>), String> {
+        let config = if self.secret.trim_start().starts_with("otpauth://") {
+            parse_uri(self.secret.trim())?
+        } else {
+            self
+        };
+        if !(4..=10).contains(&config.digits) {
+            return Err("位数应在 4 到 10 之间".to_string());
+        }
+        if config.kind == OtpKind::Totp && config.period == 0 {
+            return Err("时间步长必须大于 0".to_string());
+        }
+        let key = decode_secret(&config.secret)?;
+        Ok((config, key))
+    }
+}
+
+fn decode_secret(secret: &str) -> Result<Vec<u8>, String> {
+    let cleaned: String = secret.chars().filter(|c| !c.is_whitespace() && *c != '-').collect();
+    if cleaned.is_empty() {
+        return Err("密钥不能为空".to_string());
+    }
+    base32_decode(&cleaned, BASE32_RFC4648).map_err(|e| format!("密钥不是有效的 Base32: {}", e))
+}
+
+/// Base32 输出不带填充，与各验证器 App 的习惯一致
+fn encode_secret(key: &[u8]) -> String {
+    base32_encode(key, BASE32_RFC4648, false)
+}
+
+// ==================== 生成与校验 ====================
+
+/// RFC 4226 第 5.3 节：HMAC 后动态截断取 31 位，再对 10^digits 取模
+fn hotp(key: &[u8], counter: u64, algorithm: OtpAlgorithm, digits: u32) -> String {
+    let message = counter.to_be_bytes();
+    let hash = match algorithm {
+        OtpAlgorithm::Sha1 => hmac_digest::<Hmac<Sha1>>(key, &message),
+        OtpAlgorithm::Sha256 => hmac_digest::<Hmac<Sha256>>(key, &message),
+        OtpAlgorithm::Sha512 => hmac_digest::<Hmac<Sha512>>(key, &message),
+    };
+    let offset = (hash[hash.len() - 1] & 0x0f) as usize;
+    let binary = u32::from_be_bytes([hash[offset], hash[offset + 1], hash[offset + 2], hash[offset + 3]]) & 0x7fff_ffff;
+    let code = binary as u64 % 10u64.pow(digits);
+    format!("{:0width$}", code, width = digits as usize)
+}
+
+fn hmac_digest<M: Mac + hmac::digest::KeyInit>(key: &[u8], message: &[u8]) -> Vec<u8> {
+    let mut mac = <M as hmac::digest::KeyInit>::new_from_slice(key).expect("HMAC 接受任意长度的密钥");
+    mac.update(message);
+    mac.finalize().into_bytes().to_vec()
+}
+
+fn resolve_time(timestamp: Option<i64>) -> Result<u64, String> {
+    let timestamp = timestamp.unwrap_or_else(|| chrono::Utc::now().timestamp());
+    u64::try_from(timestamp).map_err(|_| "时间戳不能早于 1970-01-01".to_string())
+}
+
+#[derive(Debug, Serialize)]
+pub struct OtpCode {
+    code: String,
+    kind: OtpKind,
+    /// HOTP 为所用计数器，TOTP 为时间步序号
+    counter: u64,
+    /// 以下仅 TOTP：当前验证码的有效区间（Unix 秒）与剩余秒数
+    valid_from: Option<u64>,
+    valid_until: Option<u64>,
+    remaining_secs: Option<u64>,
+    /// TOTP 为下一时间步的验证码，HOTP 为 counter + 1 的验证码
+    next_code: String,
+}
+
+/// 生成验证码。timestamp 为 Unix 秒，默认当前时间，仅 TOTP 使用
+#[tauri::command]
+pub fn generate_otp(config: OtpConfig, timestamp: Option<i64>) -> Result<OtpCode, String> {
+    let (config, key) = config.resolve()?;
+    let code = |counter| hotp(&key, counter, config.algorithm, config.digits);
+    match config.kind {
+        OtpKind::Hotp => Ok(OtpCode {
+            code: code(config.counter),
+            kind: OtpKind::Hotp,
+            counter: config.counter,
+            valid_from: None,
+            valid_until: None,
+            remaining_secs: None,
+            next_code: code(config.counter.wrapping_add(1)),
+        }),
+        OtpKind::Totp => {
+            let now = resolve_time(timestamp)?;
+            let step = now / config.period;
+            let valid_from = step * config.period;
+            let valid_until = valid_from + config.period;
+            Ok(OtpCode {
+                code: code(step),
+                kind: OtpKind::Totp,
+                counter: step,
+                valid_from: Some(valid_from),
+                valid_until: Some(valid_until),
+                remaining_secs: Some(valid_until - now),
+                next_code: code(step + 1),
+            })
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct OtpValidation {
+    valid: bool,
+    /// 匹配到的计数器或时间步序号
+    counter: Option<u64>,
+    /// 相对期望值偏移了几个步长：TOTP 可正可负，HOTP 只会向前
+    drift: Option<i64>,
+    /// HOTP 校验成功后服务端应保存的下一个计数器
+    next_counter: Option<u64>,
+}
+
+/// 校验验证码。window 为允许的偏移步数（默认 1）：TOTP 前后各 window 步，
+/// HOTP 按 RFC 4226 第 7.4 节只向前查找 window 个计数器
+#[tauri::command]
+pub fn validate_otp(
+    config: OtpConfig,
+    code: String,
+    window: Option<u64>,
+    timestamp: Option<i64>,
+) -> Result<OtpValidation, String> {
+    let (config, key) = config.resolve()?;
+    let window = window.unwrap_or(DEFAULT_WINDOW);
+    if window > MAX_WINDOW {
+        return Err(format!("偏移窗口不能超过 {}", MAX_WINDOW));
+    }
+    let code: String = code.chars().filter(|c| !c.is_whitespace()).collect();
+    let matches = |counter: u64| {
+        let expected = hotp(&key, counter, config.algorithm, config.digits);
+        constant_time_eq(expected.as_bytes(), code.as_bytes())
+    };
+
+    let found = match config.kind {
+        OtpKind::Hotp => (0..=window)
+            .filter_map(|offset| config.counter.checked_add(offset))
+            .find(|&counter| matches(counter))
+            .map(|counter| (counter, (counter - config.counter) as i64)),
+        OtpKind::Totp => {
+            let step = resolve_time(timestamp)? / config.period;
+            // 由近及远：0, -1, +1, -2, +2 …
+            std::iter::once(0)
+                .chain((1..=window as i64).flat_map(|d| [-d, d]))
+                .filter_map(|drift| step.checked_add_signed(drift).map(|counter| (counter, drift)))
+                .find(|&(counter, _)| matches(counter))
+        }
+    };
+    Ok(OtpValidation {
+        valid: found.is_some(),
+        counter: found.map(|(counter, _)| counter),
+        drift: found.map(|(_, drift)| drift),
+        next_counter: match config.kind {
+            // 计数器已到 u64::MAX 时没有下一个可用值
+            OtpKind::Hotp => found.and_then(|(counter, _)| counter.checked_add(1)),
+            OtpKind::Totp => None,
+        },
+    })
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// 生成随机 Base32 密钥，默认 20 字节（160 位）
+#[tauri::command]
+pub fn generate_otp_secret(bytes: Option<usize>) -> Result<String, String> {
+    let bytes = bytes.unwrap_or(DEFAULT_SECRET_BYTES);
+    if !(MIN_SECRET_BYTES..=64).contains(&bytes) {
+        return Err(format!("密钥长度应在 {} 到 64 字节之间", MIN_SECRET_BYTES));
+    }
+    let mut key = vec![0u8; bytes];
+    OsRng.fill_bytes(&mut key);
+    Ok(encode_secret(&key))
+}
+
+// ==================== otpauth:// URI ====================
+
+/// 解析 otpauth://TYPE/LABEL?secret=…&issuer=…&algorithm=…&digits=…&period=…&counter=…
+#[tauri::command]
+pub fn parse_otp_uri(uri: String) -> Result<OtpConfig, String> {
+    let config = parse_uri(uri.trim())?;
+    decode_secret(&config.secret)?;
+    Ok(config)
+}
+
+fn parse_uri(uri: &str) -> Result<OtpConfig, String> {
+    let rest = uri
+        .strip_prefix("otpauth://")
+        .ok_or_else(|| "不是 otpauth:// URI".to_string())?;
+    let (path, query) = rest.split_once('?').unwrap_or((rest, ""));
+    let (kind, label) = path.split_once('/').unwrap_or((path, ""));
+    let kind = match kind.to_ascii_lowercase().as_str() {
+        "totp" => OtpKind::Totp,
+        "hotp" => OtpKind::Hotp,
+        other => return Err(format!("未知的 OTP 类型: {}", other)),
+    };
+
+    // 标签为 “发行方:账户” 或仅 “账户”，冒号本身也可能被编码为 %3A
+    let label = decode_component(label)?;
+    let (label_issuer, account) = match label.split_once(':') {
+        Some((issuer, account)) => (Some(issuer.trim().to_string()), account.trim().to_string()),
+        None => (None, label.trim().to_string()),
+    };
+
+    let mut config = OtpConfig {
+        kind,
+        secret: String::new(),
+        algorithm: OtpAlgorithm::default(),
+        digits: DEFAULT_DIGITS,
+        period: DEFAULT_PERIOD,
+        counter: 0,
+        issuer: label_issuer.filter(|s| !s.is_empty()),
+        account: Some(account).filter(|s| !s.is_empty()),
+    };
+    let mut has_counter = false;
+    for pair in query.split('&').filter(|p| !p.is_empty()) {
+        let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+        let value = decode_component(&value.replace('+', " "))?;
+        let number = |name: &str| value.parse::<u64>().map_err(|_| format!("参数 {} 不是有效的数字: {}", name, value));
+        match key.to_ascii_lowercase().as_str() {
+            "secret" => config.secret = value.clone(),
+            // 查询参数中的发行方优先于标签前缀
+            "issuer" if !value.is_empty() => config.issuer = Some(value.clone()),
+            "algorithm" => config.algorithm = OtpAlgorithm::parse(&value)?,
+            "digits" => {
+                config.digits = u32::try_from(number("digits")?).map_err(|_| "位数应在 4 到 10 之间".to_string())?
+            }
+            "period" => config.period = number("period")?,
+            "counter" => {
+                config.counter = number("counter")?;
+                has_counter = true;
+            }
+            _ => {}
+        }
+    }
+    if config.secret.is_empty() {
+        return Err("URI 缺少 secret 参数".to_string());
+    }
+    if kind == OtpKind::Hotp && !has_counter {
+        return Err("HOTP URI 缺少 counter 参数".to_string());
+    }
+    Ok(config)
+}
+
+fn decode_component(s: &str) -> Result<String, String> {
+    percent_decode_str(s)
+        .decode_utf8()
+        .map(|s| s.into_owned())
+        .map_err(|_| "URI 中含有无效的 UTF-8 编码".to_string())
+}
+
+/// 构建 otpauth:// URI。默认值（SHA1、6 位、30 秒）也会写出，部分 App 不认省略
+#[tauri::command]
+pub fn build_otp_uri(config: OtpConfig) -> Result<String, String> {
+    let (config, key) = config.resolve()?;
+    let encode = |s: &str| utf8_percent_encode(s, UNRESERVED).to_string();
+    let account = config.account.as_deref().unwrap_or("");
+    let label = match config.issuer.as_deref() {
+        Some(issuer) => format!("{}:{}", encode(issuer), encode(account)),
+        None => encode(account),
+    };
+    let kind = match config.kind {
+        OtpKind::Totp => "totp",
+        OtpKind::Hotp => "hotp",
+    };
+    let mut uri = format!("otpauth://{}/{}?secret={}", kind, label, encode_secret(&key));
+    if let Some(issuer) = &config.issuer {
+        uri.push_str(&format!("&issuer={}", utf8_percent_encode(issuer, QUERY_COMPONENT)));
+    }
+    uri.push_str(&format!("&algorithm={}&digits={}", config.algorithm.name(), config.digits));
+    match config.kind {
+        OtpKind::Totp => uri.push_str(&format!("&period={}", config.period)),
+        OtpKind::Hotp => uri.push_str(&format!("&counter={}", config.counter)),
+    }
+    Ok(uri)
+}
+
+// ==================== 二维码 ====================
+
+#[derive(Debug, Clone, Copy, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum QrFormat {
+    #[default]
+    Png,
+    Svg,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct OtpQrOptions {
+    #[serde(default)]
+    format: QrFormat,
+    /// 每个模块的像素数，默认 8
+    scale: Option<u32>,
+    /// 保存路径；不填则只返回 data URL
+    output_path: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OtpQrCode {
+    uri: String,
+    /// data:image/png;base64,… 或 data:image/svg+xml;base64,…
+    data_url: String,
+    width: u32,
+    height: u32,
+    output_path: Option<String>,
+}
+
+/// 把 otpauth:// URI 渲染为二维码，可先用 build_otp_uri 构建
+#[tauri::command]
+pub fn render_otp_qr(uri: String, options: Option<OtpQrOptions>) -> Result<OtpQrCode, String> {
+    let options = options.unwrap_or_default();
+    // 先解析一遍，保证二维码里是验证器 App 能识别的 URI
+    parse_otp_uri(uri.clone())?;
+    let scale = options.scale.unwrap_or(8);
+    if !(1..=64).contains(&scale) {
+        return Err("模块像素数应在 1 到 64 之间".to_string());
+    }
+    let code = QrCode::with_error_correction_level(uri.as_bytes(), EcLevel::M)
+        .map_err(|e| format!("生成二维码失败: {}", e))?;
+
+    let (bytes, mime, width, height) = match options.format {
+        QrFormat::Png => {
+            let image = code.render::<Luma<u8>>().module_dimensions(scale, scale).build();
+            let (width, height) = image.dimensions();
+            let mut png = Vec::new();
+            image
+                .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+                .map_err(|e| format!("编码 PNG 失败: {}", e))?;
+            (png, "image/png", width, height)
+        }
+        QrFormat::Svg => {
+            let svg = code.render::<svg::Color>().module_dimensions(scale, scale).build();
+            let side = (code.width() as u32 + 8) * scale;
+            (svg.into_bytes(), "image/svg+xml", side, side)
+        }
+    };
+    if let Some(path) = &options.output_path {
+        std::fs::write(path, &bytes).map_err(|e| format!("写入文件失败 {}: {}", path, e))?;
+    }
+    Ok(OtpQrCode {
+        uri,
+        data_url: format!("data:{};base64,{}", mime, STANDARD.encode(&bytes)),
+        width,
+        height,
+        output_path: options.output_path,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(kind: OtpKind, key: &[u8], algorithm: OtpAlgorithm, digits: u32) -> OtpConfig {
+        OtpConfig {
+            kind,
+            secret: encode_secret(key),
+            algorithm,
+            digits,
+            period: DEFAULT_PERIOD,
+            counter: 0,
+            issuer: None,
+            account: None,
+        }
+    }
+
+    // RFC 4226 附录 D
+    #[test]
+    fn hotp_rfc4226_vectors() {
+        let expected = [
+            "755224", "287082", "359152", "969429", "338314", "254676", "287922", "162583", "399871", "520489",
+        ];
+        for (counter, code) in expected.iter().enumerate() {
+            let config = OtpConfig {
+                counter: counter as u64,
+                ..config(OtpKind::Hotp, b"12345678901234567890", OtpAlgorithm::Sha1, 6)
+            };
+            assert_eq!(generate_otp(config, None).unwrap().code, *code, "counter {}", counter);
+        }
+    }
+
+    // RFC 6238 附录 B，三种算法的种子长度分别为 20、32、64 字节
+    #[test]
+    fn totp_rfc6238_vectors() {
+        let vectors: [(i64, [&str; 3]); 6] = [
+            (59, ["94287082", "46119246", "90693936"]),
+            (1111111109, ["07081804", "68084774", "25091201"]),
+            (1111111111, ["14050471", "67062674", "99943326"]),
+            (1234567890, ["89005924", "91819424", "93441116"]),
+            (2000000000, ["69279037", "90698825", "38618901"]),
+            (20000000000, ["65353130", "77737706", "47863826"]),
+        ];
+        let keys: [(OtpAlgorithm, &[u8]); 3] = [
+            (OtpAlgorithm::Sha1, b"12345678901234567890"),
+            (OtpAlgorithm::Sha256, b"12345678901234567890123456789012"),
+            (
+                OtpAlgorithm::Sha512,
+                b"1234567890123456789012345678901234567890123456789012345678901234",
+            ),
+        ];
+        for (time, codes) in vectors {
+            for ((algorithm, key), code) in keys.iter().zip(codes) {
+                let config = config(OtpKind::Totp, key, *algorithm, 8);
+                assert_eq!(generate_otp(config, Some(time)).unwrap().code, code, "{:?} @ {}", algorithm, time);
+            }
+        }
+    }
+
+    #[test]
+    fn validate_within_window() {
+        let key = b"12345678901234567890";
+        let totp = config(OtpKind::Totp, key, OtpAlgorithm::Sha1, 8);
+        // 59 秒处的验证码在 89 秒时落在上一个时间步
+        let result = validate_otp(totp, "94287082".to_string(), None, Some(89)).unwrap();
+        assert!(result.valid);
+        assert_eq!(result.drift, Some(-1));
+
+        let hotp = config(OtpKind::Hotp, key, OtpAlgorithm::Sha1, 6);
+        let result = validate_otp(hotp.clone(), "287082".to_string(), None, None).unwrap();
+        assert_eq!((result.counter, result.next_counter), (Some(1), Some(2)));
+        assert!(!validate_otp(hotp, "359152".to_string(), None, None).unwrap().valid);
+    }
+
+    #[test]
+    fn hotp_counter_at_max_has_no_next() {
+        let config = OtpConfig {
+            counter: u64::MAX,
+            ..config(OtpKind::Hotp, b"12345678901234567890", OtpAlgorithm::Sha1, 6)
+        };
+        let code = generate_otp(config.clone(), None).unwrap().code;
+        let result = validate_otp(config, code, None, None).unwrap();
+        assert!(result.valid);
+        assert_eq!(result.next_counter, None);
+    }
+
+    #[test]
+    fn uri_round_trip() {
+        let original = OtpConfig {
+            issuer: Some("Example Co".to_string()),
+            account: Some("alice@example.com".to_string()),
+            period: 60,
+            ..config(OtpKind::Totp, b"12345678901234567890", OtpAlgorithm::Sha256, 8)
+        };
+        let parsed = parse_uri(&build_otp_uri(original.clone()).unwrap()).unwrap();
+        assert_eq!(parsed.kind, original.kind);
+        assert_eq!(parsed.secret, original.secret);
+        assert_eq!(parsed.algorithm, original.algorithm);
+        assert_eq!((parsed.digits, parsed.period), (8, 60));
+        assert_eq!(parsed.issuer, original.issuer);
+        assert_eq!(parsed.account, original.account);
+
+        let hotp = OtpConfig {
+            counter: 42,
+            ..config(OtpKind::Hotp, b"12345678901234567890", OtpAlgorithm::Sha1, 6)
+        };
+        let parsed = parse_uri(&build_otp_uri(hotp).unwrap()).unwrap();
+        assert_eq!((parsed.kind, parsed.counter), (OtpKind::Hotp, 42));
+        assert_eq!((parsed.issuer, parsed.account), (None, None));
+    }
+}
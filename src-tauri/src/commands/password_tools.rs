@@ -0,0 +1,1155 @@
+// 密码生成与强度检测
+//
+// 生成器只用操作系统随机源（OsRng），三种模式：
+//
+//   random          按字符类随机，可排除易混淆字符，可要求每类至少出现一次
+//   pronounceable   辅音 + 元音交替的音节，便于记忆和口述
+//   passphrase      从内置词表（BIP-39 英文 2048 词，每词 11 位）抽词
+//
+// 强度检测参照 zxcvbn 的思路：先找出密码里所有可被猜到的片段（常见密码、
+// 单词、l33t 替换、键盘路径、重复、序列、日期），再用动态规划找出猜测次数
+// 最少的组合，由此得出评分、熵和四种攻击场景下的破解时间，并按最主要的
+// 弱点给出提示。
+
+use chrono::Datelike;
+use rand::rngs::OsRng;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use super::password_words::{COMMON_PASSWORDS, WORDS};
+
+// ==================== 生成 ====================
+
+const LOWERCASE: &str = "abcdefghijklmnopqrstuvwxyz";
+const UPPERCASE: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+const DIGITS: &str = "0123456789";
+const SYMBOLS: &str = "!@#$%^&*()-_=+[]{};:,.<>?/~";
+/// 容易看错或抄错的字符
+const AMBIGUOUS: &str = "0Oo1lIi|`'\"5S2Z8B";
+const CONSONANTS: &str = "bcdfghjklmnprstvwz";
+const VOWELS: &str = "aeiou";
+
+const DEFAULT_LENGTH: usize = 16;
+const MAX_LENGTH: usize = 1024;
+const DEFAULT_WORDS: usize = 6;
+const MAX_WORDS: usize = 64;
+const MAX_BULK: usize = 10_000;
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum PasswordMode {
+    #[default]
+    Random,
+    Pronounceable,
+    Passphrase,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CharClass {
+    Lowercase,
+    Uppercase,
+    Digits,
+    Symbols,
+}
+
+const ALL_CLASSES: [CharClass; 4] = [CharClass::Lowercase, CharClass::Uppercase, CharClass::Digits, CharClass::Symbols];
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct PasswordOptions {
+    #[serde(default)]
+    mode: PasswordMode,
+    /// random/pronounceable 的长度，默认 16
+    length: Option<usize>,
+    /// 使用的字符类，默认全部
+    classes: Option<Vec<CharClass>>,
+    /// 必须至少出现一次的字符类，默认与 classes 相同
+    required: Option<Vec<CharClass>>,
+    /// 自定义符号集，替换默认符号
+    symbols: Option<String>,
+    #[serde(default)]
+    exclude_ambiguous: bool,
+    /// 额外排除的字符
+    exclude: Option<String>,
+    /// passphrase 的单词数，默认 6
+    words: Option<usize>,
+    /// passphrase 的分隔符，默认 "-"
+    separator: Option<String>,
+    /// passphrase 每个单词首字母大写
+    #[serde(default)]
+    capitalize: bool,
+    /// passphrase 在随机一个单词后追加一位数字
+    #[serde(default)]
+    include_number: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct GeneratedPassword {
+    password: String,
+    /// 生成过程的随机性（位），即攻击者已知生成规则时的熵
+    entropy_bits: f64,
+}
+
+/// 生成一个密码
+#[tauri::command]
+pub fn generate_password(options: Option<PasswordOptions>) -> Result<GeneratedPassword, String> {
+    Ok(Generator::new(options.unwrap_or_default())?.generate())
+}
+
+/// 批量生成密码，最多 10000 个
+#[tauri::command]
+pub fn generate_passwords(options: Option<PasswordOptions>, count: usize) -> Result<Vec<GeneratedPassword>, String> {
+    if count == 0 || count > MAX_BULK {
+        return Err(format!("数量应在 1 到 {} 之间", MAX_BULK));
+    }
+    let generator = Generator::new(options.unwrap_or_default())?;
+    Ok((0..count).map(|_| generator.generate()).collect())
+}
+
+/// 校验过的生成参数，批量生成时只准备一次字符池
+struct Generator {
+    options: PasswordOptions,
+    length: usize,
+    /// (字符类, 过滤后的字符)
+    pools: Vec<(CharClass, Vec<char>)>,
+    required: Vec<CharClass>,
+    excluded: Vec<char>,
+}
+
+impl Generator {
+    fn new(options: PasswordOptions) -> Result<Self, String> {
+        let length = options.length.unwrap_or(DEFAULT_LENGTH);
+        if options.mode != PasswordMode::Passphrase && !(4..=MAX_LENGTH).contains(&length) {
+            return Err(format!("长度应在 4 到 {} 之间", MAX_LENGTH));
+        }
+        let classes = options.classes.clone().unwrap_or_else(|| ALL_CLASSES.to_vec());
+        let excluded: Vec<char> = options
+            .exclude
+            .iter()
+            .flat_map(|s| s.chars())
+            .chain(options.exclude_ambiguous.then(|| AMBIGUOUS.chars()).into_iter().flatten())
+            .collect();
+        let pools: Vec<(CharClass, Vec<char>)> = ALL_CLASSES
+            .iter()
+            .filter(|class| classes.contains(class))
+            .map(|&class| {
+                let source = match class {
+                    CharClass::Lowercase => LOWERCASE,
+                    CharClass::Uppercase => UPPERCASE,
+                    CharClass::Digits => DIGITS,
+                    CharClass::Symbols => options.symbols.as_deref().unwrap_or(SYMBOLS),
+                };
+                let mut chars: Vec<char> = source.chars().filter(|c| !excluded.contains(c)).collect();
+                // 自定义符号可能有不相邻的重复，重复字符会抬高其概率并虚增熵
+                chars.sort_unstable();
+                chars.dedup();
+                (class, chars)
+            })
+            .filter(|(_, chars)| !chars.is_empty())
+            .collect();
+        if options.mode == PasswordMode::Random && pools.is_empty() {
+            return Err("至少需要一种可用的字符类".to_string());
+        }
+
+        let required: Vec<CharClass> = options
+            .required
+            .clone()
+            .unwrap_or(classes)
+            .into_iter()
+            .filter(|class| pools.iter().any(|(c, _)| c == class))
+            .collect();
+        if options.mode == PasswordMode::Random && required.len() > length {
+            return Err("长度小于必须包含的字符类数量".to_string());
+        }
+        if options.mode == PasswordMode::Pronounceable {
+            let usable = |source: &str| source.chars().any(|c| !excluded.contains(&c));
+            if !usable(CONSONANTS) || !usable(VOWELS) {
+                return Err("排除的字符过多，无法生成可读密码".to_string());
+            }
+        }
+        if options.mode == PasswordMode::Passphrase {
+            let words = options.words.unwrap_or(DEFAULT_WORDS);
+            if !(1..=MAX_WORDS).contains(&words) {
+                return Err(format!("单词数应在 1 到 {} 之间", MAX_WORDS));
+            }
+        }
+        Ok(Generator {
+            options,
+            length,
+            pools,
+            required,
+            excluded,
+        })
+    }
+
+    fn pool(&self, class: CharClass) -> Option<&[char]> {
+        self.pools.iter().find(|(c, _)| *c == class).map(|(_, chars)| chars.as_slice())
+    }
+
+    fn generate(&self) -> GeneratedPassword {
+        let mut picker = Picker::default();
+        let password = match self.options.mode {
+            PasswordMode::Random => self.random(&mut picker),
+            PasswordMode::Pronounceable => self.pronounceable(&mut picker),
+            PasswordMode::Passphrase => self.passphrase(&mut picker),
+        };
+        GeneratedPassword {
+            password,
+            entropy_bits: round2(picker.bits),
+        }
+    }
+
+    /// 每个必需类先各取一个字符，其余从全部字符中取，最后整体打乱
+    fn random(&self, picker: &mut Picker) -> String {
+        let all: Vec<char> = self.pools.iter().flat_map(|(_, chars)| chars.iter().copied()).collect();
+        let mut chars: Vec<char> = self
+            .required
+            .iter()
+            .filter_map(|&class| self.pool(class))
+            .map(|pool| *picker.pick(pool))
+            .collect();
+        while chars.len() < self.length {
+            chars.push(*picker.pick(&all));
+        }
+        chars.shuffle(&mut OsRng);
+        // 必需字符的位置由打乱决定，熵按整体字符池估计
+        picker.bits = self.length as f64 * (all.len() as f64).log2();
+        chars.into_iter().collect()
+    }
+
+    /// 辅音-元音音节拼接；启用的大写、数字、符号各出现一次
+    fn pronounceable(&self, picker: &mut Picker) -> String {
+        let enabled = |class| self.pools.iter().any(|(c, _)| *c == class);
+        let letters = |source: &str| -> Vec<char> {
+            source
+                .chars()
+                .filter(|c| !self.excluded.contains(c))
+                .collect()
+        };
+        let consonants = letters(CONSONANTS);
+        let vowels = letters(VOWELS);
+        let extras: Vec<CharClass> = [CharClass::Digits, CharClass::Symbols]
+            .into_iter()
+            .filter(|&class| enabled(class))
+            .collect();
+        let letter_count = self.length.saturating_sub(extras.len()).max(1);
+
+        let mut chars: Vec<char> = Vec::with_capacity(self.length);
+        while chars.len() < letter_count {
+            chars.push(*picker.pick(&consonants));
+            if chars.len() < letter_count {
+                chars.push(*picker.pick(&vowels));
+            }
+        }
+        if enabled(CharClass::Uppercase) {
+            // 大写后的字母同样要避开排除列表，如排除易混淆字符时的 I、O
+            let candidates: Vec<usize> = (0..chars.len())
+                .filter(|&i| !self.excluded.contains(&chars[i].to_ascii_uppercase()))
+                .collect();
+            if !candidates.is_empty() {
+                let position = *picker.pick(&candidates);
+                chars[position] = chars[position].to_ascii_uppercase();
+            }
+        }
+        for class in extras {
+            if let Some(pool) = self.pool(class) {
+                let c = *picker.pick(pool);
+                let position = picker.index(chars.len() + 1);
+                chars.insert(position, c);
+            }
+        }
+        chars.into_iter().collect()
+    }
+
+    fn passphrase(&self, picker: &mut Picker) -> String {
+        let count = self.options.words.unwrap_or(DEFAULT_WORDS);
+        let mut words: Vec<String> = (0..count)
+            .map(|_| {
+                let word = *picker.pick(&WORDS);
+                if self.options.capitalize {
+                    capitalize(word)
+                } else {
+                    word.to_string()
+                }
+            })
+            .collect();
+        if self.options.include_number {
+            let position = picker.index(words.len());
+            let digit = picker.index(10);
+            words[position].push_str(&digit.to_string());
+        }
+        words.join(self.options.separator.as_deref().unwrap_or("-"))
+    }
+}
+
+/// 随机选取并累计熵
+#[derive(Default)]
+struct Picker {
+    bits: f64,
+}
+
+impl Picker {
+    fn index(&mut self, len: usize) -> usize {
+        self.bits += (len as f64).log2();
+        OsRng.gen_range(0..len)
+    }
+
+    fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.index(items.len())]
+    }
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+fn round2(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+// ==================== 强度检测 ====================
+
+/// 只分析前 100 个字符，更长的部分不再计入（与 zxcvbn 一致，100 个字符已远超任何攻击能力）
+const MAX_ANALYZED: usize = 100;
+const BRUTEFORCE_CARDINALITY: f64 = 10.0;
+const MIN_GUESSES_SINGLE_CHAR: f64 = 10.0;
+const MIN_GUESSES_MULTI_CHAR: f64 = 50.0;
+/// 片段数增加时的额外代价，对应 zxcvbn 的 MIN_GUESSES_BEFORE_GROWING_SEQUENCE
+const SEQUENCE_GROWTH_LOG10: f64 = 4.0;
+const MIN_YEAR_SPACE: f64 = 20.0;
+/// 英文单词在字典攻击中的大致排名：常见密码之后，逐个尝试整张词表
+const WORD_RANK: f64 = COMMON_PASSWORDS.len() as f64 + WORDS.len() as f64;
+
+const KEYBOARD_ROWS: [&str; 4] = ["1234567890-=", "qwertyuiop[]", "asdfghjkl;'", "zxcvbnm,./"];
+const KEYBOARD_SHIFTED: [&str; 4] = ["!@#$%^&*()_+", "QWERTYUIOP{}", "ASDFGHJKL:\"", "ZXCVBNM<>?"];
+/// 键盘上的起始键数与平均相邻键数，用于估算键盘路径的猜测次数
+const KEYBOARD_STARTS: f64 = 94.0;
+const KEYBOARD_DEGREE: f64 = 4.6;
+
+const L33T_TABLE: &[(char, &[char])] = &[
+    ('4', &['a']),
+    ('@', &['a']),
+    ('8', &['b']),
+    ('(', &['c']),
+    ('3', &['e']),
+    ('6', &['g']),
+    ('9', &['g']),
+    ('1', &['i', 'l']),
+    ('!', &['i']),
+    ('|', &['i', 'l']),
+    ('0', &['o']),
+    ('$', &['s']),
+    ('5', &['s']),
+    ('7', &['t']),
+    ('+', &['t']),
+    ('2', &['z']),
+];
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MatchPattern {
+    CommonPassword,
+    Dictionary,
+    UserInput,
+    Spatial,
+    Repeat,
+    Sequence,
+    Date,
+    Bruteforce,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StrengthMatch {
+    pattern: MatchPattern,
+    token: String,
+    /// 字符下标，含首尾
+    start: usize,
+    end: usize,
+    guesses_log10: f64,
+    /// 如“第 3 常见的密码”“倒写”“l33t 替换”
+    detail: Option<String>,
+    #[serde(skip)]
+    reversed: bool,
+    #[serde(skip)]
+    l33t: bool,
+    #[serde(skip)]
+    rank: usize,
+    #[serde(skip)]
+    turns: usize,
+    #[serde(skip)]
+    base_len: usize,
+}
+
+impl StrengthMatch {
+    fn new(pattern: MatchPattern, chars: &[char], start: usize, end: usize, guesses: f64) -> Self {
+        let len = end - start + 1;
+        let floor = if len == 1 {
+            MIN_GUESSES_SINGLE_CHAR
+        } else {
+            MIN_GUESSES_MULTI_CHAR
+        };
+        let guesses = if pattern == MatchPattern::Bruteforce {
+            guesses
+        } else {
+            guesses.max(floor)
+        };
+        StrengthMatch {
+            pattern,
+            token: chars[start..=end].iter().collect(),
+            start,
+            end,
+            guesses_log10: guesses.log10(),
+            detail: None,
+            reversed: false,
+            l33t: false,
+            rank: 0,
+            turns: 0,
+            base_len: 0,
+        }
+    }
+
+    fn with_detail(mut self, detail: String) -> Self {
+        self.detail = Some(detail);
+        self
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct CrackTime {
+    seconds: f64,
+    display: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CrackTimes {
+    /// 有限速的在线攻击，每小时 100 次
+    online_throttling: CrackTime,
+    /// 无限速的在线攻击，每秒 10 次
+    online_no_throttling: CrackTime,
+    /// 慢哈希（bcrypt、Argon2 等）离线破解，每秒 1 万次
+    offline_slow_hashing: CrackTime,
+    /// 快哈希（MD5、SHA-1 等）离线破解，每秒 100 亿次
+    offline_fast_hashing: CrackTime,
+}
+
+#[derive(Debug, Serialize)]
+pub struct StrengthFeedback {
+    warning: Option<String>,
+    suggestions: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PasswordStrength {
+    /// 0–4，对应 太弱/弱/一般/强/很强
+    score: u8,
+    label: String,
+    length: usize,
+    guesses_log10: f64,
+    /// 按最优猜测组合估算的熵（位）
+    entropy_bits: f64,
+    /// 按字符集大小 × 长度计算的理论熵，不考虑任何规律
+    charset_entropy_bits: f64,
+    crack_times: CrackTimes,
+    feedback: StrengthFeedback,
+    sequence: Vec<StrengthMatch>,
+}
+
+/// 估算密码强度。user_inputs 是用户名、邮箱、姓名等个人信息，出现在密码中会被视为极易猜到
+#[tauri::command]
+pub fn password_strength(password: String, user_inputs: Option<Vec<String>>) -> PasswordStrength {
+    let all: Vec<char> = password.chars().collect();
+    let chars = &all[..all.len().min(MAX_ANALYZED)];
+    let user_inputs: Vec<Vec<char>> = user_inputs
+        .unwrap_or_default()
+        .iter()
+        .map(|s| s.trim().to_lowercase())
+        .filter(|s| s.chars().count() >= 3)
+        .map(|s| s.chars().collect())
+        .collect();
+
+    let matches = omnimatch(chars, &user_inputs);
+    let (guesses_log10, sequence) = most_guessable(chars, &matches);
+
+    let score = match guesses_log10 {
+        g if g < 3.0 => 0,
+        g if g < 6.0 => 1,
+        g if g < 8.0 => 2,
+        g if g < 10.0 => 3,
+        _ => 4,
+    };
+    let crack = |per_second: f64| {
+        let seconds = 10f64.powf(guesses_log10) / per_second;
+        CrackTime {
+            seconds,
+            display: display_duration(seconds),
+        }
+    };
+    PasswordStrength {
+        score,
+        label: ["太弱", "弱", "一般", "强", "很强"][score as usize].to_string(),
+        length: all.len(),
+        guesses_log10: round2(guesses_log10),
+        entropy_bits: round2(guesses_log10 * 10f64.log2()),
+        charset_entropy_bits: round2(all.len() as f64 * (charset_size(&all) as f64).max(1.0).log2()),
+        crack_times: CrackTimes {
+            online_throttling: crack(100.0 / 3600.0),
+            online_no_throttling: crack(10.0),
+            offline_slow_hashing: crack(1e4),
+            offline_fast_hashing: crack(1e10),
+        },
+        feedback: feedback(score, &sequence),
+        sequence,
+    }
+}
+
+fn charset_size(chars: &[char]) -> usize {
+    let has = |f: fn(&char) -> bool| chars.iter().any(f);
+    let mut size = 0;
+    if has(char::is_ascii_lowercase) {
+        size += 26;
+    }
+    if has(char::is_ascii_uppercase) {
+        size += 26;
+    }
+    if has(char::is_ascii_digit) {
+        size += 10;
+    }
+    if has(char::is_ascii_punctuation) || has(|c| *c == ' ') {
+        size += 33;
+    }
+    if has(|c| !c.is_ascii()) {
+        size += 100;
+    }
+    size
+}
+
+fn display_duration(seconds: f64) -> String {
+    const MINUTE: f64 = 60.0;
+    const HOUR: f64 = MINUTE * 60.0;
+    const DAY: f64 = HOUR * 24.0;
+    const MONTH: f64 = DAY * 31.0;
+    const YEAR: f64 = MONTH * 12.0;
+    const CENTURY: f64 = YEAR * 100.0;
+    let (value, unit) = match seconds {
+        s if s < 1.0 => return "不到 1 秒".to_string(),
+        s if s < MINUTE => (s, "秒"),
+        s if s < HOUR => (s / MINUTE, "分钟"),
+        s if s < DAY => (s / HOUR, "小时"),
+        s if s < MONTH => (s / DAY, "天"),
+        s if s < YEAR => (s / MONTH, "个月"),
+        s if s < CENTURY => (s / YEAR, "年"),
+        _ => return "几个世纪以上".to_string(),
+    };
+    format!("{} {}", value.round(), unit)
+}
+
+// -- 匹配 --
+
+fn omnimatch(chars: &[char], user_inputs: &[Vec<char>]) -> Vec<StrengthMatch> {
+    let mut matches = Vec::new();
+    dictionary_matches(chars, user_inputs, &mut matches);
+    spatial_matches(chars, &mut matches);
+    repeat_matches(chars, user_inputs, &mut matches);
+    sequence_matches(chars, &mut matches);
+    date_matches(chars, &mut matches);
+    matches
+}
+
+/// 在常见密码、词表和个人信息中查找，包括倒写和 l33t 替换的形式
+fn dictionary_matches(chars: &[char], user_inputs: &[Vec<char>], out: &mut Vec<StrengthMatch>) {
+    let lower: Vec<char> = chars.iter().flat_map(|c| c.to_lowercase()).collect();
+    if lower.len() != chars.len() {
+        return;
+    }
+    let reversed: Vec<char> = lower.iter().rev().copied().collect();
+    let n = chars.len();
+
+    for i in 0..n {
+        for j in i + 2..n {
+            let token = &lower[i..=j];
+            for (pattern, rank) in lookup(token, user_inputs) {
+                out.push(dictionary_match(chars, i, j, pattern, rank, false, 1.0));
+            }
+            // 倒写：在反转串中的位置换算回原串
+            let (ri, rj) = (n - 1 - j, n - 1 - i);
+            for (pattern, rank) in lookup(&reversed[ri..=rj], user_inputs) {
+                if token.iter().eq(token.iter().rev()) {
+                    continue;
+                }
+                out.push(dictionary_match(chars, i, j, pattern, rank, true, 1.0));
+            }
+            // l33t：只处理含替换字符的片段
+            if token.iter().any(|c| l33t_letters(*c).is_some()) {
+                for (candidate, substitutions) in unl33t(token) {
+                    for (pattern, rank) in lookup(&candidate, user_inputs) {
+                        let variations = 2f64.powi(substitutions as i32);
+                        let mut m = dictionary_match(chars, i, j, pattern, rank, false, variations);
+                        m.l33t = true;
+                        m.detail = Some(format!("{}，l33t 替换", m.detail.unwrap_or_default()));
+                        out.push(m);
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn lookup(token: &[char], user_inputs: &[Vec<char>]) -> Vec<(MatchPattern, usize)> {
+    let word: String = token.iter().collect();
+    let mut found = Vec::new();
+    if let Some(index) = user_inputs.iter().position(|input| input.as_slice() == token) {
+        found.push((MatchPattern::UserInput, index + 1));
+    }
+    if let Some(index) = COMMON_PASSWORDS.iter().position(|p| *p == word) {
+        found.push((MatchPattern::CommonPassword, index + 1));
+    }
+    if token.len() >= 3 && WORDS.binary_search(&word.as_str()).is_ok() {
+        found.push((MatchPattern::Dictionary, 0));
+    }
+    found
+}
+
+fn dictionary_match(
+    chars: &[char],
+    i: usize,
+    j: usize,
+    pattern: MatchPattern,
+    rank: usize,
+    reversed: bool,
+    l33t_variations: f64,
+) -> StrengthMatch {
+    let base = match pattern {
+        MatchPattern::Dictionary => WORD_RANK,
+        _ => rank as f64,
+    };
+    let guesses = base * uppercase_variations(&chars[i..=j]) * l33t_variations * if reversed { 2.0 } else { 1.0 };
+    let mut detail = match pattern {
+        MatchPattern::CommonPassword => format!("第 {} 常见的密码", rank),
+        MatchPattern::UserInput => "个人信息".to_string(),
+        _ => "英文单词".to_string(),
+    };
+    if reversed {
+        detail.push_str("，倒写");
+    }
+    let mut m = StrengthMatch::new(pattern, chars, i, j, guesses).with_detail(detail);
+    m.reversed = reversed;
+    m.rank = rank;
+    m
+}
+
+/// 大小写变化带来的额外猜测倍数
+fn uppercase_variations(token: &[char]) -> f64 {
+    let upper = token.iter().filter(|c| c.is_uppercase()).count();
+    let lower = token.iter().filter(|c| c.is_lowercase()).count();
+    if upper == 0 {
+        return 1.0;
+    }
+    let first_only = token[0].is_uppercase() && upper == 1;
+    let last_only = token[token.len() - 1].is_uppercase() && upper == 1;
+    if first_only || last_only || lower == 0 {
+        return 2.0;
+    }
+    (1..=upper.min(lower)).map(|k| binomial(upper + lower, k)).sum()
+}
+
+fn binomial(n: usize, k: usize) -> f64 {
+    (0..k).fold(1.0, |acc, i| acc * (n - i) as f64 / (i + 1) as f64)
+}
+
+fn l33t_letters(c: char) -> Option<&'static [char]> {
+    L33T_TABLE.iter().find(|(sub, _)| *sub == c).map(|(_, letters)| *letters)
+}
+
+/// 还原 l33t 替换，返回 (候选, 替换数)。一字多义（如 1 → i/l）展开，最多 16 个候选
+fn unl33t(token: &[char]) -> Vec<(Vec<char>, usize)> {
+    let mut candidates: Vec<(Vec<char>, usize)> = vec![(Vec::with_capacity(token.len()), 0)];
+    for &c in token {
+        match l33t_letters(c) {
+            Some(letters) => {
+                let mut next = Vec::new();
+                for (prefix, count) in &candidates {
+                    for &letter in letters {
+                        let mut candidate = prefix.clone();
+                        candidate.push(letter);
+                        next.push((candidate, count + 1));
+                    }
+                }
+                next.truncate(16);
+                candidates = next;
+            }
+            None => candidates.iter_mut().for_each(|(prefix, _)| prefix.push(c)),
+        }
+    }
+    candidates
+}
+
+fn key_position(c: char) -> Option<(usize, usize, bool)> {
+    for (shifted, rows) in [(false, &KEYBOARD_ROWS), (true, &KEYBOARD_SHIFTED)] {
+        for (row, keys) in rows.iter().enumerate() {
+            if let Some(col) = keys.chars().position(|k| k == c) {
+                return Some((row, col, shifted));
+            }
+        }
+    }
+    None
+}
+
+/// 两键相邻时返回方向。每行比上一行右错半个键，(r, c) 的上邻是 (r-1, c)、(r-1, c+1)
+fn key_direction(a: (usize, usize), b: (usize, usize)) -> Option<(i32, i32)> {
+    let (dr, dc) = (b.0 as i32 - a.0 as i32, b.1 as i32 - a.1 as i32);
+    match (dr, dc) {
+        (0, -1) | (0, 1) | (-1, 0) | (-1, 1) | (1, -1) | (1, 0) => Some((dr, dc)),
+        _ => None,
+    }
+}
+
+/// 键盘上相邻按键组成的路径，如 qwerty、1qaz2wsx
+fn spatial_matches(chars: &[char], out: &mut Vec<StrengthMatch>) {
+    let n = chars.len();
+    let mut i = 0;
+    while i + 2 < n {
+        let mut j = i;
+        let mut turns = 0;
+        let mut shifted = 0;
+        let mut last_direction = None;
+        if let Some((_, _, s)) = key_position(chars[i]) {
+            shifted += s as usize;
+            while j + 1 < n {
+                let (Some(a), Some(b)) = (key_position(chars[j]), key_position(chars[j + 1])) else {
+                    break;
+                };
+                let Some(direction) = key_direction((a.0, a.1), (b.0, b.1)) else {
+                    break;
+                };
+                if last_direction != Some(direction) {
+                    turns += 1;
+                    last_direction = Some(direction);
+                }
+                shifted += b.2 as usize;
+                j += 1;
+            }
+        }
+        if j - i + 1 >= 3 {
+            let len = j - i + 1;
+            let mut guesses = 0.0;
+            for l in 2..=len {
+                for t in 1..=turns.min(l - 1) {
+                    guesses += binomial(l - 1, t - 1) * KEYBOARD_STARTS * KEYBOARD_DEGREE.powi(t as i32);
+                }
+            }
+            let unshifted = len - shifted;
+            if shifted > 0 && unshifted > 0 {
+                guesses *= (1..=shifted.min(unshifted)).map(|k| binomial(len, k)).sum::<f64>();
+            } else if shifted > 0 {
+                guesses *= 2.0;
+            }
+            let mut m = StrengthMatch::new(MatchPattern::Spatial, chars, i, j, guesses)
+                .with_detail(format!("键盘路径，{} 次转向", turns.saturating_sub(1)));
+            m.turns = turns;
+            out.push(m);
+            i = j;
+        } else {
+            i += 1;
+        }
+    }
+}
+
+/// 重复的字符或片段，如 aaaa、abcabc
+fn repeat_matches(chars: &[char], user_inputs: &[Vec<char>], out: &mut Vec<StrengthMatch>) {
+    let n = chars.len();
+    let mut i = 0;
+    while i < n {
+        let mut best: Option<(usize, usize)> = None;
+        for base_len in 1..=(n - i) / 2 {
+            let base = &chars[i..i + base_len];
+            let mut count = 1;
+            while i + (count + 1) * base_len <= n && &chars[i + count * base_len..i + (count + 1) * base_len] == base {
+                count += 1;
+            }
+            if count >= 2 && best.is_none_or(|(b, c)| base_len * count > b * c) {
+                best = Some((base_len, count));
+            }
+        }
+        match best {
+            Some((base_len, count)) if base_len * count >= 3 || base_len == 1 && count >= 3 => {
+                let base = &chars[i..i + base_len];
+                let base_guesses = 10f64.powf(most_guessable(base, &omnimatch(base, user_inputs)).0);
+                let end = i + base_len * count - 1;
+                let token: String = base.iter().collect();
+                let mut m = StrengthMatch::new(MatchPattern::Repeat, chars, i, end, base_guesses * count as f64)
+                    .with_detail(format!("“{}”重复 {} 次", token, count));
+                m.base_len = base_len;
+                out.push(m);
+                i = end + 1;
+            }
+            _ => i += 1,
+        }
+    }
+}
+
+/// 等差序列，如 abcdef、13579、9876
+fn sequence_matches(chars: &[char], out: &mut Vec<StrengthMatch>) {
+    let n = chars.len();
+    let class = |c: char| {
+        if c.is_ascii_alphabetic() {
+            Some(26.0)
+        } else if c.is_ascii_digit() {
+            Some(10.0)
+        } else {
+            None
+        }
+    };
+    let mut i = 0;
+    while i + 2 < n {
+        let delta = chars[i + 1] as i32 - chars[i] as i32;
+        let same_class = |a: char, b: char| {
+            a.is_ascii_lowercase() == b.is_ascii_lowercase()
+                && a.is_ascii_uppercase() == b.is_ascii_uppercase()
+                && a.is_ascii_digit() == b.is_ascii_digit()
+        };
+        let Some(space) = class(chars[i]) else {
+            i += 1;
+            continue;
+        };
+        if delta == 0 || delta.abs() > 5 {
+            i += 1;
+            continue;
+        }
+        let mut j = i + 1;
+        while j + 1 < n && chars[j + 1] as i32 - chars[j] as i32 == delta && same_class(chars[j], chars[j + 1]) {
+            j += 1;
+        }
+        if j - i + 1 >= 3 && same_class(chars[i], chars[i + 1]) {
+            let obvious_start = matches!(chars[i], 'a' | 'A' | 'z' | 'Z' | '0' | '1' | '9');
+            let mut base = if obvious_start { 4.0 } else { space };
+            if delta < 0 {
+                base *= 2.0;
+            }
+            let guesses = base * (j - i + 1) as f64 * delta.unsigned_abs() as f64;
+            out.push(
+                StrengthMatch::new(MatchPattern::Sequence, chars, i, j, guesses)
+                    .with_detail(if delta > 0 { "递增序列" } else { "递减序列" }.to_string()),
+            );
+            i = j + 1;
+        } else {
+            i += 1;
+        }
+    }
+}
+
+/// 年份和日期，如 1990、19900101、2024-01-01、01/02/99
+fn date_matches(chars: &[char], out: &mut Vec<StrengthMatch>) {
+    // 离今年越近的年份越容易被猜到
+    let reference_year = chrono::Utc::now().year();
+    let n = chars.len();
+    for i in 0..n {
+        for j in i + 3..(i + 10).min(n) {
+            let token = &chars[i..=j];
+            if !token[0].is_ascii_digit() || !token[token.len() - 1].is_ascii_digit() {
+                continue;
+            }
+            if let Some((year, separated)) = parse_date(token) {
+                let year_space = ((year - reference_year).abs() as f64).max(MIN_YEAR_SPACE);
+                let mut guesses = year_space * 365.0;
+                if separated {
+                    guesses *= 4.0;
+                }
+                out.push(StrengthMatch::new(MatchPattern::Date, chars, i, j, guesses).with_detail("日期".to_string()));
+            } else if token.len() == 4 && token.iter().all(char::is_ascii_digit) {
+                let year: i32 = token.iter().collect::<String>().parse().unwrap_or(0);
+                if (1900..=2099).contains(&year) {
+                    let guesses = ((year - reference_year).abs() as f64).max(MIN_YEAR_SPACE);
+                    let m = StrengthMatch::new(MatchPattern::Date, chars, i, j, guesses);
+                    out.push(m.with_detail("年份".to_string()));
+                }
+            }
+        }
+    }
+}
+
+/// 识别 年月日 / 日月年 / 月日年，允许两位或四位年份，返回 (年份, 是否带分隔符)
+fn parse_date(token: &[char]) -> Option<(i32, bool)> {
+    let text: String = token.iter().collect();
+    let separator = token.iter().find(|c| !c.is_ascii_digit()).copied();
+    let parts: Vec<String> = match separator {
+        Some(sep) => {
+            if !matches!(sep, '-' | '/' | '.' | '_' | ' ') {
+                return None;
+            }
+            let parts: Vec<&str> = text.split(sep).collect();
+            if parts.len() != 3 || parts.iter().any(|p| p.is_empty() || !p.chars().all(|c| c.is_ascii_digit())) {
+                return None;
+            }
+            parts.into_iter().map(str::to_string).collect()
+        }
+        None => {
+            // 无分隔符时只识别 6 位和 8 位
+            match text.len() {
+                8 => {
+                    let candidates = [
+                        [&text[..4], &text[4..6], &text[6..]],
+                        [&text[..2], &text[2..4], &text[4..]],
+                    ];
+                    return candidates.iter().find_map(|p| valid_date(p[0], p[1], p[2])).map(|y| (y, false));
+                }
+                6 => {
+                    let p = [&text[..2], &text[2..4], &text[4..]];
+                    return valid_date(p[0], p[1], p[2]).map(|y| (y, false));
+                }
+                _ => return None,
+            }
+        }
+    };
+    valid_date(&parts[0], &parts[1], &parts[2]).map(|y| (y, true))
+}
+
+/// 按 年月日、日月年、月日年 依次尝试，返回年份
+fn valid_date(a: &str, b: &str, c: &str) -> Option<i32> {
+    let year = |s: &str| -> Option<i32> {
+        let v: i32 = s.parse().ok()?;
+        match s.len() {
+            4 if (1900..=2099).contains(&v) => Some(v),
+            2 => Some(if v > 50 { 1900 + v } else { 2000 + v }),
+            _ => None,
+        }
+    };
+    let day_month = |d: &str, m: &str| -> bool {
+        if d.len() > 2 || m.len() > 2 {
+            return false;
+        }
+        let (Ok(d), Ok(m)) = (d.parse::<u32>(), m.parse::<u32>()) else {
+            return false;
+        };
+        (1..=31).contains(&d) && (1..=12).contains(&m)
+    };
+    if a.len() == 4 && day_month(c, b) {
+        return year(a);
+    }
+    if c.len() != 4 && a.len() != 2 {
+        return None;
+    }
+    if day_month(a, b) || day_month(b, a) {
+        return year(c);
+    }
+    None
+}
+
+// -- 最优组合 --
+
+/// 最优划分表中的一格：(片段猜测数乘积的对数, 本段起点, 匹配下标)；下标为 None 表示暴力破解段
+type Segment = Option<(f64, usize, Option<usize>)>;
+
+/// 在所有匹配加暴力破解片段中，找出使总猜测次数最少的划分。
+/// 总数 = k! × Π 片段猜测数 + 10^(4(k-1))，k 为片段数，与 zxcvbn 一致
+fn most_guessable(chars: &[char], matches: &[StrengthMatch]) -> (f64, Vec<StrengthMatch>) {
+    let n = chars.len();
+    if n == 0 {
+        return (0.0, Vec::new());
+    }
+    // best[end][k]：以 end 结尾、共 k 段时的最优划分
+    let mut best: Vec<Vec<Segment>> = vec![vec![None; n + 1]; n];
+    let mut ending: Vec<Vec<usize>> = vec![Vec::new(); n];
+    for (index, m) in matches.iter().enumerate() {
+        ending[m.end].push(index);
+    }
+
+    for end in 0..n {
+        let candidates = ending[end]
+            .iter()
+            .map(|&index| (matches[index].start, matches[index].guesses_log10, Some(index)))
+            .chain((0..=end).map(|start| (start, bruteforce_log10(end - start + 1), None)));
+        for (start, guesses, index) in candidates {
+            if start == 0 {
+                relax(&mut best[end][1], guesses, start, index);
+                continue;
+            }
+            for k in 1..=start {
+                if let Some((product, _, _)) = best[start - 1][k] {
+                    relax(&mut best[end][k + 1], product + guesses, start, index);
+                }
+            }
+        }
+    }
+
+    let total = |k: usize, product: f64| {
+        let a = log10_factorial(k) + product;
+        let b = SEQUENCE_GROWTH_LOG10 * (k - 1) as f64;
+        let (high, low) = if a > b { (a, b) } else { (b, a) };
+        high + (1.0 + 10f64.powf(low - high)).log10()
+    };
+    let (mut k, guesses_log10) = (1..=n)
+        .filter_map(|k| best[n - 1][k].map(|(product, _, _)| (k, total(k, product))))
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .unwrap_or((1, n as f64));
+
+    let mut sequence = Vec::new();
+    let mut end = n - 1;
+    while let Some((_, start, index)) = best[end][k] {
+        let mut m = match index {
+            Some(index) => matches[index].clone(),
+            None => {
+                let guesses = 10f64.powf(bruteforce_log10(end - start + 1));
+                StrengthMatch::new(MatchPattern::Bruteforce, chars, start, end, guesses)
+            }
+        };
+        m.guesses_log10 = round2(m.guesses_log10);
+        sequence.push(m);
+        if start == 0 {
+            break;
+        }
+        end = start - 1;
+        k -= 1;
+    }
+    sequence.reverse();
+    (guesses_log10, sequence)
+}
+
+fn relax(cell: &mut Segment, product: f64, start: usize, index: Option<usize>) {
+    if cell.is_none_or(|(current, _, _)| product < current - 1e-9) {
+        *cell = Some((product, start, index));
+    }
+}
+
+fn bruteforce_log10(len: usize) -> f64 {
+    let floor = if len == 1 {
+        MIN_GUESSES_SINGLE_CHAR
+    } else {
+        MIN_GUESSES_MULTI_CHAR
+    };
+    (len as f64 * BRUTEFORCE_CARDINALITY.log10()).max(floor.log10())
+}
+
+fn log10_factorial(k: usize) -> f64 {
+    (2..=k).map(|i| (i as f64).log10()).sum()
+}
+
+// -- 提示 --
+
+fn feedback(score: u8, sequence: &[StrengthMatch]) -> StrengthFeedback {
+    let defaults = vec![
+        "使用几个不常见的单词组合，比加符号、数字和大写更有效".to_string(),
+        "不必刻意使用符号、数字或大写字母".to_string(),
+    ];
+    if sequence.is_empty() {
+        return StrengthFeedback {
+            warning: None,
+            suggestions: defaults,
+        };
+    }
+    if score > 2 {
+        return StrengthFeedback {
+            warning: None,
+            suggestions: Vec::new(),
+        };
+    }
+    let longest = sequence
+        .iter()
+        .filter(|m| m.pattern != MatchPattern::Bruteforce)
+        .max_by_key(|m| m.token.chars().count());
+    let mut suggestions = vec!["再加一两个单词，越不常见越好".to_string()];
+    let Some(m) = longest else {
+        return StrengthFeedback {
+            warning: None,
+            suggestions,
+        };
+    };
+    let sole = sequence.len() == 1;
+    let warning = match m.pattern {
+        MatchPattern::CommonPassword | MatchPattern::Dictionary | MatchPattern::UserInput => {
+            let token = m.token.as_str();
+            let lower = token.to_lowercase();
+            let first_upper = token.chars().next().is_some_and(char::is_uppercase);
+            if first_upper && token.chars().skip(1).all(|c| !c.is_uppercase()) {
+                suggestions.push("首字母大写对强度帮助不大".to_string());
+            } else if token != lower && token.chars().all(|c| !c.is_lowercase()) {
+                suggestions.push("全部大写和全部小写一样容易猜到".to_string());
+            }
+            if m.reversed {
+                suggestions.push("倒写的单词并不难猜".to_string());
+            }
+            if m.l33t {
+                suggestions.push("用 @ 代替 a 这类替换很容易被预测".to_string());
+            }
+            match m.pattern {
+                MatchPattern::CommonPassword if sole && !m.l33t && !m.reversed => Some(if m.rank <= 10 {
+                    "这是最常用的 10 个密码之一".to_string()
+                } else if m.rank <= 100 {
+                    "这是最常用的 100 个密码之一".to_string()
+                } else {
+                    "这是非常常见的密码".to_string()
+                }),
+                MatchPattern::CommonPassword => Some("与常见密码相似".to_string()),
+                MatchPattern::UserInput => Some("包含了用户名、姓名等个人信息".to_string()),
+                _ if sole => Some("单个单词很容易被猜到".to_string()),
+                _ => None,
+            }
+        }
+        MatchPattern::Spatial => {
+            suggestions.push("使用更长、转折更多的键盘图案".to_string());
+            Some(if m.turns <= 1 {
+                "键盘上一排连续的按键很容易被猜到".to_string()
+            } else {
+                "短的键盘图案很容易被猜到".to_string()
+            })
+        }
+        MatchPattern::Repeat => {
+            suggestions.push("避免重复的单词和字符".to_string());
+            Some(if m.base_len == 1 {
+                "像 aaa 这样的重复字符很容易被猜到".to_string()
+            } else {
+                "像 abcabcabc 这样的重复只比 abc 略难猜".to_string()
+            })
+        }
+        MatchPattern::Sequence => {
+            suggestions.push("避免使用序列".to_string());
+            Some("像 abc 或 6543 这样的序列很容易被猜到".to_string())
+        }
+        MatchPattern::Date => {
+            suggestions.push("避免使用与自己相关的日期和年份".to_string());
+            Some("日期和年份很容易被猜到".to_string())
+        }
+        MatchPattern::Bruteforce => None,
+    };
+    StrengthFeedback { warning, suggestions }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strength(password: &str) -> PasswordStrength {
+        password_strength(password.to_string(), None)
+    }
+
+    fn patterns(result: &PasswordStrength) -> Vec<MatchPattern> {
+        result.sequence.iter().map(|m| m.pattern).collect()
+    }
+
+    #[test]
+    fn estimator_scores() {
+        let cases = [
+            ("password1", 0, MatchPattern::CommonPassword),
+            ("qwerty", 0, MatchPattern::CommonPassword),
+            ("aaaaaa", 0, MatchPattern::CommonPassword),
+            ("1987-06-05", 1, MatchPattern::Date),
+        ];
+        for (password, score, pattern) in cases {
+            let result = strength(password);
+            assert_eq!(result.score, score, "{}", password);
+            assert_eq!(patterns(&result), [pattern], "{}", password);
+        }
+    }
+
+    #[test]
+    fn recent_years_are_cheaper() {
+        let this_year = chrono::Utc::now().year();
+        let recent = strength(&format!("x{}", this_year));
+        let distant = strength(&format!("x{}", this_year - 60));
+        assert!(recent.guesses_log10 < distant.guesses_log10);
+    }
+}
@@ -0,0 +1,307 @@
+// 多音字的词组读音，供 `to_pinyin` 使用
+//
+// pinyin crate 只知道逐字的读音，并把最常见的读音排在第一位，遇到银行、重庆
+// 这样的词就会出错。本表列出字取非默认（或轻声）读音的常用词，由人工整理，
+// 并不完整；单字条目覆盖 jieba 单独切出的助词。按 UTF-8 字节排序以便二分查找。
+
+/// 最长条目的字数
+pub const MAX_PHRASE_CHARS: usize = 4;
+
+pub const PHRASES: [(&str, &str); 296] = [
+    ("一切", "yī qiè"),
+    ("一年半载", "yī nián bàn zǎi"),
+    ("丈量", "zhàng liáng"),
+    ("三更", "sān gēng"),
+    ("上当", "shàng dàng"),
+    ("专长", "zhuān cháng"),
+    ("中奖", "zhòng jiǎng"),
+    ("中毒", "zhòng dú"),
+    ("中间件", "zhōng jiān jiàn"),
+    ("主角", "zhǔ jué"),
+    ("乐器", "yuè qì"),
+    ("乐曲", "yuè qǔ"),
+    ("乐队", "yuè duì"),
+    ("了", "le"),
+    ("了不起", "liǎo bu qǐ"),
+    ("了解", "liǎo jiě"),
+    ("亲家", "qìng jia"),
+    ("人参", "rén shēn"),
+    ("什么", "shén me"),
+    ("仔细", "zǐ xì"),
+    ("以为", "yǐ wéi"),
+    ("休假", "xiū jià"),
+    ("会计", "kuài jì"),
+    ("传记", "zhuàn jì"),
+    ("似的", "shì de"),
+    ("作为", "zuò wéi"),
+    ("作曲", "zuò qǔ"),
+    ("供应", "gōng yìng"),
+    ("供给", "gōng jǐ"),
+    ("便宜", "pián yi"),
+    ("倔强", "jué jiàng"),
+    ("假期", "jià qī"),
+    ("偏差", "piān chā"),
+    ("偿还", "cháng huán"),
+    ("六安", "lù ān"),
+    ("关卡", "guān qiǎ"),
+    ("兴奋", "xīng fèn"),
+    ("兴起", "xīng qǐ"),
+    ("兴趣", "xìng qù"),
+    ("内行", "nèi háng"),
+    ("几乎", "jī hū"),
+    ("出差", "chū chāi"),
+    ("击中", "jī zhòng"),
+    ("分散", "fēn sàn"),
+    ("切割", "qiē gē"),
+    ("切换", "qiē huàn"),
+    ("切片", "qiē piàn"),
+    ("划算", "huá suàn"),
+    ("划船", "huá chuán"),
+    ("动弹", "dòng tan"),
+    ("勉强", "miǎn qiǎng"),
+    ("午觉", "wǔ jiào"),
+    ("协调", "xié tiáo"),
+    ("单于", "chán yú"),
+    ("压轴", "yā zhòu"),
+    ("厌恶", "yàn wù"),
+    ("厦门", "xià mén"),
+    ("参差", "cēn cī"),
+    ("反应", "fǎn yìng"),
+    ("反省", "fǎn xǐng"),
+    ("发卡", "fà qiǎ"),
+    ("变更", "biàn gēng"),
+    ("只有", "zhǐ yǒu"),
+    ("可恶", "kě wù"),
+    ("同行", "tóng háng"),
+    ("命中", "mìng zhòng"),
+    ("咽喉", "yān hóu"),
+    ("响应", "xiǎng yìng"),
+    ("商量", "shāng liang"),
+    ("回应", "huí yìng"),
+    ("困难", "kùn nan"),
+    ("地壳", "dì qiào"),
+    ("地方", "dì fang"),
+    ("坦率", "tǎn shuài"),
+    ("填空", "tián kòng"),
+    ("增长", "zēng zhǎng"),
+    ("处理", "chǔ lǐ"),
+    ("处罚", "chǔ fá"),
+    ("处置", "chǔ zhì"),
+    ("外行", "wài háng"),
+    ("多少", "duō shao"),
+    ("大厦", "dà shà"),
+    ("大夫", "dài fu"),
+    ("头发", "tóu fa"),
+    ("奇偶", "jī ǒu"),
+    ("奇数", "jī shù"),
+    ("好奇", "hào qí"),
+    ("好客", "hào kè"),
+    ("字符", "zì fú"),
+    ("宁可", "nìng kě"),
+    ("宁愿", "nìng yuàn"),
+    ("宝藏", "bǎo zàng"),
+    ("家长", "jiā zhǎng"),
+    ("宿舍", "sù shè"),
+    ("寒假", "hán jià"),
+    ("对应", "duì yìng"),
+    ("尉迟", "yù chí"),
+    ("少女", "shào nǚ"),
+    ("少年", "shào nián"),
+    ("属于", "shǔ yú"),
+    ("差别", "chā bié"),
+    ("差异", "chā yì"),
+    ("差距", "chā jù"),
+    ("市长", "shì zhǎng"),
+    ("干净", "gān jìng"),
+    ("干扰", "gān rǎo"),
+    ("干燥", "gān zào"),
+    ("应用", "yìng yòng"),
+    ("店铺", "diàn pù"),
+    ("延长", "yán cháng"),
+    ("弯曲", "wān qū"),
+    ("弹出", "tán chū"),
+    ("弹性", "tán xìng"),
+    ("弹琴", "tán qín"),
+    ("弹窗", "tán chuāng"),
+    ("归还", "guī huán"),
+    ("当作", "dàng zuò"),
+    ("当成", "dàng chéng"),
+    ("很长", "hěn cháng"),
+    ("得", "de"),
+    ("怎么", "zěn me"),
+    ("恶心", "ě xin"),
+    ("成为", "chéng wéi"),
+    ("成分", "chéng fèn"),
+    ("成长", "chéng zhǎng"),
+    ("投降", "tóu xiáng"),
+    ("挣扎", "zhēng zhá"),
+    ("掉色", "diào shǎi"),
+    ("排行", "pái háng"),
+    ("排行榜", "pái háng bǎng"),
+    ("提防", "dī fang"),
+    ("擅长", "shàn cháng"),
+    ("放假", "fàng jià"),
+    ("教书", "jiāo shū"),
+    ("散文", "sǎn wén"),
+    ("散步", "sàn bù"),
+    ("数据库", "shù jù kù"),
+    ("数数", "shǔ shù"),
+    ("新兴", "xīn xīng"),
+    ("旋转", "xuán zhuàn"),
+    ("晕车", "yùn chē"),
+    ("暑假", "shǔ jià"),
+    ("暖和", "nuǎn huo"),
+    ("曲线", "qū xiàn"),
+    ("更换", "gēng huàn"),
+    ("更改", "gēng gǎi"),
+    ("更新", "gēng xīn"),
+    ("曾孙", "zēng sūn"),
+    ("朝代", "cháo dài"),
+    ("朝气", "zhāo qì"),
+    ("朝阳", "cháo yáng"),
+    ("朝鲜", "cháo xiǎn"),
+    ("松散", "sōng sǎn"),
+    ("校准", "jiào zhǔn"),
+    ("校对", "jiào duì"),
+    ("校长", "xiào zhǎng"),
+    ("校验", "jiào yàn"),
+    ("模具", "mú jù"),
+    ("模板", "mú bǎn"),
+    ("模样", "mú yàng"),
+    ("歌曲", "gē qǔ"),
+    ("正月", "zhēng yuè"),
+    ("沉没", "chén mò"),
+    ("没收", "mò shōu"),
+    ("测量", "cè liáng"),
+    ("海参", "hǎi shēn"),
+    ("涨价", "zhǎng jià"),
+    ("淹没", "yān mò"),
+    ("游说", "yóu shuì"),
+    ("湖泊", "hú pō"),
+    ("漫长", "màn cháng"),
+    ("灾难", "zāi nàn"),
+    ("照相", "zhào xiàng"),
+    ("爱好", "ài hào"),
+    ("特长", "tè cháng"),
+    ("率领", "shuài lǐng"),
+    ("班长", "bān zhǎng"),
+    ("理发", "lǐ fà"),
+    ("生长", "shēng zhǎng"),
+    ("的", "de"),
+    ("的士", "dí shì"),
+    ("的确", "dí què"),
+    ("目的", "mù dì"),
+    ("直率", "zhí shuài"),
+    ("相处", "xiāng chǔ"),
+    ("相机", "xiàng jī"),
+    ("省份", "shěng fèn"),
+    ("真相", "zhēn xiàng"),
+    ("着", "zhe"),
+    ("着急", "zháo jí"),
+    ("着想", "zhuó xiǎng"),
+    ("着手", "zhuó shǒu"),
+    ("着陆", "zhuó lù"),
+    ("睡着", "shuì zháo"),
+    ("睡觉", "shuì jiào"),
+    ("种地", "zhòng dì"),
+    ("种树", "zhòng shù"),
+    ("种植", "zhòng zhí"),
+    ("空格", "kòng gé"),
+    ("空白", "kòng bái"),
+    ("空缺", "kòng quē"),
+    ("空调", "kōng tiáo"),
+    ("空闲", "kòng xián"),
+    ("穿着", "chuān zhuó"),
+    ("答应", "dā ying"),
+    ("系鞋带", "jì xié dài"),
+    ("组长", "zǔ zhǎng"),
+    ("结实", "jiē shi"),
+    ("给予", "jǐ yǔ"),
+    ("背包", "bēi bāo"),
+    ("背负", "bēi fù"),
+    ("自给", "zì jǐ"),
+    ("色子", "shǎi zi"),
+    ("节省", "jié shěng"),
+    ("茶几", "chá jī"),
+    ("薄荷", "bò he"),
+    ("蚌埠", "bèng bù"),
+    ("血液", "xuè yè"),
+    ("行业", "háng yè"),
+    ("行为", "xíng wéi"),
+    ("行列", "háng liè"),
+    ("行情", "háng qíng"),
+    ("行长", "háng zhǎng"),
+    ("西藏", "xī zàng"),
+    ("要么", "yào me"),
+    ("要挟", "yāo xié"),
+    ("要求", "yāo qiú"),
+    ("覆盖", "fù gài"),
+    ("觉得", "jué de"),
+    ("角色", "jué sè"),
+    ("解放", "jiě fàng"),
+    ("解散", "jiě sàn"),
+    ("认为", "rèn wéi"),
+    ("记得", "jì de"),
+    ("记载", "jì zǎi"),
+    ("误差", "wù chā"),
+    ("请假", "qǐng jià"),
+    ("调和", "tiáo hé"),
+    ("调整", "tiáo zhěng"),
+    ("调节", "tiáo jié"),
+    ("调解", "tiáo jiě"),
+    ("调试", "tiáo shì"),
+    ("贝壳", "bèi ké"),
+    ("身分", "shēn fèn"),
+    ("转动", "zhuàn dòng"),
+    ("转载", "zhuǎn zǎi"),
+    ("转速", "zhuàn sù"),
+    ("过分", "guò fèn"),
+    ("还原", "huán yuán"),
+    ("还款", "huán kuǎn"),
+    ("适应", "shì yìng"),
+    ("适当", "shì dàng"),
+    ("遇难", "yù nàn"),
+    ("部长", "bù zhǎng"),
+    ("都市", "dū shì"),
+    ("都江堰", "dū jiāng yàn"),
+    ("配角", "pèi jué"),
+    ("重写", "chóng xiě"),
+    ("重叠", "chóng dié"),
+    ("重名", "chóng míng"),
+    ("重启", "chóng qǐ"),
+    ("重复", "chóng fù"),
+    ("重庆", "chóng qìng"),
+    ("重新", "chóng xīn"),
+    ("重构", "chóng gòu"),
+    ("重点", "zhòng diǎn"),
+    ("重置", "chóng zhì"),
+    ("重要", "zhòng yào"),
+    ("重试", "chóng shì"),
+    ("重载", "chóng zài"),
+    ("重量", "zhòng liàng"),
+    ("银行", "yín háng"),
+    ("银行卡", "yín háng kǎ"),
+    ("长久", "cháng jiǔ"),
+    ("长城", "cháng chéng"),
+    ("长大", "zhǎng dà"),
+    ("长度", "cháng dù"),
+    ("长时间", "cháng shí jiān"),
+    ("长期", "cháng qī"),
+    ("长江", "cháng jiāng"),
+    ("长沙", "cháng shā"),
+    ("长短", "cháng duǎn"),
+    ("长辈", "zhǎng bèi"),
+    ("长远", "cháng yuǎn"),
+    ("长途", "cháng tú"),
+    ("间接", "jiàn jiē"),
+    ("间断", "jiàn duàn"),
+    ("间距", "jiàn jù"),
+    ("间隔", "jiàn gé"),
+    ("降低", "jiàng dī"),
+    ("难民", "nàn mín"),
+    ("音乐", "yīn yuè"),
+    ("饼干", "bǐng gān"),
+    ("首相", "shǒu xiàng"),
+    ("首都", "shǒu dū"),
+    ("高兴", "gāo xìng"),
+];
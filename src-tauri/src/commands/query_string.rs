@@ -0,0 +1,441 @@
+// JSON⇄Query 命令背后的查询字符串 / 表单请求体转换
+//
+// 嵌套 JSON 按 qs（Node）、PHP 与 Rails 的约定展开为 `key=value` 对，也可还原:
+//
+//   嵌套  brackets  a[b][c]=1        dots  a.b.c=1
+//   数组  indices   a[0]=x&a[1]=y    brackets  a[]=x&a[]=y
+//         repeat    a=x&a=y          comma     a=x,y
+//
+// 解析时总能识别方括号和重复键；点号和逗号拆分只在指定时生效，
+// 因为两者在普通的键和值中都是合法字符。
+
+use rand::distributions::Alphanumeric;
+use rand::Rng;
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+/// 解析时大于此值的数组下标按对象键处理，`a[99999999]=1` 不会分配巨大的数组
+const MAX_ARRAY_INDEX: usize = 1000;
+
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum QueryArrayStyle {
+    #[default]
+    Indices,
+    Brackets,
+    Repeat,
+    Comma,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum QueryNestingStyle {
+    #[default]
+    Brackets,
+    Dots,
+}
+
+// ==================== JSON -> 键值对 ====================
+
+/// 把 `value` 展开为解码后的 `(key, value)` 对
+pub fn flatten(value: &Value, arrays: QueryArrayStyle, nesting: QueryNestingStyle) -> Vec<(String, String)> {
+    let mut pairs = Vec::new();
+    flatten_into(value, String::new(), arrays, nesting, &mut pairs);
+    pairs
+}
+
+fn flatten_into(
+    value: &Value,
+    prefix: String,
+    arrays: QueryArrayStyle,
+    nesting: QueryNestingStyle,
+    pairs: &mut Vec<(String, String)>,
+) {
+    match value {
+        Value::Object(map) => {
+            for (k, v) in map {
+                let key = if prefix.is_empty() {
+                    k.clone()
+                } else {
+                    match nesting {
+                        QueryNestingStyle::Brackets => format!("{}[{}]", prefix, k),
+                        QueryNestingStyle::Dots => format!("{}.{}", prefix, k),
+                    }
+                };
+                flatten_into(v, key, arrays, nesting, pairs);
+            }
+        }
+        Value::Array(arr) => {
+            let all_scalars = arr.iter().all(|v| !v.is_object() && !v.is_array());
+            match arrays {
+                QueryArrayStyle::Comma if all_scalars && !arr.is_empty() => {
+                    let joined = arr.iter().map(scalar_text).collect::<Vec<_>>().join(",");
+                    pairs.push((prefix, joined));
+                }
+                QueryArrayStyle::Brackets if all_scalars => {
+                    for v in arr {
+                        flatten_into(v, format!("{}[]", prefix), arrays, nesting, pairs);
+                    }
+                }
+                QueryArrayStyle::Repeat if all_scalars => {
+                    for v in arr {
+                        flatten_into(v, prefix.clone(), arrays, nesting, pairs);
+                    }
+                }
+                // 下标形式，也是其他风格无法表达嵌套值时的回退（`a[][b]=1&a[][c]=2`
+                // 分不清是一个对象还是两个）
+                _ => {
+                    for (i, v) in arr.iter().enumerate() {
+                        flatten_into(v, format!("{}[{}]", prefix, i), arrays, nesting, pairs);
+                    }
+                }
+            }
+        }
+        scalar => pairs.push((prefix, scalar_text(scalar))),
+    }
+}
+
+fn scalar_text(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+/// `k=v&k=v`，使用 RFC 3986 百分号编码（保留非保留字符）
+pub fn encode_query(pairs: &[(String, String)]) -> String {
+    pairs
+        .iter()
+        .map(|(k, v)| format!("{}={}", urlencoding::encode(k), urlencoding::encode(v)))
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+/// `application/x-www-form-urlencoded` 请求体: 与查询字符串相同，但空格编码为 `+`
+pub fn encode_form(pairs: &[(String, String)]) -> String {
+    pairs
+        .iter()
+        .map(|(k, v)| format!("{}={}", form_encode(k), form_encode(v)))
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+fn form_encode(s: &str) -> String {
+    urlencoding::encode(s).replace("%20", "+")
+}
+
+/// 使用随机分隔符的 `multipart/form-data` 请求体，返回 `(content_type, body)`
+pub fn encode_multipart(pairs: &[(String, String)]) -> (String, String) {
+    let token: String = rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(16)
+        .map(char::from)
+        .collect();
+    let boundary = format!("----TBoxFormBoundary{}", token);
+
+    let mut body = String::new();
+    for (k, v) in pairs {
+        // HTML 的 multipart 编码会转义字段名中的这些字符
+        let name = k.replace('"', "%22").replace('\r', "%0D").replace('\n', "%0A");
+        body.push_str(&format!(
+            "--{}\r\nContent-Disposition: form-data; name=\"{}\"\r\n\r\n{}\r\n",
+            boundary, name, v
+        ));
+    }
+    body.push_str(&format!("--{}--\r\n", boundary));
+    (format!("multipart/form-data; boundary={}", boundary), body)
+}
+
+// ==================== 查询字符串 -> JSON ====================
+
+/// 把查询字符串（也可以是完整 URL 或以 `?` 开头）拆分为解码后的键值对，
+/// `plus_as_space` 时按表单规则解码
+pub fn parse_pairs(input: &str, plus_as_space: bool) -> Vec<(String, String)> {
+    let input = input.trim();
+    let query = match input.find('?') {
+        Some(0) => &input[1..],
+        // `redirect=/a?b=1` 本身就是查询字符串；只有 URL 中的 `?` 才是查询的开始
+        Some(i) if looks_like_url(input) && !input[..i].contains(['=', '&']) => &input[i + 1..],
+        _ => input,
+    };
+    let query = query.split('#').next().unwrap_or("");
+
+    query
+        .split('&')
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let (k, v) = part.split_once('=').unwrap_or((part, ""));
+            (decode_component(k, plus_as_space), decode_component(v, plus_as_space))
+        })
+        .collect()
+}
+
+/// 绝对 URL（`scheme:`）或以 `/` 开头的路径
+fn looks_like_url(input: &str) -> bool {
+    if input.starts_with('/') {
+        return true;
+    }
+    match input.split_once(':') {
+        Some((scheme, _)) => {
+            scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+                && scheme.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+        }
+        None => false,
+    }
+}
+
+fn decode_component(s: &str, plus_as_space: bool) -> String {
+    let s = if plus_as_space {
+        s.replace('+', " ")
+    } else {
+        s.to_string()
+    };
+    String::from_utf8_lossy(&urlencoding::decode_binary(s.as_bytes())).into_owned()
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    Key(String),
+    Index(usize),
+    Push,
+}
+
+fn parse_key(key: &str, nesting: QueryNestingStyle) -> Vec<Segment> {
+    let dots = nesting == QueryNestingStyle::Dots;
+    let mut segments = Vec::new();
+    let mut current = String::new();
+    let mut rest = key;
+
+    // 根: 第一个 `[`（点号模式下还有 `.`）之前的全部内容。以 `[` 开头的键按字面处理。
+    while let Some(c) = rest.chars().next() {
+        if (c == '[' && !current.is_empty() && rest.contains(']')) || (dots && c == '.' && !current.is_empty()) {
+            break;
+        }
+        current.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+    segments.push(Segment::Key(current));
+
+    while let Some(c) = rest.chars().next() {
+        if c == '[' {
+            let Some(end) = rest.find(']') else {
+                // 方括号未闭合: 剩余部分作为字面键
+                segments.push(Segment::Key(rest.to_string()));
+                break;
+            };
+            let inner = &rest[1..end];
+            segments.push(bracket_segment(inner));
+            rest = &rest[end + 1..];
+        } else if dots && c == '.' {
+            rest = &rest[1..];
+            let end = rest.find(['.', '[']).unwrap_or(rest.len());
+            segments.push(Segment::Key(rest[..end].to_string()));
+            rest = &rest[end..];
+        } else {
+            // `]` 之后的多余内容，如 `a[b]c`
+            segments.push(Segment::Key(rest.to_string()));
+            break;
+        }
+    }
+    segments
+}
+
+fn bracket_segment(inner: &str) -> Segment {
+    if inner.is_empty() {
+        return Segment::Push;
+    }
+    match inner.parse::<usize>() {
+        Ok(i) if i <= MAX_ARRAY_INDEX && !(inner.len() > 1 && inner.starts_with('0')) => Segment::Index(i),
+        _ => Segment::Key(inner.to_string()),
+    }
+}
+
+/// 解析过程中构建的有序树，最后再转换为 JSON
+#[derive(Debug)]
+enum Node {
+    Leaf(String),
+    Object(Vec<(String, Node)>),
+    Array(Vec<Node>),
+}
+
+impl Node {
+    fn container_for(segment: &Segment) -> Node {
+        match segment {
+            Segment::Key(_) => Node::Object(Vec::new()),
+            _ => Node::Array(Vec::new()),
+        }
+    }
+
+    /// 把数组转换为以下标为键的对象，以便加入字符串键（`a[0]=x&a[b]=y`）
+    fn make_object(&mut self) {
+        if let Node::Array(items) = self {
+            let members = std::mem::take(items)
+                .into_iter()
+                .enumerate()
+                .map(|(i, n)| (i.to_string(), n))
+                .collect();
+            *self = Node::Object(members);
+        }
+    }
+
+    fn insert(&mut self, segments: &[Segment], value: Node) {
+        let (first, rest) = segments.split_first().expect("segments are never empty");
+        let slot: &mut Node = match first {
+            Segment::Key(key) => {
+                self.make_object();
+                let Node::Object(members) = self else {
+                    unreachable!("make_object leaves an object");
+                };
+                match members.iter().position(|(k, _)| k == key) {
+                    Some(i) => &mut members[i].1,
+                    None if rest.is_empty() => {
+                        members.push((key.clone(), value));
+                        return;
+                    }
+                    None => {
+                        members.push((key.clone(), Node::container_for(&rest[0])));
+                        &mut members.last_mut().expect("just pushed").1
+                    }
+                }
+            }
+            Segment::Push | Segment::Index(_) => {
+                let index = match (first, &*self) {
+                    (Segment::Index(i), Node::Array(items)) if *i < items.len() => Some(*i),
+                    _ => None,
+                };
+                match self {
+                    Node::Object(members) => {
+                        // 已经是对象: 继续把编号作为键
+                        let key = match first {
+                            Segment::Index(i) => i.to_string(),
+                            _ => members.len().to_string(),
+                        };
+                        let mut path = vec![Segment::Key(key)];
+                        path.extend_from_slice(rest);
+                        return self.insert(&path, value);
+                    }
+                    Node::Leaf(_) => unreachable!("leaves are never descended into"),
+                    Node::Array(items) => match index {
+                        Some(i) => &mut items[i],
+                        None if rest.is_empty() => {
+                            // 与 qs 一样压缩稀疏下标
+                            items.push(value);
+                            return;
+                        }
+                        None => {
+                            items.push(Node::container_for(&rest[0]));
+                            items.last_mut().expect("just pushed")
+                        }
+                    },
+                }
+            }
+        };
+
+        if rest.is_empty() {
+            // 重复的键: 把值收集为数组
+            match slot {
+                Node::Array(items) => items.push(value),
+                Node::Leaf(_) => {
+                    let old = std::mem::replace(slot, Node::Array(Vec::new()));
+                    *slot = Node::Array(vec![old, value]);
+                }
+                Node::Object(members) => {
+                    let key = members.len().to_string();
+                    members.push((key, value));
+                }
+            }
+            return;
+        }
+        if let Node::Leaf(_) = slot {
+            // `a=1&a[b]=2`: 保留标量，同时加入新的子节点
+            let old = std::mem::replace(slot, Node::Array(Vec::new()));
+            *slot = Node::Array(vec![old]);
+        }
+        slot.insert(rest, value);
+    }
+
+    fn into_value(self, infer_types: bool) -> Value {
+        match self {
+            Node::Leaf(s) if infer_types => infer(&s),
+            Node::Leaf(s) => Value::String(s),
+            Node::Array(items) => Value::Array(items.into_iter().map(|n| n.into_value(infer_types)).collect()),
+            Node::Object(members) => {
+                let mut map = Map::new();
+                for (k, v) in members {
+                    map.insert(k, v.into_value(infer_types));
+                }
+                Value::Object(map)
+            }
+        }
+    }
+}
+
+/// `"42"` -> 42，`"true"` -> true，`"null"` -> null，其余保持字符串。
+/// 数字只有在原样打印回来时才转换，`007` 这样的编号得以保留。
+fn infer(s: &str) -> Value {
+    match s {
+        "true" => return Value::Bool(true),
+        "false" => return Value::Bool(false),
+        "null" => return Value::Null,
+        _ => {}
+    }
+    if let Ok(n) = serde_json::from_str::<serde_json::Number>(s) {
+        if n.to_string() == s {
+            return Value::Number(n);
+        }
+    }
+    Value::String(s.to_string())
+}
+
+/// 由解码后的键值对重建嵌套 JSON
+pub fn unflatten(
+    pairs: &[(String, String)],
+    arrays: QueryArrayStyle,
+    nesting: QueryNestingStyle,
+    infer_types: bool,
+) -> Value {
+    let mut root = Node::Object(Vec::new());
+    for (key, value) in pairs {
+        let segments = parse_key(key, nesting);
+        let leaf = match arrays {
+            QueryArrayStyle::Comma if value.contains(',') => {
+                Node::Array(value.split(',').map(|s| Node::Leaf(s.to_string())).collect())
+            }
+            _ => Node::Leaf(value.clone()),
+        };
+        root.insert(&segments, leaf);
+    }
+    root.into_value(infer_types)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flatten_unflatten_round_trip_keeps_key_order() {
+        let json = r#"{"zeta":1,"alpha":{"z":"x y","a":[1,2]},"mid":true,"list":[{"b":1,"a":2},{"b":3,"a":4}]}"#;
+        let value: Value = serde_json::from_str(json).unwrap();
+        let styles = [
+            QueryArrayStyle::Indices,
+            QueryArrayStyle::Brackets,
+            QueryArrayStyle::Repeat,
+            QueryArrayStyle::Comma,
+        ];
+        for arrays in styles {
+            for nesting in [QueryNestingStyle::Brackets, QueryNestingStyle::Dots] {
+                let query = encode_query(&flatten(&value, arrays, nesting));
+                let back = unflatten(&parse_pairs(&query, false), arrays, nesting, true);
+                assert_eq!(serde_json::to_string(&back).unwrap(), json, "{:?} {:?}: {}", arrays, nesting, query);
+            }
+        }
+    }
+
+    #[test]
+    fn unflatten_keeps_first_seen_order() {
+        let pairs = parse_pairs("b=1&a[y]=2&a[x]=3&c=4", false);
+        let value = unflatten(&pairs, QueryArrayStyle::Indices, QueryNestingStyle::Brackets, false);
+        assert_eq!(serde_json::to_string(&value).unwrap(), r#"{"b":"1","a":{"y":"2","x":"3"},"c":"4"}"#);
+    }
+}
@@ -2,9 +2,10 @@ use serde::Serialize;
 use std::collections::{HashMap, HashSet};
 use std::sync::Mutex;
 use once_cell::sync::Lazy;
-use jieba_rs::Jieba;
 use pinyin::{self, Args};
 
+use super::chinese_tools::JIEBA;
+
 #[derive(Debug, Clone, Serialize)]
 pub struct SearchResult {
     pub id: u32,
@@ -34,9 +35,6 @@ pub struct AiRouteResult {
     pub score: f64,
     pub reason: String,
 }
-/// 全局分词器
-static JIEBA: Lazy<Jieba> = Lazy::new(|| Jieba::new());
-
 /// 全局搜索索引
 static SEARCH_INDEX: Lazy<Mutex<SearchIndex>> = Lazy::new(|| Mutex::new(SearchIndex::new()));
 
@@ -0,0 +1,495 @@
+// Unicode 检查器：字素、码点、名称、类别、编码字节与各语言转义写法，
+// NFC/NFD/NFKC/NFKD 规范化，以及隐藏字符排查。
+//
+// 排查项：
+//   zero_width      零宽字符（U+200B/200C/200D/2060/FEFF …），emoji 序列里的 ZWJ 除外
+//   bidi_control    双向文本控制符（Trojan Source 攻击手法）
+//   invisible       其他不可见的格式字符与空白填充字符（软连字符、韩文填充符 …）
+//   unusual_space   非 U+0020 的空白（不换行空格、全角空格、行/段分隔符 …）
+//   control         除 \t \n \r 外的 C0/C1 控制字符
+//   bom             文本开头的 BOM
+//   homoglyph       按 UTS 39 confusables 与 ASCII 字母数字同形的字符（西里尔字母 а …）
+//   mixed_script    混用多种文字的单词（CJK 字符不参与判断，中英混排不算）
+
+use std::collections::BTreeMap;
+
+use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
+use serde::{Deserialize, Serialize};
+use unicode_general_category::{get_general_category, GeneralCategory};
+use unicode_normalization::{is_nfc, is_nfd, is_nfkc, is_nfkd, UnicodeNormalization};
+use unicode_script::{Script, UnicodeScript};
+use unicode_security::{skeleton, MixedScript};
+use unicode_segmentation::UnicodeSegmentation;
+
+use super::byte_io::{self, ByteInput, ByteOutput};
+
+/// 明细中最多列出的字素数
+const MAX_GRAPHEMES: usize = 2000;
+/// 最多报告的可疑字符数
+const MAX_WARNINGS: usize = 500;
+
+#[derive(Serialize)]
+pub struct UnicodeInspection {
+    pub graphemes: Vec<GraphemeInfo>,
+    pub grapheme_count: usize,
+    pub char_count: usize,
+    pub utf8_length: usize,
+    /// UTF-16 码元数，即 JavaScript 的 `length`
+    pub utf16_length: usize,
+    /// 文本是否已经是各规范化形式
+    pub normalization: NormalizationStatus,
+    pub warnings: Vec<UnicodeWarning>,
+    /// 各类可疑字符的总数（不受 `MAX_WARNINGS` 限制）
+    pub warning_counts: BTreeMap<WarningKind, usize>,
+    /// 字素明细被截断
+    pub truncated: bool,
+}
+
+#[derive(Serialize)]
+pub struct GraphemeInfo {
+    pub grapheme: String,
+    /// 行、列从 1 开始，列按字素计
+    pub line: usize,
+    pub column: usize,
+    pub code_points: Vec<CodePointInfo>,
+}
+
+#[derive(Serialize)]
+pub struct CodePointInfo {
+    pub character: String,
+    /// `U+4E2D`
+    pub code_point: String,
+    pub decimal: u32,
+    pub name: String,
+    /// 通用类别缩写，如 `Lo`
+    pub category: String,
+    pub category_name: String,
+    pub script: String,
+    pub utf8: String,
+    pub utf16: String,
+    pub escapes: CharEscapes,
+}
+
+#[derive(Serialize)]
+pub struct CharEscapes {
+    /// JavaScript / JSON / Java / C#，辅助平面字符为代理对
+    pub javascript: String,
+    /// ES6 / Rust / Swift / Ruby：`\u{1F600}`
+    pub es6: String,
+    pub python: String,
+    /// C / C++ / Go：`中`、`\U0001F600`
+    pub c: String,
+    pub css: String,
+    pub html_hex: String,
+    pub html_decimal: String,
+    /// UTF-8 百分号编码
+    pub url: String,
+}
+
+#[derive(Serialize)]
+pub struct NormalizationStatus {
+    pub nfc: bool,
+    pub nfd: bool,
+    pub nfkc: bool,
+    pub nfkd: bool,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum WarningKind {
+    ZeroWidth,
+    BidiControl,
+    Invisible,
+    UnusualSpace,
+    Control,
+    Bom,
+    Homoglyph,
+    MixedScript,
+}
+
+#[derive(Serialize)]
+pub struct UnicodeWarning {
+    pub kind: WarningKind,
+    /// 可疑字符，混合文字时为整个单词
+    pub text: String,
+    pub code_point: Option<String>,
+    pub name: Option<String>,
+    pub line: usize,
+    pub column: usize,
+    pub detail: String,
+}
+
+/// 逐字素列出码点信息并排查隐藏字符
+#[tauri::command]
+pub fn inspect_unicode(input: ByteInput) -> Result<UnicodeInspection, String> {
+    let text = input.into_text()?;
+    let mut graphemes = Vec::new();
+    let mut warnings = Vec::new();
+    let mut warning_counts = BTreeMap::new();
+    let mut report = |warning: UnicodeWarning| {
+        *warning_counts.entry(warning.kind).or_insert(0) += 1;
+        if warnings.len() < MAX_WARNINGS {
+            warnings.push(warning);
+        }
+    };
+
+    let (mut line, mut column) = (1, 1);
+    let mut grapheme_count = 0;
+    for (offset, grapheme) in text.grapheme_indices(true) {
+        grapheme_count += 1;
+        let emoji = is_emoji(grapheme);
+        for c in grapheme.chars() {
+            if let Some((kind, detail)) = classify(c, offset == 0, emoji) {
+                report(UnicodeWarning {
+                    kind,
+                    text: c.to_string(),
+                    code_point: Some(code_point(c)),
+                    name: Some(char_name(c)),
+                    line,
+                    column,
+                    detail,
+                });
+            }
+        }
+        if graphemes.len() < MAX_GRAPHEMES {
+            graphemes.push(GraphemeInfo {
+                grapheme: grapheme.to_string(),
+                line,
+                column,
+                code_points: grapheme.chars().map(code_point_info).collect(),
+            });
+        }
+        if grapheme.contains('\n') {
+            line += 1;
+            column = 1;
+        } else {
+            column += 1;
+        }
+    }
+
+    for (word, line, column) in words(&text) {
+        if let Some(detail) = mixed_script(word) {
+            report(UnicodeWarning {
+                kind: WarningKind::MixedScript,
+                text: word.to_string(),
+                code_point: None,
+                name: None,
+                line,
+                column,
+                detail,
+            });
+        }
+    }
+    warnings.sort_by_key(|w| (w.line, w.column));
+
+    Ok(UnicodeInspection {
+        truncated: grapheme_count > graphemes.len(),
+        graphemes,
+        grapheme_count,
+        char_count: text.chars().count(),
+        utf8_length: text.len(),
+        utf16_length: text.encode_utf16().count(),
+        normalization: NormalizationStatus {
+            nfc: is_nfc(&text),
+            nfd: is_nfd(&text),
+            nfkc: is_nfkc(&text),
+            nfkd: is_nfkd(&text),
+        },
+        warnings,
+        warning_counts,
+    })
+}
+
+// ==================== 规范化 ====================
+
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum NormalizationForm {
+    #[default]
+    Nfc,
+    Nfd,
+    Nfkc,
+    Nfkd,
+}
+
+#[derive(Serialize)]
+pub struct NormalizeResult {
+    pub output: String,
+    pub changed: bool,
+    /// 被移除的隐藏字符数
+    pub removed: usize,
+}
+
+/// 规范化文本；`strip_invisible` 同时移除零宽、双向控制、不可见格式字符和 BOM
+#[tauri::command]
+pub fn normalize_unicode(
+    input: ByteInput,
+    form: Option<NormalizationForm>,
+    strip_invisible: Option<bool>,
+    output: Option<ByteOutput>,
+) -> Result<NormalizeResult, String> {
+    let text = input.into_text()?;
+    let mut removed = 0;
+    let source = if strip_invisible.unwrap_or(false) {
+        let mut kept = String::with_capacity(text.len());
+        for grapheme in text.graphemes(true) {
+            let emoji = is_emoji(grapheme);
+            for c in grapheme.chars() {
+                let hidden = matches!(
+                    classify(c, true, emoji),
+                    Some((
+                        WarningKind::ZeroWidth | WarningKind::BidiControl | WarningKind::Invisible | WarningKind::Bom,
+                        _
+                    ))
+                );
+                if hidden {
+                    removed += 1;
+                } else {
+                    kept.push(c);
+                }
+            }
+        }
+        kept
+    } else {
+        text.clone()
+    };
+    let normalized: String = match form.unwrap_or_default() {
+        NormalizationForm::Nfc => source.nfc().collect(),
+        NormalizationForm::Nfd => source.nfd().collect(),
+        NormalizationForm::Nfkc => source.nfkc().collect(),
+        NormalizationForm::Nfkd => source.nfkd().collect(),
+    };
+    Ok(NormalizeResult {
+        changed: normalized != text,
+        output: output.unwrap_or_default().write_text(normalized)?,
+        removed,
+    })
+}
+
+// ==================== 转义 ====================
+
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum EscapeStyle {
+    #[default]
+    Javascript,
+    Es6,
+    Python,
+    C,
+    Css,
+    HtmlHex,
+    HtmlDecimal,
+    Url,
+}
+
+/// 按指定语言的写法转义非 ASCII 字符和控制字符，`all` 为 true 时转义全部字符
+#[tauri::command]
+pub fn escape_unicode(
+    input: ByteInput,
+    style: Option<EscapeStyle>,
+    all: Option<bool>,
+    output: Option<ByteOutput>,
+) -> Result<String, String> {
+    let text = input.into_text()?;
+    let style = style.unwrap_or_default();
+    let all = all.unwrap_or(false);
+    let mut result = String::with_capacity(text.len() * 2);
+    for c in text.chars() {
+        if !all && c.is_ascii() && !c.is_ascii_control() {
+            result.push(c);
+            continue;
+        }
+        match style {
+            EscapeStyle::Javascript => result.push_str(&escape_utf16(c)),
+            EscapeStyle::Es6 => result.push_str(&format!("\\u{{{:X}}}", c as u32)),
+            EscapeStyle::Python => result.push_str(&escape_python(c)),
+            EscapeStyle::C => result.push_str(&escape_c(c)),
+            // 十六进制转义会吞掉其后的一个空白，补一个空格隔开
+            EscapeStyle::Css => result.push_str(&format!("\\{:X} ", c as u32)),
+            EscapeStyle::HtmlHex => result.push_str(&format!("&#x{:X};", c as u32)),
+            EscapeStyle::HtmlDecimal => result.push_str(&format!("&#{};", c as u32)),
+            EscapeStyle::Url => result.push_str(&escape_url(c)),
+        }
+    }
+    output.unwrap_or_default().write_text(result)
+}
+
+// ==================== 内部实现 ====================
+
+fn code_point(c: char) -> String {
+    format!("U+{:04X}", c as u32)
+}
+
+fn code_point_info(c: char) -> CodePointInfo {
+    let category = get_general_category(c);
+    let mut utf8 = [0u8; 4];
+    let mut utf16 = [0u16; 2];
+    CodePointInfo {
+        character: c.to_string(),
+        code_point: code_point(c),
+        decimal: c as u32,
+        name: char_name(c),
+        category: category.abbreviation().to_string(),
+        category_name: format!("{:?}", category),
+        script: c.script().full_name().to_string(),
+        utf8: byte_io::encode_hex(c.encode_utf8(&mut utf8).as_bytes(), true, " "),
+        utf16: c
+            .encode_utf16(&mut utf16)
+            .iter()
+            .map(|unit| format!("{:04X}", unit))
+            .collect::<Vec<_>>()
+            .join(" "),
+        escapes: CharEscapes {
+            javascript: escape_utf16(c),
+            es6: format!("\\u{{{:X}}}", c as u32),
+            python: escape_python(c),
+            c: escape_c(c),
+            css: format!("\\{:X}", c as u32),
+            html_hex: format!("&#x{:X};", c as u32),
+            html_decimal: format!("&#{};", c as u32),
+            url: escape_url(c),
+        },
+    }
+}
+
+/// 字符名称；没有正式名称的码点按 Unicode 的惯例写成 `<control-0009>`
+fn char_name(c: char) -> String {
+    if let Some(name) = unicode_names2::name(c) {
+        return name.to_string();
+    }
+    let label = match get_general_category(c) {
+        GeneralCategory::Control => "control",
+        GeneralCategory::PrivateUse => "private-use",
+        GeneralCategory::Surrogate => "surrogate",
+        GeneralCategory::Unassigned => "unassigned",
+        _ => "unnamed",
+    };
+    format!("<{}-{:04X}>", label, c as u32)
+}
+
+fn escape_utf16(c: char) -> String {
+    let mut units = [0u16; 2];
+    c.encode_utf16(&mut units)
+        .iter()
+        .map(|unit| format!("\\u{:04X}", unit))
+        .collect()
+}
+
+fn escape_python(c: char) -> String {
+    match c as u32 {
+        cp @ 0..=0xFF => format!("\\x{:02x}", cp),
+        cp @ 0x100..=0xFFFF => format!("\\u{:04x}", cp),
+        cp => format!("\\U{:08x}", cp),
+    }
+}
+
+fn escape_c(c: char) -> String {
+    match c as u32 {
+        cp @ 0..=0xFFFF => format!("\\u{:04X}", cp),
+        cp => format!("\\U{:08X}", cp),
+    }
+}
+
+fn escape_url(c: char) -> String {
+    let mut buf = [0u8; 4];
+    utf8_percent_encode(c.encode_utf8(&mut buf), NON_ALPHANUMERIC).to_string()
+}
+
+/// 判断单个字符是否可疑。`at_start` 表示位于文本开头（BOM 合法），
+/// `in_emoji` 表示所在字素含 emoji（ZWJ、标签字符合法）
+fn classify(c: char, at_start: bool, in_emoji: bool) -> Option<(WarningKind, String)> {
+    let category = get_general_category(c);
+    let found = match c {
+        '\u{FEFF}' if at_start => (WarningKind::Bom, "文本开头的字节顺序标记".to_string()),
+        '\u{200D}' | '\u{E0020}'..='\u{E007F}' if in_emoji => return None,
+        '\u{200B}' | '\u{200C}' | '\u{200D}' | '\u{2060}' | '\u{FEFF}' | '\u{180E}' => {
+            (WarningKind::ZeroWidth, "零宽字符，肉眼不可见".to_string())
+        }
+        '\u{061C}' | '\u{200E}' | '\u{200F}' | '\u{202A}'..='\u{202E}' | '\u{2066}'..='\u{2069}' => (
+            WarningKind::BidiControl,
+            "双向文本控制符，可能使显示顺序与实际顺序不一致".to_string(),
+        ),
+        '\u{034F}' | '\u{115F}' | '\u{1160}' | '\u{17B4}' | '\u{17B5}' | '\u{2800}' | '\u{3164}' | '\u{FFA0}' => {
+            (WarningKind::Invisible, "显示为空白的字符".to_string())
+        }
+        '\t' | '\n' | '\r' | ' ' => return None,
+        '\u{0085}' | '\u{2028}' | '\u{2029}' => (WarningKind::UnusualSpace, "非常规换行符".to_string()),
+        _ => match category {
+            GeneralCategory::Format if !in_emoji => (WarningKind::Invisible, "不可见的格式字符".to_string()),
+            GeneralCategory::SpaceSeparator => (WarningKind::UnusualSpace, "非常规空白字符".to_string()),
+            GeneralCategory::Control => (WarningKind::Control, "控制字符".to_string()),
+            _ => return homoglyph(c).map(|ascii| (WarningKind::Homoglyph, format!("易与 '{}' 混淆", ascii))),
+        },
+    };
+    Some(found)
+}
+
+/// 与之同形的 ASCII 字母数字（CJK 字符如 `〇` 不算）
+fn homoglyph(c: char) -> Option<String> {
+    if c.is_ascii() || !c.is_alphanumeric() || is_cjk(c) {
+        return None;
+    }
+    let ascii: String = skeleton(c.encode_utf8(&mut [0u8; 4])).collect();
+    (!ascii.is_empty() && ascii.chars().all(|a| a.is_ascii_alphanumeric())).then_some(ascii)
+}
+
+/// 去掉 CJK 字符后若仍混用多种文字（如拉丁与西里尔），返回说明
+fn mixed_script(word: &str) -> Option<String> {
+    if word.is_ascii() {
+        return None;
+    }
+    let rest: String = word
+        .chars()
+        .filter(|&c| !is_cjk(c))
+        .collect();
+    if rest.is_empty() || rest.as_str().is_single_script() {
+        return None;
+    }
+    let mut scripts: Vec<&str> = Vec::new();
+    for c in rest.chars() {
+        let script = c.script();
+        if !matches!(script, Script::Common | Script::Inherited) && !scripts.contains(&script.full_name()) {
+            scripts.push(script.full_name());
+        }
+    }
+    Some(format!("单词混用多种文字: {}", scripts.join(", ")))
+}
+
+/// emoji 序列中的 ZWJ、标签字符属于正常用法
+fn is_emoji(grapheme: &str) -> bool {
+    grapheme
+        .chars()
+        .any(|c| get_general_category(c) == GeneralCategory::OtherSymbol)
+}
+
+fn is_cjk(c: char) -> bool {
+    matches!(c.script(), Script::Han | Script::Hiragana | Script::Katakana | Script::Hangul)
+}
+
+/// 按字母数字切分单词，附带起始行列（列按字素计）
+fn words(text: &str) -> Vec<(&str, usize, usize)> {
+    let mut words = Vec::new();
+    let (mut line, mut column) = (1, 1);
+    let mut start: Option<(usize, usize, usize)> = None;
+    for (offset, grapheme) in text.grapheme_indices(true) {
+        let is_word = grapheme.chars().next().is_some_and(char::is_alphanumeric);
+        match (is_word, start) {
+            (true, None) => start = Some((offset, line, column)),
+            (false, Some((begin, l, c))) => {
+                words.push((&text[begin..offset], l, c));
+                start = None;
+            }
+            _ => {}
+        }
+        if grapheme.contains('\n') {
+            line += 1;
+            column = 1;
+        } else {
+            column += 1;
+        }
+    }
+    if let Some((begin, l, c)) = start {
+        words.push((&text[begin..], l, c));
+    }
+    words
+}
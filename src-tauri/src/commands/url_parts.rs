@@ -0,0 +1,311 @@
+// `parse_url` / `build_url` 背后的 URL 拆分与重组，以及 RFC 3986 中各组成部分的
+// 百分号编码集合。
+//
+// 解析沿用 RFC 3986 附录 B 的通用语法正则，接受任何形似 URL 的输入（包括相对
+// 引用），且从不做规范化。主机名按标签逐个用 punycode 双向转换；这是不含
+// UTS 46 映射的 IDNA "xn--" 转换，足以用于展示和由 Unicode 输入构建链接。
+
+use once_cell::sync::Lazy;
+use percent_encoding::{percent_decode_str, percent_encode, utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+// ==================== 编码集合 ====================
+
+/// 除 ALPHA / DIGIT / `-._~` 以外的全部字符
+pub const UNRESERVED: &AsciiSet = &NON_ALPHANUMERIC.remove(b'-').remove(b'.').remove(b'_').remove(b'~');
+
+/// pchar = unreserved / sub-delims / `:` / `@`，用于单个路径片段
+pub const PATH_SEGMENT: &AsciiSet = &UNRESERVED
+    .remove(b'!')
+    .remove(b'$')
+    .remove(b'&')
+    .remove(b'\'')
+    .remove(b'(')
+    .remove(b')')
+    .remove(b'*')
+    .remove(b'+')
+    .remove(b',')
+    .remove(b';')
+    .remove(b'=')
+    .remove(b':')
+    .remove(b'@');
+
+pub const PATH: &AsciiSet = &PATH_SEGMENT.remove(b'/');
+
+/// 查询中的键或值: pchar、`/` 和 `?`，去掉用于分隔键值对的字符
+pub const QUERY_COMPONENT: &AsciiSet = &PATH_SEGMENT.remove(b'/').remove(b'?').add(b'&').add(b'=').add(b'+');
+
+pub const FRAGMENT: &AsciiSet = &PATH_SEGMENT.remove(b'/').remove(b'?');
+
+/// 用户名: 允许 sub-delims，不允许 `:` 和 `@`
+pub const USERINFO: &AsciiSet = &PATH_SEGMENT.add(b':').add(b'@');
+
+/// 密码: 与用户名相同，但第一个 `:` 之后可以出现 `:`
+pub const PASSWORD: &AsciiSet = &PATH_SEGMENT.add(b'@');
+
+/// application/x-www-form-urlencoded，空格替换为 `+` 之前的编码集合
+pub const FORM: &AsciiSet = &NON_ALPHANUMERIC.remove(b'*').remove(b'-').remove(b'.').remove(b'_');
+
+/// 表单请求体的百分号编码: 使用 `FORM` 集合，空格编码为 `+`
+pub fn form_encode(input: impl AsRef<[u8]>) -> String {
+    percent_encode(input.as_ref(), FORM).to_string().replace("%20", "+")
+}
+
+// ==================== 解析 ====================
+
+static URL_PARTS: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^(?:([A-Za-z][A-Za-z0-9+.\-]*):)?(?://([^/?#]*))?([^?#]*)(?:\?([^#]*))?(?:#(.*))?$").unwrap());
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct UrlParam {
+    pub key: String,
+    pub value: String,
+}
+
+#[derive(Serialize)]
+pub struct ParsedUrl {
+    pub scheme: Option<String>,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    /// 原样的主机名（IPv6 字面量去掉方括号）
+    pub host: Option<String>,
+    pub host_unicode: Option<String>,
+    pub host_ascii: Option<String>,
+    pub is_ipv6: bool,
+    pub port: Option<u16>,
+    /// 显式端口，或该协议的默认端口
+    pub effective_port: Option<u16>,
+    /// 原始（仍为编码形式的）路径
+    pub path: String,
+    pub path_segments: Vec<String>,
+    /// 原始（仍为编码形式的）查询，不含 `?`
+    pub query: Option<String>,
+    pub query_params: Vec<UrlParam>,
+    pub fragment: Option<String>,
+}
+
+/// 把 `input` 拆分为解码后的各组成部分。百分号转义用 `decode` 解码，由调用方
+/// 选择字符集；`form` 还会把查询中的 `+` 转为空格。
+pub fn parse(input: &str, form: bool, decode: impl Fn(&[u8]) -> String) -> Result<ParsedUrl, String> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Err("输入不能为空".to_string());
+    }
+    let caps = URL_PARTS.captures(input).ok_or("无法解析URL")?;
+    let unescape = |s: &str| decode(&percent_decode_str(s).collect::<Vec<u8>>());
+
+    let scheme = caps.get(1).map(|m| m.as_str().to_ascii_lowercase());
+    let mut parsed = ParsedUrl {
+        scheme: scheme.clone(),
+        username: None,
+        password: None,
+        host: None,
+        host_unicode: None,
+        host_ascii: None,
+        is_ipv6: false,
+        port: None,
+        effective_port: None,
+        path: caps.get(3).map_or("", |m| m.as_str()).to_string(),
+        path_segments: Vec::new(),
+        query: caps.get(4).map(|m| m.as_str().to_string()),
+        query_params: Vec::new(),
+        fragment: caps.get(5).map(|m| unescape(m.as_str())),
+    };
+
+    if let Some(authority) = caps.get(2).map(|m| m.as_str()) {
+        let (userinfo, host_port) = match authority.rsplit_once('@') {
+            Some((userinfo, rest)) => (Some(userinfo), rest),
+            None => (None, authority),
+        };
+        if let Some(userinfo) = userinfo {
+            let (user, password) = match userinfo.split_once(':') {
+                Some((user, password)) => (user, Some(password)),
+                None => (userinfo, None),
+            };
+            parsed.username = Some(unescape(user));
+            parsed.password = password.map(unescape);
+        }
+
+        let (host, port) = split_host_port(host_port)?;
+        parsed.is_ipv6 = host.contains(':');
+        if !host.is_empty() {
+            let host = unescape(host);
+            if !parsed.is_ipv6 {
+                parsed.host_unicode = Some(host_to_unicode(&host)?);
+                parsed.host_ascii = Some(host_to_ascii(&host)?);
+            }
+            parsed.host = Some(host);
+        }
+        parsed.port = port;
+    }
+    parsed.effective_port = parsed.port.or_else(|| scheme.as_deref().and_then(default_port));
+
+    if !parsed.path.is_empty() {
+        let path = parsed.path.strip_prefix('/').unwrap_or(&parsed.path);
+        parsed.path_segments = path.split('/').map(unescape).collect();
+    }
+
+    if let Some(query) = &parsed.query {
+        parsed.query_params = query
+            .split('&')
+            .filter(|part| !part.is_empty())
+            .map(|part| {
+                let (key, value) = part.split_once('=').unwrap_or((part, ""));
+                let plus = |s: &str| if form { s.replace('+', " ") } else { s.to_string() };
+                UrlParam {
+                    key: unescape(&plus(key)),
+                    value: unescape(&plus(value)),
+                }
+            })
+            .collect();
+    }
+
+    Ok(parsed)
+}
+
+fn split_host_port(host_port: &str) -> Result<(&str, Option<u16>), String> {
+    let (host, port) = if let Some(rest) = host_port.strip_prefix('[') {
+        let end = rest.find(']').ok_or("IPv6 地址缺少 ']'")?;
+        let port = rest[end + 1..].strip_prefix(':');
+        (&rest[..end], port)
+    } else {
+        match host_port.rsplit_once(':') {
+            Some((host, port)) => (host, Some(port)),
+            None => (host_port, None),
+        }
+    };
+    let port = match port {
+        None | Some("") => None,
+        Some(port) => Some(port.parse::<u16>().map_err(|_| format!("无效的端口: {}", port))?),
+    };
+    Ok((host, port))
+}
+
+pub fn default_port(scheme: &str) -> Option<u16> {
+    match scheme {
+        "http" | "ws" => Some(80),
+        "https" | "wss" => Some(443),
+        "ftp" => Some(21),
+        "ssh" | "sftp" => Some(22),
+        _ => None,
+    }
+}
+
+/// 把 `xn--` 标签转换为 Unicode
+pub fn host_to_unicode(host: &str) -> Result<String, String> {
+    map_labels(host, |label| match label.get(..4) {
+        Some(prefix) if prefix.eq_ignore_ascii_case("xn--") => {
+            punycode::decode(&label[4..]).map_err(|_| format!("无效的 Punycode 标签: {}", label))
+        }
+        _ => Ok(label.to_string()),
+    })
+}
+
+/// 把非 ASCII 标签转换为 `xn--` 形式，ASCII 标签转为小写
+pub fn host_to_ascii(host: &str) -> Result<String, String> {
+    map_labels(host, |label| {
+        if label.is_ascii() {
+            Ok(label.to_ascii_lowercase())
+        } else {
+            punycode::encode(&label.to_lowercase())
+                .map(|encoded| format!("xn--{}", encoded))
+                .map_err(|_| format!("无法转换为 Punycode: {}", label))
+        }
+    })
+}
+
+fn map_labels(host: &str, f: impl Fn(&str) -> Result<String, String>) -> Result<String, String> {
+    // IDNA 同样把表意句号和全角句号视为点号
+    let host = host.replace(['\u{3002}', '\u{FF0E}', '\u{FF61}'], ".");
+    Ok(host.split('.').map(f).collect::<Result<Vec<_>, _>>()?.join("."))
+}
+
+// ==================== 构建 ====================
+
+#[derive(Deserialize, Default)]
+pub struct UrlParts {
+    pub scheme: Option<String>,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub host: Option<String>,
+    pub port: Option<u16>,
+    /// 解码后的路径片段。有主机名时优先于 `path`；没有主机名时以 `path` 为准，
+    /// 因为片段无法表达路径是否以 `/` 开头。
+    pub path_segments: Option<Vec<String>>,
+    /// 解码后的路径，`/` 保留为分隔符
+    pub path: Option<String>,
+    pub query_params: Option<Vec<UrlParam>>,
+    pub fragment: Option<String>,
+}
+
+/// 由解码后的各部分组装 URL，每部分使用各自的编码集合。
+/// `form` 把查询按表单请求体编码（空格编码为 `+`）。
+pub fn build(parts: &UrlParts, form: bool) -> Result<String, String> {
+    let mut url = String::new();
+    if let Some(scheme) = parts.scheme.as_deref().filter(|s| !s.is_empty()) {
+        url.push_str(&scheme.to_ascii_lowercase());
+        url.push(':');
+    }
+
+    if let Some(host) = parts.host.as_deref() {
+        url.push_str("//");
+        if let Some(user) = &parts.username {
+            url.push_str(&utf8_percent_encode(user, USERINFO).to_string());
+            if let Some(password) = &parts.password {
+                url.push(':');
+                url.push_str(&utf8_percent_encode(password, PASSWORD).to_string());
+            }
+            url.push('@');
+        }
+        if host.contains(':') {
+            url.push_str(&format!("[{}]", host.trim_matches(['[', ']'])));
+        } else {
+            url.push_str(&host_to_ascii(host)?);
+        }
+        if let Some(port) = parts.port {
+            url.push_str(&format!(":{}", port));
+        }
+    }
+
+    let path = match (&parts.path_segments, &parts.path) {
+        // 没有 authority: 路径按原样保留（`/a`、`a/b`、`x@y`）
+        (_, Some(path)) if parts.host.is_none() => utf8_percent_encode(path, PATH).to_string(),
+        (Some(segments), _) => segments
+            .iter()
+            .map(|s| utf8_percent_encode(s, PATH_SEGMENT).to_string())
+            .collect::<Vec<_>>()
+            .join("/"),
+        (None, Some(path)) => utf8_percent_encode(path, PATH).to_string(),
+        (None, None) => String::new(),
+    };
+    // 只有 authority 与路径之间才需要 `/`
+    if parts.host.is_some() && !path.is_empty() && !path.starts_with('/') {
+        url.push('/');
+    }
+    url.push_str(&path);
+
+    if let Some(params) = parts.query_params.as_ref().filter(|p| !p.is_empty()) {
+        let encode = |s: &str| {
+            if form {
+                form_encode(s)
+            } else {
+                utf8_percent_encode(s, QUERY_COMPONENT).to_string()
+            }
+        };
+        url.push('?');
+        url.push_str(
+            &params
+                .iter()
+                .map(|p| format!("{}={}", encode(&p.key), encode(&p.value)))
+                .collect::<Vec<_>>()
+                .join("&"),
+        );
+    }
+
+    if let Some(fragment) = &parts.fragment {
+        url.push('#');
+        url.push_str(&utf8_percent_encode(fragment, FRAGMENT).to_string());
+    }
+    Ok(url)
+}
@@ -0,0 +1,1018 @@
+// 本地密码保险库
+//
+// 每个条目单独用 AES-256-GCM 加密，密钥由主密码经 Argon2id 派生。与
+// `llm_secret.bin` 不同，这里不绑定主机名：保险库文件可以复制到其他机器，
+// 用同一个主密码打开；没有主密码则文件毫无用处。
+//
+// 派生出的密钥只在解锁期间驻留内存。每个保险库命令都会刷新空闲计时，
+// 超过 `auto_lock_secs` 无操作后，后台线程丢弃（并清零）密钥。
+//
+// ~/.toolbox 下的文件布局:
+//   vault.json   — 版本、Argon2id 参数与盐、自动锁定时间、
+//                  用于识别错误主密码并保护设置的加密校验块，
+//                  以及加密后的条目
+//                  { id, data: base64(nonce(12) | 密文 | gcm-tag(16)) }。
+//                  条目 id 作为附加数据参与认证，密文块无法在条目间互换。
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, Once, OnceLock};
+use std::time::{Duration, Instant};
+
+use aes_gcm::aead::{Aead, KeyInit, Payload};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use argon2::{Algorithm, Argon2, Params, Version};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine as _;
+use rand::rngs::OsRng;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use zeroize::Zeroizing;
+
+const VAULT_VERSION: u32 = 1;
+const NONCE_LEN: usize = 12;
+const KEY_LEN: usize = 32;
+const SALT_LEN: usize = 16;
+
+/// 加密校验块 `verifier` 中的固定标记。校验块除了识别错误的主密码，
+/// 还携带不允许在无密钥时修改的设置（见 `VerifierData`）。
+const VERIFIER_MAGIC: &str = "tbox.vault.v1";
+const VERIFIER_AAD: &[u8] = b"verifier";
+
+/// Argon2id 默认参数: 64 MiB、3 轮、1 条并行通道 —— OWASP 推荐的基线，
+/// 在当前的笔记本上约 0.3 秒。
+const DEFAULT_MEMORY_KIB: u32 = 64 * 1024;
+const DEFAULT_ITERATIONS: u32 = 3;
+const DEFAULT_PARALLELISM: u32 = 1;
+
+const DEFAULT_AUTO_LOCK_SECS: u64 = 300;
+const MIN_MASTER_PASSWORD_LEN: usize = 8;
+const WATCH_INTERVAL: Duration = Duration::from_secs(5);
+
+// ==================== 文件格式 ====================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct VaultFile {
+    version: u32,
+    kdf: KdfParams,
+    /// 0 表示不自动锁定。此处的明文副本仅供锁定状态下展示，
+    /// 实际生效的是 `verifier` 中经过认证的值。
+    auto_lock_secs: u64,
+    verifier: String,
+    entries: Vec<SealedEntry>,
+}
+
+/// `verifier` 的明文内容
+#[derive(Debug, Serialize, Deserialize)]
+struct VerifierData {
+    magic: String,
+    auto_lock_secs: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct KdfParams {
+    algorithm: String,
+    memory_kib: u32,
+    iterations: u32,
+    parallelism: u32,
+    salt: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct SealedEntry {
+    id: String,
+    data: String,
+}
+
+// ==================== 条目 ====================
+
+/// 解密后的条目。`totp` 保存 Base32 密钥或完整的 `otpauth://` URI，
+/// 与用户输入或导入时的原样一致。
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct VaultEntry {
+    #[serde(default)]
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub username: String,
+    #[serde(default)]
+    pub password: String,
+    #[serde(default)]
+    pub urls: Vec<String>,
+    #[serde(default)]
+    pub notes: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub totp: Option<String>,
+    #[serde(default)]
+    pub created_at: String,
+    #[serde(default)]
+    pub updated_at: String,
+}
+
+/// `vault_save_entry` 的前端入参。不带 `id` 时新建条目，带 `id` 时替换已有条目。
+#[derive(Debug, Clone, Deserialize, Default)]
+pub struct VaultEntryInput {
+    #[serde(default)]
+    pub id: Option<String>,
+    pub name: String,
+    #[serde(default)]
+    pub username: String,
+    #[serde(default)]
+    pub password: String,
+    #[serde(default)]
+    pub urls: Vec<String>,
+    #[serde(default)]
+    pub notes: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub totp: Option<String>,
+}
+
+/// 列表视图中的条目 —— 不含密码、备注和 TOTP 密钥，这些只由 `vault_get_entry` 返回。
+#[derive(Debug, Clone, Serialize)]
+pub struct VaultEntrySummary {
+    pub id: String,
+    pub name: String,
+    pub username: String,
+    pub urls: Vec<String>,
+    pub tags: Vec<String>,
+    pub has_totp: bool,
+    pub updated_at: String,
+}
+
+impl From<&VaultEntry> for VaultEntrySummary {
+    fn from(entry: &VaultEntry) -> Self {
+        VaultEntrySummary {
+            id: entry.id.clone(),
+            name: entry.name.clone(),
+            username: entry.username.clone(),
+            urls: entry.urls.clone(),
+            tags: entry.tags.clone(),
+            has_totp: entry.totp.is_some(),
+            updated_at: entry.updated_at.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct VaultStatus {
+    pub exists: bool,
+    pub unlocked: bool,
+    pub entry_count: usize,
+    pub auto_lock_secs: u64,
+    /// 距自动锁定的秒数；已锁定或未开启自动锁定时为 `None`
+    pub locks_in_secs: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct VaultTag {
+    pub tag: String,
+    pub count: usize,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum VaultFileFormat {
+    /// KeePass / KeePassXC 导出的 CSV
+    KeepassCsv,
+    /// Bitwarden 导出的未加密 JSON
+    BitwardenJson,
+}
+
+#[derive(Debug, Clone, Serialize, Default)]
+pub struct VaultImportReport {
+    pub imported: usize,
+    /// 名称、用户名和密码与已有条目相同
+    pub duplicates: usize,
+    /// 非登录类（银行卡、身份信息）或没有名称的记录
+    pub skipped: usize,
+    pub errors: Vec<String>,
+}
+
+// ==================== 会话 ====================
+
+struct Session {
+    key: Zeroizing<[u8; KEY_LEN]>,
+    last_used: Instant,
+    auto_lock: Option<Duration>,
+}
+
+impl Session {
+    fn expired(&self) -> bool {
+        self.auto_lock.is_some_and(|timeout| self.last_used.elapsed() >= timeout)
+    }
+}
+
+static SESSION: Mutex<Option<Session>> = Mutex::new(None);
+static WATCHER: Once = Once::new();
+static VAULT_PATH: OnceLock<PathBuf> = OnceLock::new();
+
+fn session() -> std::sync::MutexGuard<'static, Option<Session>> {
+    // 持锁期间 panic 后 `Option` 依然可用，直接恢复，避免保险库在进程剩余时间内不可用
+    SESSION.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// 空闲超时后丢弃密钥
+fn expire_session() {
+    let mut guard = session();
+    if guard.as_ref().is_some_and(Session::expired) {
+        *guard = None;
+    }
+}
+
+fn start_watcher() {
+    WATCHER.call_once(|| {
+        std::thread::spawn(|| loop {
+            std::thread::sleep(WATCH_INTERVAL);
+            expire_session();
+        });
+    });
+}
+
+/// 返回已解锁的会话，并刷新空闲计时
+fn active(guard: &mut Option<Session>) -> Result<&mut Session, String> {
+    if guard.as_ref().is_some_and(Session::expired) {
+        *guard = None;
+    }
+    let session = guard.as_mut().ok_or_else(|| "保险库已锁定，请先输入主密码解锁".to_string())?;
+    session.last_used = Instant::now();
+    Ok(session)
+}
+
+/// 用已解锁的密钥执行 `f` 并刷新空闲计时。仅用于读取，`f` 执行前已释放锁。
+fn with_key<T>(f: impl FnOnce(&[u8; KEY_LEN]) -> Result<T, String>) -> Result<T, String> {
+    let key = active(&mut session())?.key.clone();
+    f(&key)
+}
+
+/// 持有会话锁完成读取、修改、写回保险库，避免并发命令互相覆盖修改
+fn update_vault<T>(f: impl FnOnce(&mut Session, &mut VaultFile) -> Result<T, String>) -> Result<T, String> {
+    let mut guard = session();
+    let session = active(&mut guard)?;
+    let mut vault = require_vault()?;
+    let result = f(session, &mut vault)?;
+    write_vault(&vault)?;
+    Ok(result)
+}
+
+fn auto_lock_duration(secs: u64) -> Option<Duration> {
+    (secs > 0).then(|| Duration::from_secs(secs))
+}
+
+// ==================== 文件读写 ====================
+
+fn vault_path() -> Result<&'static PathBuf, String> {
+    if let Some(path) = VAULT_PATH.get() {
+        return Ok(path);
+    }
+    let mut dir = dirs::home_dir().ok_or_else(|| "无法获取用户主目录".to_string())?;
+    dir.push(".toolbox");
+    fs::create_dir_all(&dir).map_err(|e| format!("创建配置目录失败: {}", e))?;
+    Ok(VAULT_PATH.get_or_init(|| dir.join("vault.json")))
+}
+
+fn read_vault() -> Result<Option<VaultFile>, String> {
+    let path = vault_path()?;
+    let bytes = match fs::read(path) {
+        Ok(bytes) => bytes,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(format!("读取保险库失败: {}", e)),
+    };
+    let vault: VaultFile = serde_json::from_slice(&bytes).map_err(|e| format!("保险库文件已损坏: {}", e))?;
+    if vault.version > VAULT_VERSION {
+        return Err(format!("保险库版本 {} 高于当前支持的版本，请升级应用", vault.version));
+    }
+    Ok(Some(vault))
+}
+
+fn require_vault() -> Result<VaultFile, String> {
+    read_vault()?.ok_or_else(|| "保险库尚未创建".to_string())
+}
+
+fn write_vault(vault: &VaultFile) -> Result<(), String> {
+    let json = serde_json::to_vec_pretty(vault).map_err(|e| format!("序列化保险库失败: {}", e))?;
+    write_private(vault_path()?, &json).map_err(|e| format!("写入保险库失败: {}", e))
+}
+
+/// 先写临时文件再重命名，写到一半崩溃也不会留下截断的保险库。临时文件名随机，
+/// 以仅属主可读写的权限新建，任何时刻都不会被他人读取，也不会跟随预先放置的
+/// 文件或符号链接。
+fn write_private(path: &Path, bytes: &[u8]) -> std::io::Result<()> {
+    let name = path.file_name().map(|n| n.to_string_lossy()).unwrap_or_default();
+    let tmp = path.with_file_name(format!(".{}.{:016x}.tmp", name, OsRng.next_u64()));
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let result = options
+        .open(&tmp)
+        .and_then(|mut file| file.write_all(bytes).and_then(|_| file.sync_all()))
+        .and_then(|_| fs::rename(&tmp, path));
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp);
+    }
+    result
+}
+
+// ==================== 加解密 ====================
+
+fn new_kdf_params() -> KdfParams {
+    let mut salt = [0u8; SALT_LEN];
+    OsRng.fill_bytes(&mut salt);
+    KdfParams {
+        algorithm: "argon2id".to_string(),
+        memory_kib: DEFAULT_MEMORY_KIB,
+        iterations: DEFAULT_ITERATIONS,
+        parallelism: DEFAULT_PARALLELISM,
+        salt: STANDARD.encode(salt),
+    }
+}
+
+fn derive_key(password: &str, kdf: &KdfParams) -> Result<Zeroizing<[u8; KEY_LEN]>, String> {
+    if kdf.algorithm != "argon2id" {
+        return Err(format!("不支持的密钥派生算法: {}", kdf.algorithm));
+    }
+    let salt = STANDARD.decode(&kdf.salt).map_err(|_| "保险库文件已损坏: salt 无效".to_string())?;
+    let params = Params::new(kdf.memory_kib, kdf.iterations, kdf.parallelism, Some(KEY_LEN))
+        .map_err(|e| format!("Argon2 参数无效: {}", e))?;
+    let mut key = Zeroizing::new([0u8; KEY_LEN]);
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password_into(password.as_bytes(), &salt, key.as_mut())
+        .map_err(|e| format!("密钥派生失败: {}", e))?;
+    Ok(key)
+}
+
+fn seal(key: &[u8; KEY_LEN], plaintext: &[u8], aad: &[u8]) -> Result<String, String> {
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key));
+    let mut nonce_bytes = [0u8; NONCE_LEN];
+    OsRng.fill_bytes(&mut nonce_bytes);
+    let ciphertext = cipher
+        .encrypt(Nonce::from_slice(&nonce_bytes), Payload { msg: plaintext, aad })
+        .map_err(|_| "加密失败".to_string())?;
+    let mut bundle = Vec::with_capacity(NONCE_LEN + ciphertext.len());
+    bundle.extend_from_slice(&nonce_bytes);
+    bundle.extend_from_slice(&ciphertext);
+    Ok(STANDARD.encode(bundle))
+}
+
+fn open(key: &[u8; KEY_LEN], sealed: &str, aad: &[u8]) -> Result<Zeroizing<Vec<u8>>, String> {
+    let bundle = STANDARD.decode(sealed).map_err(|_| "保险库数据已损坏".to_string())?;
+    if bundle.len() <= NONCE_LEN {
+        return Err("保险库数据已损坏".to_string());
+    }
+    let (nonce_bytes, ciphertext) = bundle.split_at(NONCE_LEN);
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key));
+    cipher
+        .decrypt(Nonce::from_slice(nonce_bytes), Payload { msg: ciphertext, aad })
+        .map(Zeroizing::new)
+        .map_err(|_| "解密失败".to_string())
+}
+
+fn seal_verifier(key: &[u8; KEY_LEN], auto_lock_secs: u64) -> Result<String, String> {
+    let data = VerifierData {
+        magic: VERIFIER_MAGIC.to_string(),
+        auto_lock_secs,
+    };
+    let json = serde_json::to_vec(&data).map_err(|e| format!("序列化保险库失败: {}", e))?;
+    seal(key, &json, VERIFIER_AAD)
+}
+
+/// 由 `password` 派生密钥并用校验块验证，返回密钥和经过认证的自动锁定时间
+fn unlock_key(vault: &VaultFile, password: &str) -> Result<(Zeroizing<[u8; KEY_LEN]>, u64), String> {
+    let key = derive_key(password, &vault.kdf)?;
+    let json = open(&key, &vault.verifier, VERIFIER_AAD).map_err(|_| "主密码错误".to_string())?;
+    let data: VerifierData = serde_json::from_slice(&json)
+        .ok()
+        .filter(|data: &VerifierData| data.magic == VERIFIER_MAGIC)
+        .ok_or_else(|| "保险库文件已损坏: verifier 无效".to_string())?;
+    Ok((key, data.auto_lock_secs))
+}
+
+fn seal_entry(key: &[u8; KEY_LEN], entry: &VaultEntry) -> Result<SealedEntry, String> {
+    let json = Zeroizing::new(serde_json::to_vec(entry).map_err(|e| format!("序列化条目失败: {}", e))?);
+    Ok(SealedEntry {
+        id: entry.id.clone(),
+        data: seal(key, &json, entry.id.as_bytes())?,
+    })
+}
+
+fn open_entry(key: &[u8; KEY_LEN], sealed: &SealedEntry) -> Result<VaultEntry, String> {
+    let json = open(key, &sealed.data, sealed.id.as_bytes()).map_err(|_| format!("条目 {} 已损坏", sealed.id))?;
+    let mut entry: VaultEntry =
+        serde_json::from_slice(&json).map_err(|e| format!("条目 {} 已损坏: {}", sealed.id, e))?;
+    entry.id = sealed.id.clone();
+    Ok(entry)
+}
+
+fn open_all(key: &[u8; KEY_LEN], vault: &VaultFile) -> Result<Vec<VaultEntry>, String> {
+    vault.entries.iter().map(|sealed| open_entry(key, sealed)).collect()
+}
+
+// ==================== 辅助函数 ====================
+
+fn now() -> String {
+    chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+}
+
+fn clean_list(items: Vec<String>) -> Vec<String> {
+    let mut out: Vec<String> = Vec::with_capacity(items.len());
+    for item in items {
+        let item = item.trim();
+        if !item.is_empty() && !out.iter().any(|existing| existing == item) {
+            out.push(item.to_string());
+        }
+    }
+    out
+}
+
+fn non_empty(value: Option<String>) -> Option<String> {
+    value.map(|v| v.trim().to_string()).filter(|v| !v.is_empty())
+}
+
+/// 把入参转换为存储的条目，替换已有条目时保留其 `created_at`
+fn build_entry(input: VaultEntryInput, id: String, created_at: Option<String>) -> Result<VaultEntry, String> {
+    let name = input.name.trim().to_string();
+    if name.is_empty() {
+        return Err("名称不能为空".to_string());
+    }
+    let timestamp = now();
+    Ok(VaultEntry {
+        id,
+        name,
+        username: input.username.trim().to_string(),
+        password: input.password,
+        urls: clean_list(input.urls),
+        notes: input.notes,
+        tags: clean_list(input.tags),
+        totp: non_empty(input.totp),
+        created_at: created_at.unwrap_or_else(|| timestamp.clone()),
+        updated_at: timestamp,
+    })
+}
+
+/// 按空白拆分的每个关键词都须出现在名称、用户名、URL、标签或备注中（不区分大小写）
+fn matches_query(entry: &VaultEntry, terms: &[String]) -> bool {
+    let haystack = [
+        entry.name.as_str(),
+        entry.username.as_str(),
+        entry.notes.as_str(),
+        &entry.urls.join(" "),
+        &entry.tags.join(" "),
+    ]
+    .join("\n")
+    .to_lowercase();
+    terms.iter().all(|term| haystack.contains(term.as_str()))
+}
+
+fn validate_master_password(password: &str) -> Result<(), String> {
+    if password.chars().count() < MIN_MASTER_PASSWORD_LEN {
+        return Err(format!("主密码至少需要 {} 个字符", MIN_MASTER_PASSWORD_LEN));
+    }
+    Ok(())
+}
+
+async fn blocking<T: Send + 'static>(f: impl FnOnce() -> Result<T, String> + Send + 'static) -> Result<T, String> {
+    tokio::task::spawn_blocking(f)
+        .await
+        .map_err(|e| format!("任务执行失败: {}", e))?
+}
+
+// ==================== 命令 ====================
+
+#[tauri::command]
+pub fn vault_status() -> Result<VaultStatus, String> {
+    expire_session();
+    let Some(vault) = read_vault()? else {
+        return Ok(VaultStatus {
+            exists: false,
+            unlocked: false,
+            entry_count: 0,
+            auto_lock_secs: DEFAULT_AUTO_LOCK_SECS,
+            locks_in_secs: None,
+        });
+    };
+    let guard = session();
+    let locks_in_secs = guard.as_ref().and_then(|session| {
+        session
+            .auto_lock
+            .map(|timeout| timeout.saturating_sub(session.last_used.elapsed()).as_secs())
+    });
+    Ok(VaultStatus {
+        exists: true,
+        unlocked: guard.is_some(),
+        entry_count: vault.entries.len(),
+        auto_lock_secs: vault.auto_lock_secs,
+        locks_in_secs,
+    })
+}
+
+/// 创建空保险库，并保持解锁状态
+#[tauri::command]
+pub async fn vault_create(master_password: String, auto_lock_secs: Option<u64>) -> Result<VaultStatus, String> {
+    validate_master_password(&master_password)?;
+    if read_vault()?.is_some() {
+        return Err("保险库已存在".to_string());
+    }
+    let auto_lock_secs = auto_lock_secs.unwrap_or(DEFAULT_AUTO_LOCK_SECS);
+    let (vault, key) = blocking(move || {
+        let kdf = new_kdf_params();
+        let key = derive_key(&master_password, &kdf)?;
+        let vault = VaultFile {
+            version: VAULT_VERSION,
+            kdf,
+            auto_lock_secs,
+            verifier: seal_verifier(&key, auto_lock_secs)?,
+            entries: Vec::new(),
+        };
+        Ok((vault, key))
+    })
+    .await?;
+    {
+        let mut guard = session();
+        // 派生密钥期间可能已被另一个窗口创建
+        if read_vault()?.is_some() {
+            return Err("保险库已存在".to_string());
+        }
+        write_vault(&vault)?;
+        begin_session(&mut guard, key, auto_lock_secs);
+    }
+    vault_status()
+}
+
+#[tauri::command]
+pub async fn vault_unlock(master_password: String) -> Result<VaultStatus, String> {
+    let vault = require_vault()?;
+    let (key, auto_lock_secs) = blocking(move || unlock_key(&vault, &master_password)).await?;
+    begin_session(&mut session(), key, auto_lock_secs);
+    vault_status()
+}
+
+fn begin_session(guard: &mut Option<Session>, key: Zeroizing<[u8; KEY_LEN]>, auto_lock_secs: u64) {
+    *guard = Some(Session {
+        key,
+        last_used: Instant::now(),
+        auto_lock: auto_lock_duration(auto_lock_secs),
+    });
+    start_watcher();
+}
+
+#[tauri::command]
+pub fn vault_lock() {
+    *session() = None;
+}
+
+/// 用新的盐重新派生密钥，并重新加密所有条目
+#[tauri::command]
+pub async fn vault_change_password(old_password: String, new_password: String) -> Result<VaultStatus, String> {
+    validate_master_password(&new_password)?;
+    let original = require_vault()?;
+    let vault = original.clone();
+    let (vault, key) = blocking(move || {
+        let (old_key, auto_lock_secs) = unlock_key(&vault, &old_password)?;
+        let entries = open_all(&old_key, &vault)?;
+        let kdf = new_kdf_params();
+        let key = derive_key(&new_password, &kdf)?;
+        let sealed = entries
+            .iter()
+            .map(|entry| seal_entry(&key, entry))
+            .collect::<Result<Vec<_>, String>>()?;
+        let vault = VaultFile {
+            kdf,
+            auto_lock_secs,
+            verifier: seal_verifier(&key, auto_lock_secs)?,
+            entries: sealed,
+            ..vault
+        };
+        Ok((vault, key))
+    })
+    .await?;
+    {
+        let mut guard = session();
+        // 重新加密期间若有其他修改写入，放弃本次结果，避免覆盖
+        let current = require_vault()?;
+        if current.verifier != original.verifier || current.entries != original.entries {
+            return Err("保险库在修改主密码期间发生了变化，请重试".to_string());
+        }
+        write_vault(&vault)?;
+        begin_session(&mut guard, key, vault.auto_lock_secs);
+    }
+    vault_status()
+}
+
+/// 设置空闲超时秒数，0 表示不自动锁定
+#[tauri::command]
+pub fn vault_set_auto_lock(secs: u64) -> Result<VaultStatus, String> {
+    update_vault(|session, vault| {
+        vault.auto_lock_secs = secs;
+        vault.verifier = seal_verifier(&session.key, secs)?;
+        session.auto_lock = auto_lock_duration(secs);
+        Ok(())
+    })?;
+    vault_status()
+}
+
+/// 按名称排序列出条目，可按搜索词和标签过滤
+#[tauri::command]
+pub fn vault_list_entries(query: Option<String>, tag: Option<String>) -> Result<Vec<VaultEntrySummary>, String> {
+    let terms: Vec<String> = query
+        .unwrap_or_default()
+        .split_whitespace()
+        .map(str::to_lowercase)
+        .collect();
+    let tag = non_empty(tag);
+    with_key(|key| {
+        let mut entries = open_all(key, &require_vault()?)?;
+        entries.retain(|entry| {
+            matches_query(entry, &terms) && tag.as_ref().is_none_or(|tag| entry.tags.contains(tag))
+        });
+        entries.sort_by_cached_key(|entry| entry.name.to_lowercase());
+        Ok(entries.iter().map(VaultEntrySummary::from).collect())
+    })
+}
+
+#[tauri::command]
+pub fn vault_get_entry(id: String) -> Result<VaultEntry, String> {
+    with_key(|key| {
+        let vault = require_vault()?;
+        let sealed = vault
+            .entries
+            .iter()
+            .find(|sealed| sealed.id == id)
+            .ok_or_else(|| "条目不存在".to_string())?;
+        open_entry(key, sealed)
+    })
+}
+
+#[tauri::command]
+pub fn vault_save_entry(entry: VaultEntryInput) -> Result<VaultEntry, String> {
+    update_vault(|session, vault| {
+        let key = &session.key;
+        let saved = match entry.id.clone().filter(|id| !id.is_empty()) {
+            Some(id) => {
+                let index = vault
+                    .entries
+                    .iter()
+                    .position(|sealed| sealed.id == id)
+                    .ok_or_else(|| "条目不存在".to_string())?;
+                let created_at = open_entry(key, &vault.entries[index])?.created_at;
+                let saved = build_entry(entry, id, Some(created_at))?;
+                vault.entries[index] = seal_entry(key, &saved)?;
+                saved
+            }
+            None => {
+                let saved = build_entry(entry, uuid::Uuid::new_v4().to_string(), None)?;
+                vault.entries.push(seal_entry(key, &saved)?);
+                saved
+            }
+        };
+        Ok(saved)
+    })
+}
+
+#[tauri::command]
+pub fn vault_delete_entry(id: String) -> Result<(), String> {
+    update_vault(|_, vault| {
+        let before = vault.entries.len();
+        vault.entries.retain(|sealed| sealed.id != id);
+        if vault.entries.len() == before {
+            return Err("条目不存在".to_string());
+        }
+        Ok(())
+    })
+}
+
+/// 所有标签及其条目数，按标签排序
+#[tauri::command]
+pub fn vault_list_tags() -> Result<Vec<VaultTag>, String> {
+    with_key(|key| {
+        let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+        for entry in open_all(key, &require_vault()?)? {
+            for tag in entry.tags {
+                *counts.entry(tag).or_default() += 1;
+            }
+        }
+        Ok(counts.into_iter().map(|(tag, count)| VaultTag { tag, count }).collect())
+    })
+}
+
+#[tauri::command]
+pub fn vault_import(path: String, format: VaultFileFormat) -> Result<VaultImportReport, String> {
+    let content = fs::read_to_string(&path).map_err(|e| format!("读取导入文件失败: {}", e))?;
+    let content = content.trim_start_matches('\u{feff}');
+    let (inputs, mut report) = match format {
+        VaultFileFormat::KeepassCsv => parse_keepass_csv(content)?,
+        VaultFileFormat::BitwardenJson => parse_bitwarden_json(content)?,
+    };
+    update_vault(|session, vault| {
+        let key = &session.key;
+        let mut existing: Vec<(String, String, String)> = open_all(key, vault)?
+            .into_iter()
+            .map(|e| (e.name, e.username, e.password))
+            .collect();
+        for input in inputs {
+            let entry = match build_entry(input, uuid::Uuid::new_v4().to_string(), None) {
+                Ok(entry) => entry,
+                Err(_) => {
+                    report.skipped += 1;
+                    continue;
+                }
+            };
+            let identity = (entry.name.clone(), entry.username.clone(), entry.password.clone());
+            if existing.contains(&identity) {
+                report.duplicates += 1;
+                continue;
+            }
+            vault.entries.push(seal_entry(key, &entry)?);
+            existing.push(identity);
+            report.imported += 1;
+        }
+        Ok(report)
+    })
+}
+
+/// 以明文导出所有条目。需要再次输入主密码，防止无人值守的已解锁会话被用来导出保险库。
+#[tauri::command]
+pub async fn vault_export(path: String, format: VaultFileFormat, master_password: String) -> Result<usize, String> {
+    let vault = require_vault()?;
+    let entries = blocking(move || {
+        let (key, _) = unlock_key(&vault, &master_password)?;
+        with_key(|_| open_all(&key, &vault))
+    })
+    .await?;
+    let content = match format {
+        VaultFileFormat::KeepassCsv => export_keepass_csv(&entries)?,
+        VaultFileFormat::BitwardenJson => export_bitwarden_json(&entries)?,
+    };
+    write_private(Path::new(&path), content.as_bytes()).map_err(|e| format!("写入导出文件失败: {}", e))?;
+    Ok(entries.len())
+}
+
+// ==================== KeePass CSV ====================
+
+/// KeePassXC（"Title"、"Username"、"URL" 等）与 KeePass 2
+/// （"Account"、"Login Name"、"Web Site"、"Comments"）使用的列名
+fn keepass_field(header: &str) -> Option<&'static str> {
+    match header.trim().to_lowercase().as_str() {
+        "title" | "account" | "name" => Some("name"),
+        "username" | "user name" | "login name" | "login" => Some("username"),
+        "password" => Some("password"),
+        "url" | "web site" | "website" => Some("url"),
+        "notes" | "comments" => Some("notes"),
+        "totp" | "otp" => Some("totp"),
+        "group" => Some("group"),
+        "tags" => Some("tags"),
+        _ => None,
+    }
+}
+
+fn parse_keepass_csv(content: &str) -> Result<(Vec<VaultEntryInput>, VaultImportReport), String> {
+    let mut reader = csv::ReaderBuilder::new().flexible(true).from_reader(content.as_bytes());
+    let headers = reader.headers().map_err(|e| format!("CSV 解析失败: {}", e))?.clone();
+    let fields: Vec<Option<&str>> = headers.iter().map(keepass_field).collect();
+    if !fields.contains(&Some("name")) || !fields.contains(&Some("password")) {
+        return Err("无法识别的 KeePass CSV：缺少 Title/Account 或 Password 列".to_string());
+    }
+
+    let mut inputs = Vec::new();
+    let mut report = VaultImportReport::default();
+    for (line, record) in reader.records().enumerate() {
+        let record = match record {
+            Ok(record) => record,
+            Err(e) => {
+                report.errors.push(format!("第 {} 行: {}", line + 2, e));
+                continue;
+            }
+        };
+        let mut input = VaultEntryInput::default();
+        for (field, value) in fields.iter().zip(record.iter()) {
+            match field {
+                Some("name") => input.name = value.to_string(),
+                Some("username") => input.username = value.to_string(),
+                Some("password") => input.password = value.to_string(),
+                Some("url") => input.urls.push(value.to_string()),
+                Some("notes") => input.notes = value.to_string(),
+                Some("totp") => input.totp = Some(value.to_string()),
+                // KeePassXC 把分组路径写成 "Root/Work/Mail"
+                Some("group") => input.tags.extend(
+                    value.split('/').filter(|g| !g.eq_ignore_ascii_case("root")).map(str::to_string),
+                ),
+                Some("tags") => input.tags.extend(value.split([',', ';']).map(str::to_string)),
+                _ => {}
+            }
+        }
+        inputs.push(input);
+    }
+    Ok((inputs, report))
+}
+
+/// KeePassXC 的导出列，另加它同样能读回的 "Tags"
+const KEEPASS_COLUMNS: [&str; 10] = [
+    "Group",
+    "Title",
+    "Username",
+    "Password",
+    "URL",
+    "Notes",
+    "TOTP",
+    "Tags",
+    "Last Modified",
+    "Created",
+];
+
+fn export_keepass_csv(entries: &[VaultEntry]) -> Result<String, String> {
+    let mut writer = csv::WriterBuilder::new().from_writer(Vec::new());
+    let err = |e: csv::Error| format!("生成 CSV 失败: {}", e);
+    writer.write_record(KEEPASS_COLUMNS).map_err(err)?;
+    for entry in entries {
+        // CSV 只有一个 URL 列，其余 URL 保留在备注中
+        let mut notes = entry.notes.clone();
+        if entry.urls.len() > 1 {
+            if !notes.is_empty() {
+                notes.push('\n');
+            }
+            notes.push_str(&entry.urls[1..].join("\n"));
+        }
+        writer
+            .write_record([
+                entry.tags.first().map(String::as_str).unwrap_or("Root"),
+                &entry.name,
+                &entry.username,
+                &entry.password,
+                entry.urls.first().map(String::as_str).unwrap_or(""),
+                &notes,
+                entry.totp.as_deref().unwrap_or(""),
+                &entry.tags.join(","),
+                &entry.updated_at,
+                &entry.created_at,
+            ])
+            .map_err(err)?;
+    }
+    let bytes = writer.into_inner().map_err(|e| format!("生成 CSV 失败: {}", e))?;
+    String::from_utf8(bytes).map_err(|e| format!("生成 CSV 失败: {}", e))
+}
+
+// ==================== Bitwarden JSON ====================
+
+/// Bitwarden 条目类型: 1 登录，2 安全笔记，3 银行卡，4 身份信息
+const BITWARDEN_LOGIN: u64 = 1;
+const BITWARDEN_NOTE: u64 = 2;
+
+fn parse_bitwarden_json(content: &str) -> Result<(Vec<VaultEntryInput>, VaultImportReport), String> {
+    let root: Value = serde_json::from_str(content).map_err(|e| format!("Bitwarden JSON 解析失败: {}", e))?;
+    if root["encrypted"].as_bool() == Some(true) {
+        return Err("这是加密的 Bitwarden 导出，请在 Bitwarden 中导出为未加密的 JSON".to_string());
+    }
+    let items = root["items"]
+        .as_array()
+        .ok_or_else(|| "无法识别的 Bitwarden JSON：缺少 items".to_string())?;
+    let folders: BTreeMap<&str, &str> = root["folders"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|folder| Some((folder["id"].as_str()?, folder["name"].as_str()?)))
+        .collect();
+    let text = |value: &Value| value.as_str().unwrap_or("").to_string();
+
+    let mut inputs = Vec::new();
+    let mut report = VaultImportReport::default();
+    for item in items {
+        let kind = item["type"].as_u64().unwrap_or(0);
+        if kind != BITWARDEN_LOGIN && kind != BITWARDEN_NOTE {
+            report.skipped += 1;
+            continue;
+        }
+        let login = &item["login"];
+        let mut input = VaultEntryInput {
+            name: text(&item["name"]),
+            username: text(&login["username"]),
+            password: text(&login["password"]),
+            notes: text(&item["notes"]),
+            totp: login["totp"].as_str().map(str::to_string),
+            urls: login["uris"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|uri| uri["uri"].as_str().map(str::to_string))
+                .collect(),
+            ..VaultEntryInput::default()
+        };
+        if let Some(folder) = item["folderId"].as_str().and_then(|id| folders.get(id)) {
+            input.tags.push(folder.to_string());
+        }
+        if item["favorite"].as_bool() == Some(true) {
+            input.tags.push("收藏".to_string());
+        }
+        // 自定义字段没有对应项，以可读形式保留在备注中
+        for field in item["fields"].as_array().into_iter().flatten() {
+            if !input.notes.is_empty() {
+                input.notes.push('\n');
+            }
+            input.notes.push_str(&format!("{}: {}", text(&field["name"]), text(&field["value"])));
+        }
+        inputs.push(input);
+    }
+    Ok((inputs, report))
+}
+
+fn export_bitwarden_json(entries: &[VaultEntry]) -> Result<String, String> {
+    let mut folder_ids: BTreeMap<&str, String> = BTreeMap::new();
+    for entry in entries {
+        if let Some(tag) = entry.tags.first() {
+            folder_ids
+                .entry(tag.as_str())
+                .or_insert_with(|| uuid::Uuid::new_v4().to_string());
+        }
+    }
+    let folders: Vec<Value> = folder_ids
+        .iter()
+        .map(|(name, id)| json!({ "id": id, "name": name }))
+        .collect();
+    let items: Vec<Value> = entries
+        .iter()
+        .map(|entry| {
+            json!({
+                "id": entry.id,
+                "type": BITWARDEN_LOGIN,
+                "name": entry.name,
+                "notes": (!entry.notes.is_empty()).then_some(&entry.notes),
+                "folderId": entry.tags.first().and_then(|tag| folder_ids.get(tag.as_str())),
+                "favorite": false,
+                "login": {
+                    "username": entry.username,
+                    "password": entry.password,
+                    "totp": entry.totp,
+                    "uris": entry.urls.iter().map(|uri| json!({ "match": null, "uri": uri })).collect::<Vec<_>>(),
+                },
+                "creationDate": entry.created_at,
+                "revisionDate": entry.updated_at,
+            })
+        })
+        .collect();
+    serde_json::to_string_pretty(&json!({ "encrypted": false, "folders": folders, "items": items }))
+        .map_err(|e| format!("生成 JSON 失败: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 会话与保险库路径都是进程级全局状态，整个生命周期放在同一个测试里顺序执行
+    #[tokio::test]
+    async fn vault_lifecycle() {
+        let dir = std::env::temp_dir().join(format!("tbox-vault-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        VAULT_PATH.set(dir.join("vault.json")).unwrap();
+
+        let status = vault_create("correct horse".to_string(), Some(60)).await.unwrap();
+        assert!(status.exists && status.unlocked);
+        assert!(vault_create("correct horse".to_string(), None).await.is_err());
+
+        let saved = vault_save_entry(VaultEntryInput {
+            name: "GitHub".to_string(),
+            username: "octocat".to_string(),
+            password: "s3cret-Pa55".to_string(),
+            tags: vec!["work".to_string()],
+            ..Default::default()
+        })
+        .unwrap();
+        let raw = fs::read_to_string(vault_path().unwrap()).unwrap();
+        assert!(!raw.contains("s3cret-Pa55") && !raw.contains("octocat"));
+
+        vault_lock();
+        assert!(!vault_status().unwrap().unlocked);
+        assert!(vault_get_entry(saved.id.clone()).is_err());
+        assert_eq!(vault_unlock("wrong password".to_string()).await.unwrap_err(), "主密码错误");
+
+        vault_unlock("correct horse".to_string()).await.unwrap();
+        let entry = vault_get_entry(saved.id.clone()).unwrap();
+        assert_eq!(entry.password, "s3cret-Pa55");
+        assert_eq!(entry.username, "octocat");
+
+        // 篡改明文中的自动锁定时间不影响实际生效的值
+        vault_set_auto_lock(120).unwrap();
+        let mut file = require_vault().unwrap();
+        file.auto_lock_secs = 0;
+        write_vault(&file).unwrap();
+        vault_lock();
+        vault_unlock("correct horse".to_string()).await.unwrap();
+        assert_eq!(session().as_ref().unwrap().auto_lock, Some(Duration::from_secs(120)));
+
+        // 篡改条目密文后解密失败
+        let mut file = require_vault().unwrap();
+        file.entries[0].id = "other".to_string();
+        write_vault(&file).unwrap();
+        assert!(vault_get_entry("other".to_string()).is_err());
+
+        vault_lock();
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
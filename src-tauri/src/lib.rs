@@ -133,10 +133,19 @@ pub fn run() {
             commands::json::get_json_info,
             commands::json::compare_json,
             commands::json::json_to_query_params,
+            commands::json::query_params_to_json,
+            commands::json::repair_json,
+            commands::json::format_json_file,
+            commands::json::compress_json_file,
+            commands::json::validate_json_file,
+            commands::json::get_json_info_file,
+            commands::json::json_tree_children,
 
             // 编码工具
             commands::encoding::url_encode,
             commands::encoding::url_decode,
+            commands::encoding::parse_url,
+            commands::encoding::build_url,
             commands::encoding::unicode_to_chinese,
             commands::encoding::chinese_to_unicode,
             commands::encoding::html_encode,
@@ -145,12 +154,108 @@ pub fn run() {
             commands::encoding::base58_decode,
             commands::encoding::base62_encode,
             commands::encoding::base62_decode,
+            commands::encoding::base_n_encode,
+            commands::encoding::base_n_decode,
+            commands::encoding::base64_encode,
+            commands::encoding::base64_decode,
+            commands::encoding::base32_encode,
+            commands::encoding::base32_decode,
+            commands::encoding::base45_encode,
+            commands::encoding::base45_decode,
+            commands::encoding::base85_encode,
+            commands::encoding::base85_decode,
+            commands::encoding::magic_decode,
             commands::encoding::hex_to_string,
             commands::encoding::string_to_hex,
             commands::encoding::punycode_encode,
             commands::encoding::punycode_decode,
             commands::encoding::binary_to_hex,
             commands::encoding::hex_to_binary,
+            commands::charset_tools::detect_charset,
+            commands::charset_tools::transcode,
+            commands::charset_tools::transcode_files,
+            commands::unicode_tools::inspect_unicode,
+            commands::unicode_tools::normalize_unicode,
+            commands::unicode_tools::escape_unicode,
+            commands::number_tools::convert_base,
+            commands::number_tools::dec_to_hex,
+            commands::number_tools::dec_to_binary,
+            commands::number_tools::dec_to_octal,
+            commands::number_tools::hex_to_dec,
+            commands::number_tools::binary_to_dec,
+            commands::number_tools::octal_to_dec,
+            commands::number_tools::twos_complement,
+            commands::number_tools::decode_float,
+            commands::number_tools::swap_endian,
+            commands::number_tools::scientific_to_decimal,
+            commands::number_tools::to_roman,
+            commands::number_tools::from_roman,
+            commands::chinese_tools::to_pinyin,
+            commands::chinese_tools::convert_chinese,
+            commands::chinese_tools::convert_width,
+            commands::chinese_tools::segment_text,
+            commands::chinese_tools::word_frequency,
+            commands::hash::hash,
+            commands::hash::hash_files,
+            commands::hash::verify_checksum,
+            commands::hash::verify_manifest,
+            commands::crypto::aes_encrypt,
+            commands::crypto::aes_decrypt,
+            commands::crypto::rsa_generate_keypair,
+            commands::crypto::rsa_convert_key,
+            commands::crypto::rsa_encrypt,
+            commands::crypto::rsa_decrypt,
+            commands::crypto::rsa_sign,
+            commands::crypto::rsa_verify,
+            commands::crypto::ec_generate_keypair,
+            commands::crypto::ec_convert_key,
+            commands::crypto::ec_sign,
+            commands::crypto::ec_verify,
+            commands::crypto::ssh_key_inspect,
+            commands::gm_crypto::sm3_hash,
+            commands::gm_crypto::hmac_sm3,
+            commands::gm_crypto::generate_sm4_key,
+            commands::gm_crypto::sm4_encrypt,
+            commands::gm_crypto::sm4_decrypt,
+            commands::gm_crypto::generate_sm2_keypair,
+            commands::gm_crypto::sm2_public_key,
+            commands::gm_crypto::sm2_encrypt,
+            commands::gm_crypto::sm2_decrypt,
+            commands::gm_crypto::sm2_sign,
+            commands::gm_crypto::sm2_verify,
+            commands::jwt::decode_jwt,
+            commands::jwt::verify_jwt,
+            commands::jwt::sign_jwt,
+            commands::vault::vault_status,
+            commands::vault::vault_create,
+            commands::vault::vault_unlock,
+            commands::vault::vault_lock,
+            commands::vault::vault_change_password,
+            commands::vault::vault_set_auto_lock,
+            commands::vault::vault_list_entries,
+            commands::vault::vault_get_entry,
+            commands::vault::vault_save_entry,
+            commands::vault::vault_delete_entry,
+            commands::vault::vault_list_tags,
+            commands::vault::vault_import,
+            commands::vault::vault_export,
+            commands::password_tools::generate_password,
+            commands::password_tools::generate_passwords,
+            commands::password_tools::password_strength,
+            commands::otp::generate_otp,
+            commands::otp::validate_otp,
+            commands::otp::generate_otp_secret,
+            commands::otp::parse_otp_uri,
+            commands::otp::build_otp_uri,
+            commands::otp::render_otp_qr,
+            commands::x509::parse_certificates,
+            commands::x509::parse_csr,
+            commands::x509::verify_certificate_chain,
+            commands::x509::generate_certificate,
+            commands::x509::sign_csr,
+            commands::compression::compress,
+            commands::compression::decompress,
+            commands::compression::compare_compression,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");